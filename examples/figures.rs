@@ -0,0 +1,25 @@
+// regenerates the figures of the README in assets/:
+// cargo run --example figures
+use mpc_zk_paillier::curve::ecc::{CurveParams, Point};
+use mpc_zk_paillier::plot;
+use num_bigint::BigInt;
+use std::fs;
+
+fn point(x: i64, y: i64) -> Point {
+    Point::new(BigInt::from(x), BigInt::from(y))
+}
+
+fn main() -> Result<(), String> {
+    let new_ec = CurveParams::e17().curve;
+    let figures = [
+        ("assets/e17_points.svg", plot::plot_curve(&new_ec)?),
+        ("assets/e17_chord.svg", plot::plot_addition(&new_ec, &point(7, 9), &point(10, 16))?),
+        ("assets/e17_tangent.svg", plot::plot_addition(&new_ec, &point(7, 9), &point(7, 9))?),
+        ("assets/e17_orbit.svg", plot::plot_orbit(&new_ec, &point(7, 9))?),
+    ];
+    for (path, svg) in figures {
+        fs::write(path, svg).map_err(|e| format!("{}: {}", path, e))?;
+        println!("wrote {}", path);
+    }
+    Ok(())
+}
@@ -0,0 +1,54 @@
+// compares the scalar multiplications of EcWei on the standard curves:
+// cargo run --release --example scalar_mul_bench
+use mpc_zk_paillier::arithmetic::basic_op::random_scalar;
+use mpc_zk_paillier::curve::ecc::{CurveParams, FixedBaseTable, Point};
+use num_bigint::BigInt;
+use std::time::{Duration, Instant};
+
+const ROUNDS: u32 = 100;
+
+// average time of f over ROUNDS fresh random scalars, after a few rounds of warm-up
+fn bench(n: &BigInt, f: impl Fn(&BigInt) -> Point) -> Duration {
+    let scalars: Vec<BigInt> = (0..ROUNDS).map(|_| random_scalar(n)).collect();
+    for k in scalars.iter().take(5) {
+        std::hint::black_box(f(k));
+    }
+    let start = Instant::now();
+    for k in scalars.iter() {
+        std::hint::black_box(f(k));
+    }
+    start.elapsed() / ROUNDS
+}
+
+fn main() {
+    for params in [CurveParams::secp256k1(), CurveParams::secp256r1(), CurveParams::secp384r1()] {
+        let (curve, point_g, n) = (&params.curve, &params.point_g, &params.n);
+        println!("\n {} ({} bits)", params.name, n.bits());
+
+        let start = Instant::now();
+        let table = FixedBaseTable::new(curve, point_g, n, 4).unwrap();
+        println!("   FixedBaseTable::new (w = 4)     {:>12.2?}", start.elapsed());
+
+        let base = bench(n, |k| curve.scalar_mul(point_g, k));
+        report("scalar_mul(G)", base, base);
+        report("scalar_mul_ct(G)", bench(n, |k| curve.scalar_mul_ct(point_g, k)), base);
+        report("FixedBaseTable::mul (w = 4)", bench(n, |k| table.mul(k)), base);
+        for window in [6, 8] {
+            let table = FixedBaseTable::new(curve, point_g, n, window).unwrap();
+            let label = format!("FixedBaseTable::mul (w = {})", window);
+            report(&label, bench(n, |k| table.mul(k)), base);
+        }
+
+        // variable base: a random point Q
+        let point_q = curve.scalar_mul(point_g, &random_scalar(n));
+        let base = bench(n, |k| curve.scalar_mul(&point_q, k));
+        println!();
+        report("scalar_mul(Q)", base, base);
+        report("scalar_mul_wnaf(Q)", bench(n, |k| curve.scalar_mul_wnaf(&point_q, k)), base);
+    }
+}
+
+// time and speedup against the double-and-add baseline
+fn report(label: &str, time: Duration, base: Duration) {
+    println!("   {:<32}{:>12.2?}   x{:.2}", label, time, base.as_secs_f64() / time.as_secs_f64());
+}
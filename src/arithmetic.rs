@@ -1,5 +1,5 @@
-use num_bigint::BigInt;
-use num_traits::{One, Zero};
+use num_bigint::{BigInt, BigUint, RandBigInt};
+use num_traits::{One, Signed, Zero};
 
 pub mod basic_op {
     use super::*;
@@ -16,31 +16,532 @@ pub mod basic_op {
         (a * b) / gcd(a, b)
     }
 
+    // (g, s, t) with s a + t b = g = gcd(a, b) >= 0
+    pub fn ext_gcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+        let (mut old_r, mut r) = (a.clone(), b.clone());
+        let (mut old_s, mut s) = (BigInt::one(), BigInt::zero());
+        let (mut old_t, mut t) = (BigInt::zero(), BigInt::one());
+        while !r.is_zero() {
+            let q = &old_r / &r;
+            let next_r = &old_r - &q * &r;
+            old_r = std::mem::replace(&mut r, next_r);
+            let next_s = &old_s - &q * &s;
+            old_s = std::mem::replace(&mut s, next_s);
+            let next_t = &old_t - &q * &t;
+            old_t = std::mem::replace(&mut t, next_t);
+        }
+        if old_r.is_negative() {
+            (-old_r, -old_s, -old_t)
+        } else {
+            (old_r, old_s, old_t)
+        }
+    }
+
+    // x mod n in [0, n), negative x included
+    pub fn reduce(x: &BigInt, n: &BigInt) -> BigInt {
+        ((x % n) + n) % n
+    }
+
     pub fn inv_mod(x: &BigInt, p: &BigInt) -> Option<BigInt> {
         if x == &BigInt::zero() { return Some(BigInt::zero()) };
-        // if x is negative, adjust it by adding p
-        let mut x = x.clone();
-        if x < BigInt::zero() {
-            x += p;
+        // reduce x into [0, p) so that negative inputs work too
+        let x = reduce(x, p);
+
+        // extended Euclidean algorithm: keeps old_s * x = old_r (mod p)
+        let (mut old_r, mut r) = (x, p.clone());
+        let (mut old_s, mut s) = (BigInt::one(), BigInt::zero());
+        while !r.is_zero() {
+            let q = &old_r / &r;
+            let next_r = &old_r - &q * &r;
+            old_r = std::mem::replace(&mut r, next_r);
+            let next_s = &old_s - &q * &s;
+            old_s = std::mem::replace(&mut s, next_s);
+        }
+
+        if !old_r.is_one() {
+            return None; // x and p are not coprime, so there is no inverse
+        }
+        Some(reduce(&old_s, p))
+    }
+
+    pub fn div_mod(x: &BigInt, p: &BigInt) -> Option<BigInt> {
+        inv_mod(x, p).map(|inv| (x.clone() * inv) % p)
+    }
+
+    // Legendre symbol (a / p) for an odd prime p: 1, -1 or 0
+    pub fn legendre(a: &BigInt, p: &BigInt) -> i32 {
+        let a = reduce(a, p);
+        if a.is_zero() {
+            return 0;
         }
+        let e = (p - BigInt::one()) / 2;
+        if a.modpow(&e, p).is_one() {
+            1
+        } else {
+            -1
+        }
+    }
 
-        // finding the multiplicative inverse using exhaustive search
-        let mut i = BigInt::one();
-        while &i < p {
-            if (x.clone() * &i) % p == BigInt::one() {
-                return Some(i.clone()); // if found, return it
+    // square root of a modulo an odd prime p (Tonelli-Shanks), the smaller of the two roots
+    pub fn sqrt_mod(a: &BigInt, p: &BigInt) -> Option<BigInt> {
+        let a = reduce(a, p);
+        if a.is_zero() {
+            return Some(BigInt::zero());
+        }
+        if legendre(&a, p) != 1 {
+            return None;
+        }
+        let one = BigInt::one();
+        let two = BigInt::from(2);
+        let root = if (p % 4u32) == BigInt::from(3) {
+            a.modpow(&((p + &one) / 4), p)
+        } else {
+            // p - 1 = q * 2^s with q odd
+            let mut q = p - &one;
+            let mut s = 0u32;
+            while (&q % &two).is_zero() {
+                q /= 2;
+                s += 1;
+            }
+            let mut z = two.clone();
+            while legendre(&z, p) != -1 {
+                z += 1;
+            }
+            let mut m = s;
+            let mut c = z.modpow(&q, p);
+            let mut t = a.modpow(&q, p);
+            let mut r = a.modpow(&((&q + &one) / 2), p);
+            while !t.is_one() {
+                // least i with t^(2^i) = 1
+                let mut i = 0u32;
+                let mut t2 = t.clone();
+                while !t2.is_one() {
+                    t2 = (&t2 * &t2) % p;
+                    i += 1;
+                }
+                let b = c.modpow(&(BigInt::one() << (m - i - 1)), p);
+                m = i;
+                c = (&b * &b) % p;
+                t = (t * &c) % p;
+                r = (r * b) % p;
             }
-            i += BigInt::one(); // Increment i
+            r
+        };
+        let other = p - &root;
+        Some(if root < other { root } else { other })
+    }
+
+    // x, y > 0 with x^2 + d y^2 = m for a prime m and 0 < d < m (Cornacchia), if there are
+    // any: Euclid on m and a root of -d above m / 2 stops at the first remainder below sqrt(m)
+    pub fn cornacchia(d: &BigInt, m: &BigInt) -> Option<(BigInt, BigInt)> {
+        let root = sqrt_mod(&(m - d), m)?;
+        let (mut r0, mut r1) = (m.clone(), m - root);
+        while &r1 * &r1 >= *m {
+            let r2 = &r0 % &r1;
+            r0 = std::mem::replace(&mut r1, r2);
+        }
+        let rest = m - &r1 * &r1;
+        if !(&rest % d).is_zero() {
+            return None;
+        }
+        let y = (&rest / d).sqrt();
+        if &y * &y * d == rest && !y.is_zero() {
+            Some((r1, y))
+        } else {
+            None
         }
+    }
 
-        None // if no inverse is found, return None
+    // uniformly random scalar in [1, n - 1]
+    pub fn random_scalar(n: &BigInt) -> BigInt {
+        let mut rng = rand::thread_rng();
+        rng.gen_bigint_range(&BigInt::one(), n)
     }
 
-    pub fn div_mod(x: &BigInt, p: &BigInt) -> Option<BigInt> {
-        match inv_mod(x, p) {
-            Some(inv) => Some((x.clone() * inv) % p),
-            None => None,
+    // uniform in [0, n): simulated challenges and responses must range over the same set as
+    // real ones, which can be 0
+    pub fn random_mod(n: &BigInt) -> BigInt {
+        let mut rng = rand::thread_rng();
+        rng.gen_bigint_range(&BigInt::zero(), n)
+    }
+
+    const SMALL_PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+    // Miller-Rabin: the first thirteen primes as bases are deterministic below 3.3 * 10^24
+    // (twelve only reach 3.18 * 10^23), above 80 bits 16 random bases are added
+    pub fn is_probable_prime(n: &BigInt) -> bool {
+        let one = BigInt::one();
+        let two = BigInt::from(2);
+        if n < &two {
+            return false;
+        }
+        for prime in SMALL_PRIMES {
+            if (n % prime).is_zero() {
+                return n == &BigInt::from(prime);
+            }
+        }
+        // n - 1 = d * 2^s with d odd
+        let n_minus_one = n - &one;
+        let s = n_minus_one.trailing_zeros().unwrap_or(0);
+        let d = &n_minus_one >> s;
+
+        let mut bases: Vec<BigInt> = SMALL_PRIMES.iter().map(|prime| BigInt::from(*prime)).collect();
+        if n.bits() > 80 {
+            let mut rng = rand::thread_rng();
+            bases.extend((0..16).map(|_| rng.gen_bigint_range(&two, &n_minus_one)));
+        }
+        'witness: for base in bases {
+            let mut x = base.modpow(&d, n);
+            if x == one || x == n_minus_one {
+                continue;
+            }
+            for _ in 1..s {
+                x = (&x * &x) % n;
+                if x == n_minus_one {
+                    continue 'witness;
+                }
+            }
+            return false;
+        }
+        true
+    }
+
+    // random prime of exactly `bits` bits
+    pub fn random_prime(bits: u64) -> BigInt {
+        let mut rng = rand::thread_rng();
+        loop {
+            let candidate = rng.gen_biguint(bits) | (BigUint::one() << (bits - 1)) | BigUint::one();
+            let candidate = BigInt::from(candidate);
+            if is_probable_prime(&candidate) {
+                return candidate;
+            }
+        }
+    }
+
+    // a non-trivial factor of a composite n (Pollard's rho with Brent's cycle detection)
+    pub fn pollard_rho(n: &BigInt) -> BigInt {
+        let one = BigInt::one();
+        if (n % 2u32).is_zero() {
+            return BigInt::from(2);
+        }
+        let mut rng = rand::thread_rng();
+        loop {
+            let c = rng.gen_bigint_range(&one, n);
+            let f = |x: &BigInt| (x * x + &c) % n;
+            let mut y = rng.gen_bigint_range(&BigInt::zero(), n);
+            let mut r = 1u64;
+            let mut q = one.clone();
+            let mut g = one.clone();
+            let mut x = y.clone();
+            let mut ys = y.clone();
+            // products of 128 differences share one gcd
+            while g.is_one() {
+                x = y.clone();
+                for _ in 0..r {
+                    y = f(&y);
+                }
+                let mut k = 0u64;
+                while k < r && g.is_one() {
+                    ys = y.clone();
+                    for _ in 0..128.min(r - k) {
+                        y = f(&y);
+                        q = (q * (&x - &y).abs()) % n;
+                    }
+                    g = gcd(&q, n);
+                    k += 128;
+                }
+                r *= 2;
+            }
+            if &g == n {
+                // the batch overshot: step back one difference at a time
+                loop {
+                    ys = f(&ys);
+                    g = gcd(&(&x - &ys).abs(), n);
+                    if !g.is_one() {
+                        break;
+                    }
+                }
+            }
+            if &g != n {
+                return g;
+            }
+        }
+    }
+
+    // the positive divisors of n > 0 in increasing order
+    pub fn divisors(n: &BigInt) -> Vec<BigInt> {
+        let mut divisors = vec![BigInt::one()];
+        for (prime, e) in factorize(n) {
+            let mut next = Vec::with_capacity(divisors.len() * (e as usize + 1));
+            for d in &divisors {
+                let mut power = d.clone();
+                for _ in 0..=e {
+                    next.push(power.clone());
+                    power *= &prime;
+                }
+            }
+            divisors = next;
+        }
+        divisors.sort();
+        divisors
+    }
+
+    // prime factorization of n > 0 as (prime, exponent) pairs in increasing order
+    pub fn factorize(n: &BigInt) -> Vec<(BigInt, u32)> {
+        let mut primes = Vec::new();
+        let mut rest = n.abs();
+        // trial division takes care of the small factors
+        let mut d = 2u32;
+        while d < 1000 && rest > BigInt::one() {
+            while (&rest % d).is_zero() {
+                rest /= d;
+                primes.push(BigInt::from(d));
+            }
+            d += 1;
+        }
+        let mut stack = vec![rest];
+        while let Some(m) = stack.pop() {
+            if m.is_one() {
+                continue;
+            }
+            if is_probable_prime(&m) {
+                primes.push(m);
+            } else {
+                let factor = pollard_rho(&m);
+                stack.push(&m / &factor);
+                stack.push(factor);
+            }
         }
+        primes.sort();
+        let mut factors: Vec<(BigInt, u32)> = Vec::new();
+        for prime in primes {
+            match factors.last_mut() {
+                Some((last, e)) if *last == prime => *e += 1,
+                _ => factors.push((prime, 1)),
+            }
+        }
+        factors
+    }
+
+}
+
+// polynomials over F_p, as coefficient vectors from the constant term up with no
+// trailing zeros (the zero polynomial is empty); every coefficient lies in [0, p)
+pub mod poly_op {
+    use super::*;
+    use super::basic_op::reduce;
+
+    pub type Poly = Vec<BigInt>;
+
+    pub fn trim(mut a: Poly) -> Poly {
+        while a.last().is_some_and(|c| c.is_zero()) {
+            a.pop();
+        }
+        a
+    }
+
+    // coefficients given from the constant term up, reduced modulo p
+    pub fn from_coefficients(coefficients: &[BigInt], p: &BigInt) -> Poly {
+        trim(coefficients.iter().map(|c| reduce(c, p)).collect())
+    }
+
+    // degree of a non-zero polynomial
+    pub fn degree(a: &Poly) -> usize {
+        a.len().saturating_sub(1)
+    }
+
+    pub fn add(a: &Poly, b: &Poly, p: &BigInt) -> Poly {
+        let len = a.len().max(b.len());
+        let zero = BigInt::zero();
+        trim(
+            (0..len)
+                .map(|i| (a.get(i).unwrap_or(&zero) + b.get(i).unwrap_or(&zero)) % p)
+                .collect(),
+        )
     }
 
+    pub fn sub(a: &Poly, b: &Poly, p: &BigInt) -> Poly {
+        add(a, &scale(b, &BigInt::from(-1), p), p)
+    }
+
+    pub fn scale(a: &Poly, k: &BigInt, p: &BigInt) -> Poly {
+        let k = reduce(k, p);
+        trim(a.iter().map(|c| (c * &k) % p).collect())
+    }
+
+    pub fn mul(a: &Poly, b: &Poly, p: &BigInt) -> Poly {
+        if a.is_empty() || b.is_empty() {
+            return Poly::new();
+        }
+        let mut out = vec![BigInt::zero(); a.len() + b.len() - 1];
+        for (i, a_i) in a.iter().enumerate() {
+            if a_i.is_zero() {
+                continue;
+            }
+            for (j, b_j) in b.iter().enumerate() {
+                out[i + j] += a_i * b_j;
+            }
+        }
+        trim(out.into_iter().map(|c| c % p).collect())
+    }
+
+    // quotient and remainder of a by a non-zero m
+    pub fn div_rem(a: &Poly, m: &Poly, p: &BigInt) -> (Poly, Poly) {
+        let mut rem = a.clone();
+        if rem.len() < m.len() {
+            return (Poly::new(), rem);
+        }
+        let lead_inv = basic_op::inv_mod(m.last().unwrap(), p).unwrap();
+        let mut quot = vec![BigInt::zero(); rem.len() - m.len() + 1];
+        while rem.len() >= m.len() {
+            let shift = rem.len() - m.len();
+            let coeff = (rem.last().unwrap() * &lead_inv) % p;
+            for (i, m_i) in m.iter().enumerate() {
+                rem[shift + i] = reduce(&(&rem[shift + i] - &coeff * m_i), p);
+            }
+            quot[shift] = coeff;
+            rem = trim(rem);
+        }
+        (trim(quot), rem)
+    }
+
+    pub fn rem(a: &Poly, m: &Poly, p: &BigInt) -> Poly {
+        div_rem(a, m, p).1
+    }
+
+    pub fn mul_mod(a: &Poly, b: &Poly, m: &Poly, p: &BigInt) -> Poly {
+        rem(&mul(a, b, p), m, p)
+    }
+
+    // a^e mod m by square-and-multiply
+    pub fn pow_mod(a: &Poly, e: &BigInt, m: &Poly, p: &BigInt) -> Poly {
+        let mut result = rem(&vec![BigInt::one()], m, p);
+        let base = rem(a, m, p);
+        for i in (0..e.bits()).rev() {
+            result = mul_mod(&result, &result, m, p);
+            if e.bit(i) {
+                result = mul_mod(&result, &base, m, p);
+            }
+        }
+        result
+    }
+
+    pub fn monic(a: &Poly, p: &BigInt) -> Poly {
+        match a.last() {
+            Some(lead) => scale(a, &basic_op::inv_mod(lead, p).unwrap(), p),
+            None => Poly::new(),
+        }
+    }
+
+    // monic gcd of a and b
+    pub fn gcd(a: &Poly, b: &Poly, p: &BigInt) -> Poly {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        while !b.is_empty() {
+            let r = rem(&a, &b, p);
+            a = b;
+            b = r;
+        }
+        monic(&a, p)
+    }
+
+    // inverse of a modulo m; when there is none, the non-trivial monic gcd(a, m) is
+    // returned instead as the error
+    pub fn inv_mod(a: &Poly, m: &Poly, p: &BigInt) -> Result<Poly, Poly> {
+        // extended Euclid: s * a = r (mod m) is kept for both rows
+        let (mut old_r, mut r) = (rem(a, m, p), m.clone());
+        let (mut old_s, mut s) = (vec![BigInt::one()], Poly::new());
+        while !r.is_empty() {
+            let (q, next_r) = div_rem(&old_r, &r, p);
+            old_r = std::mem::replace(&mut r, next_r);
+            let next_s = sub(&old_s, &mul(&q, &s, p), p);
+            old_s = std::mem::replace(&mut s, next_s);
+        }
+        if old_r.len() == 1 {
+            let inv = basic_op::inv_mod(&old_r[0], p).unwrap();
+            Ok(rem(&scale(&old_s, &inv, p), m, p))
+        } else {
+            Err(monic(&old_r, p))
+        }
+    }
+
+    // the distinct roots of a in F_p (p an odd prime), in increasing order: gcd(a, x^p - x)
+    // keeps the linear factors, which are split by Cantor-Zassenhaus, i.e. by
+    // gcd(g, (x + delta)^((p - 1) / 2) - 1) for random delta
+    pub fn roots(a: &Poly, p: &BigInt) -> Vec<BigInt> {
+        let a = monic(&trim(a.iter().map(|c| reduce(c, p)).collect()), p);
+        if degree(&a) == 0 {
+            return Vec::new();
+        }
+        let x = vec![BigInt::zero(), BigInt::one()];
+        let split = gcd(&a, &sub(&pow_mod(&x, p, &a, p), &x, p), p);
+        let mut roots = Vec::new();
+        let mut pending = vec![split];
+        let half = (p - 1) / 2;
+        while let Some(g) = pending.pop() {
+            match degree(&g) {
+                0 => {}
+                1 => roots.push(reduce(&-&g[0], p)),
+                _ => {
+                    let delta = basic_op::random_scalar(p);
+                    let shifted = vec![delta, BigInt::one()];
+                    let h = gcd(&g, &sub(&pow_mod(&shifted, &half, &g, p), &vec![BigInt::one()], p), p);
+                    if degree(&h) == 0 || degree(&h) == degree(&g) {
+                        pending.push(g);
+                    } else {
+                        pending.push(div_rem(&g, &h, p).0);
+                        pending.push(h);
+                    }
+                }
+            }
+        }
+        roots.sort();
+        roots
+    }
+
+    // a(x) by Horner's rule
+    pub fn eval(a: &Poly, x: &BigInt, p: &BigInt) -> BigInt {
+        a.iter().rev().fold(BigInt::zero(), |acc, c| reduce(&(acc * x + c), p))
+    }
+
+    pub fn derivative(a: &Poly, p: &BigInt) -> Poly {
+        trim(
+            a.iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, c)| (c * BigInt::from(i)) % p)
+                .collect(),
+        )
+    }
+
+    // the monic q of least degree L with q_0 s_n + ... + q_L s_(n+L) = 0 for all n, i.e. the
+    // denominator of sum s_n x^-(n+1) (Berlekamp-Massey); 2L terms of the sequence suffice
+    pub fn min_poly(sequence: &[BigInt], p: &BigInt) -> Poly {
+        // connection polynomial c with s_n + c_1 s_(n-1) + ... + c_L s_(n-L) = 0
+        let (mut c, mut prev) = (vec![BigInt::one()], vec![BigInt::one()]);
+        let (mut len, mut shift, mut prev_discrepancy) = (0usize, 1usize, BigInt::one());
+        for n in 0..sequence.len() {
+            let discrepancy = reduce(
+                &(0..=len).fold(BigInt::zero(), |acc, i| acc + c.get(i).unwrap_or(&BigInt::zero()) * &sequence[n - i]),
+                p,
+            );
+            if discrepancy.is_zero() {
+                shift += 1;
+                continue;
+            }
+            let factor = (&discrepancy * basic_op::inv_mod(&prev_discrepancy, p).unwrap()) % p;
+            let mut correction = vec![BigInt::zero(); shift];
+            correction.extend(scale(&prev, &factor, p));
+            let next = sub(&c, &correction, p);
+            if 2 * len <= n {
+                prev = std::mem::replace(&mut c, next);
+                len = n + 1 - len;
+                prev_discrepancy = discrepancy;
+                shift = 1;
+            } else {
+                c = next;
+                shift += 1;
+            }
+        }
+        (0..=len).map(|i| c.get(len - i).cloned().unwrap_or_else(BigInt::zero)).collect()
+    }
 }
@@ -0,0 +1,3 @@
+// attacks on the primitives of this crate, to show what the toy parameters give away
+pub mod dlog;
+pub mod factoring;
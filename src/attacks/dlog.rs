@@ -0,0 +1,286 @@
+// discrete logarithms on EcWei groups: given G and T = x G, find x. Brute force and
+// baby-step giant-step work in any cyclic group, Pollard's rho takes O(sqrt(n)) steps and
+// little memory, the kangaroo searches an interval [lower, upper] in O(sqrt(upper - lower))
+// and Pohlig-Hellman reduces the problem to the prime factors of the order. Together they
+// show why a curve is only as strong as the largest prime factor of its order
+use crate::arithmetic::basic_op::{self, random_mod, reduce};
+use crate::curve::ecc::{EcWei, Point};
+use num_bigint::BigInt;
+use num_traits::{One, ToPrimitive, Zero};
+use rand::Rng;
+use std::collections::HashMap;
+
+// baby-step tables larger than this are refused rather than allocated
+pub const BSGS_MAX_TABLE: u64 = 1 << 26;
+// Pohlig-Hellman hands prime factors below this bound to baby-step giant-step, the others to rho
+const BSGS_PRIME_BITS: u64 = 32;
+// the random walk of rho adds one of this many fixed points, chosen by the current point
+const RHO_PARTITIONS: usize = 20;
+// walks and kangaroos are given up after this many times their expected number of steps
+const WALK_BUDGET: u64 = 64;
+const KANGAROO_ATTEMPTS: usize = 8;
+// intervals this narrow are searched point by point
+const KANGAROO_MIN_WIDTH: u64 = 64;
+
+fn not_found() -> String {
+    "T is not a multiple of G".to_string()
+}
+
+// x in [0, n) with x G = T by trying every x: n steps
+pub fn brute_force(new_ec: &EcWei, point_g: &Point, target: &Point, n: &BigInt) -> Result<BigInt, String> {
+    let mut current = Point::Infinity;
+    let mut x = BigInt::zero();
+    while &x < n {
+        if &current == target {
+            return Ok(x);
+        }
+        current = new_ec.point_add(&current, point_g);
+        x += 1;
+    }
+    Err(not_found())
+}
+
+// x = i m + j with m = ceil(sqrt(n)): the baby steps j G are stored, and the giant steps
+// T - i m G are looked up among them. O(sqrt(n)) time and memory
+pub fn baby_step_giant_step(new_ec: &EcWei, point_g: &Point, target: &Point, n: &BigInt) -> Result<BigInt, String> {
+    let m = n.sqrt() + 1u32;
+    let steps = match m.to_u64() {
+        Some(steps) if steps <= BSGS_MAX_TABLE => steps,
+        _ => return Err(format!("the baby-step table would hold more than {} points", BSGS_MAX_TABLE)),
+    };
+    let mut baby = HashMap::with_capacity(steps as usize);
+    let mut current = Point::Infinity;
+    for j in 0..steps {
+        baby.entry(current.clone()).or_insert(j);
+        current = new_ec.point_add(&current, point_g);
+    }
+    let giant_step = new_ec.negate(&current);
+    let mut giant = target.clone();
+    for i in 0..=steps {
+        if let Some(j) = baby.get(&giant) {
+            return Ok((i * &m + j) % n);
+        }
+        giant = new_ec.point_add(&giant, &giant_step);
+    }
+    Err(not_found())
+}
+
+// the x in [0, n) with u x = v mod n, when there are few of them
+fn solve_linear(u: &BigInt, v: &BigInt, n: &BigInt) -> Vec<BigInt> {
+    let u = reduce(u, n);
+    let v = reduce(v, n);
+    let g = basic_op::gcd(&u, n);
+    if g.is_zero() || !(&v % &g).is_zero() || g > BigInt::from(1024) {
+        return Vec::new();
+    }
+    let reduced = n / &g;
+    let x0 = match basic_op::inv_mod(&(&u / &g), &reduced) {
+        Some(inverse) => (&v / &g) * inverse % &reduced,
+        None => return Vec::new(),
+    };
+    let count = g.to_u64().unwrap();
+    (0..count).map(|k| &x0 + &reduced * k).collect()
+}
+
+// Pollard's rho with distinguished points: walks X -> X + R_j, where R_j = a_j G + b_j T is
+// picked by the x-coordinate of X, start from random a G + b T and stop at a point whose
+// x has d low zero bits. Two walks that end at the same distinguished point give
+// a + b x = a' + b' x mod n. n is the order of G; walks stuck in a cycle are restarted
+pub fn pollard_rho(new_ec: &EcWei, point_g: &Point, target: &Point, n: &BigInt) -> Result<BigInt, String> {
+    if !new_ec.scalar_mul(target, n).is_infinity() {
+        return Err(not_found());
+    }
+    if target.is_infinity() {
+        return Ok(BigInt::zero());
+    }
+    let combine = |a: &BigInt, b: &BigInt| new_ec.point_add(&new_ec.scalar_mul(point_g, a), &new_ec.scalar_mul(target, b));
+    let jumps: Vec<(BigInt, BigInt, Point)> = (0..RHO_PARTITIONS)
+        .map(|_| {
+            let (a, b) = (random_mod(n), random_mod(n));
+            let point = combine(&a, &b);
+            (a, b, point)
+        })
+        .collect();
+    let partition = |point: &Point| match point.x() {
+        Some(x) => (x % RHO_PARTITIONS).to_usize().unwrap(),
+        None => 0,
+    };
+    // a fraction 2^-d of the points is distinguished, with 2^d about n^(1/8)
+    let d = n.bits() / 8;
+    let is_distinguished = |point: &Point| match point.x() {
+        Some(x) => x.trailing_zeros().is_none_or(|zeros| zeros >= d),
+        None => true,
+    };
+    let max_walk = WALK_BUDGET << d;
+    let mut budget = WALK_BUDGET.saturating_mul(n.sqrt().to_u64().unwrap_or(u64::MAX).saturating_add(16));
+
+    let mut distinguished: HashMap<Point, (BigInt, BigInt)> = HashMap::new();
+    while budget > 0 {
+        let (mut a, mut b) = (random_mod(n), random_mod(n));
+        let mut point = combine(&a, &b);
+        let mut length = 0;
+        while !is_distinguished(&point) && length < max_walk {
+            let (a_j, b_j, point_j) = &jumps[partition(&point)];
+            point = new_ec.point_add(&point, point_j);
+            a = (a + a_j) % n;
+            b = (b + b_j) % n;
+            length += 1;
+        }
+        budget = budget.saturating_sub(length + 1);
+        if !is_distinguished(&point) {
+            continue;
+        }
+        match distinguished.get(&point) {
+            Some((a_0, b_0)) if *b_0 != b => {
+                for x in solve_linear(&(&b - b_0), &(a_0 - &a), n) {
+                    if &new_ec.scalar_mul(point_g, &x) == target {
+                        return Ok(x);
+                    }
+                }
+            }
+            Some(_) => {}
+            None => {
+                distinguished.insert(point, (a, b));
+            }
+        }
+    }
+    Err("the random walks found no useful collision".to_string())
+}
+
+// Pollard's kangaroo for x in [lower, upper]: a tame kangaroo starts at upper G and
+// jumps by powers of two, chosen by its position, with mean about sqrt(w) / 2 for the
+// width w; it sets a trap where it stops. A wild kangaroo starts at T and follows the same
+// rule, so it falls into the trap once it lands on the tame path. About 6 sqrt(w) steps
+pub fn kangaroo(new_ec: &EcWei, point_g: &Point, target: &Point, lower: &BigInt, upper: &BigInt) -> Result<BigInt, String> {
+    if upper < lower {
+        return Err("the interval is empty".to_string());
+    }
+    let width = upper - lower;
+    // T - lower G = x' G with x' in [0, width]
+    let shifted = new_ec.point_add(target, &new_ec.negate(&new_ec.scalar_mul(point_g, lower)));
+    if width < BigInt::from(KANGAROO_MIN_WIDTH) {
+        let mut current = Point::Infinity;
+        let mut x = BigInt::zero();
+        while x <= width {
+            if current == shifted {
+                return Ok(lower + x);
+            }
+            current = new_ec.point_add(&current, point_g);
+            x += 1;
+        }
+        return Err("T is not x G for any x in the interval".to_string());
+    }
+
+    let root = width.sqrt();
+    let mut k = 1u32;
+    while (BigInt::one() << k) - 1u32 < &root * k / 2u32 {
+        k += 1;
+    }
+    let jumps: Vec<(BigInt, Point)> = (0..k)
+        .map(|i| {
+            let distance = BigInt::one() << i;
+            let point = new_ec.scalar_mul(point_g, &distance);
+            (distance, point)
+        })
+        .collect();
+    let tame_steps = 4 * root.to_u64().ok_or("the interval is too wide")?;
+
+    for _ in 0..KANGAROO_ATTEMPTS {
+        // a fresh salt gives fresh paths when an attempt misses
+        let salt: u64 = rand::thread_rng().gen();
+        let jump = |point: &Point| -> &(BigInt, Point) {
+            let index = match point.x() {
+                Some(x) => ((x + salt) % k).to_usize().unwrap(),
+                None => 0,
+            };
+            &jumps[index]
+        };
+
+        let mut tame = new_ec.scalar_mul(point_g, &width);
+        let mut tame_distance = width.clone();
+        for _ in 0..tame_steps {
+            let (distance, point) = jump(&tame);
+            tame = new_ec.point_add(&tame, point);
+            tame_distance += distance;
+        }
+
+        // the wild kangaroo has passed the trap once its distance exceeds the tame one
+        let mut wild = shifted.clone();
+        let mut wild_distance = BigInt::zero();
+        while wild_distance <= tame_distance {
+            if wild == tame {
+                // a T just above the interval can fall into the trap too
+                let x = lower + &tame_distance - &wild_distance;
+                if &x <= upper && &new_ec.scalar_mul(point_g, &x) == target {
+                    return Ok(x);
+                }
+                break;
+            }
+            let (distance, point) = jump(&wild);
+            wild = new_ec.point_add(&wild, point);
+            wild_distance += distance;
+        }
+    }
+    Err("T is not x G for any x in the interval".to_string())
+}
+
+// the logarithm in a group of prime order q
+fn prime_order_log(new_ec: &EcWei, point_g: &Point, target: &Point, q: &BigInt) -> Result<BigInt, String> {
+    if q.bits() <= BSGS_PRIME_BITS {
+        baby_step_giant_step(new_ec, point_g, target, q)
+    } else {
+        pollard_rho(new_ec, point_g, target, q)
+    }
+}
+
+// n = prod q^e is a multiple of the order of G, for example #E. For each q, x is found
+// modulo the order q^f of (n / q^e) G one base-q digit at a time, in the subgroup of order
+// q, and the CRT puts the residues together. The cost is dominated by sqrt of the largest
+// prime factor of n
+pub fn pohlig_hellman(new_ec: &EcWei, point_g: &Point, target: &Point, n: &BigInt) -> Result<BigInt, String> {
+    if !new_ec.scalar_mul(target, n).is_infinity() {
+        return Err(not_found());
+    }
+    let mut x = BigInt::zero();
+    let mut modulus = BigInt::one();
+    for (q, e) in basic_op::factorize(n) {
+        let cofactor = n / q.pow(e);
+        let point_g_q = new_ec.scalar_mul(point_g, &cofactor);
+        let target_q = new_ec.scalar_mul(target, &cofactor);
+        // G_q has order q^f with f <= e, less than e when n is a proper multiple of the order
+        let mut f = 0;
+        let mut gamma = Point::Infinity;
+        let mut multiple = point_g_q.clone();
+        while !multiple.is_infinity() {
+            gamma = multiple.clone();
+            multiple = new_ec.scalar_mul(&multiple, &q);
+            f += 1;
+        }
+        if f == 0 {
+            continue;
+        }
+        // gamma = q^(f-1) G_q has order q; x mod q^f is found one digit at a time
+        let mut x_q = BigInt::zero();
+        for i in 0..f {
+            let rest = new_ec.point_add(&target_q, &new_ec.negate(&new_ec.scalar_mul(&point_g_q, &x_q)));
+            let h = new_ec.scalar_mul(&rest, &q.pow(f - 1 - i));
+            x_q += prime_order_log(new_ec, &gamma, &h, &q)? * q.pow(i);
+        }
+        // x = x mod modulus and x_q mod q^f
+        let q_f = q.pow(f);
+        let inverse = basic_op::inv_mod(&modulus, &q_f).unwrap();
+        let lift = reduce(&(&x_q - &x), &q_f) * inverse % &q_f;
+        x += &modulus * lift;
+        modulus *= &q_f;
+    }
+    if &new_ec.scalar_mul(point_g, &x) != target {
+        return Err(not_found());
+    }
+    Ok(x)
+}
+
+// log_G(T) with Pohlig-Hellman over the order of G
+pub fn discrete_log(new_ec: &EcWei, point_g: &Point, target: &Point) -> Result<BigInt, String> {
+    let n = point_g.order(new_ec);
+    pohlig_hellman(new_ec, point_g, target, &n)
+}
@@ -0,0 +1,489 @@
+// integer factorization, to show how little a small or badly chosen RSA or Paillier modulus
+// protects: trial division, Pollard's rho, Pollard's p - 1 and Williams' p + 1 (for a prime
+// whose p - 1 or p + 1 has only small factors), Fermat's method (for close primes) and a
+// small quadratic sieve. Each method returns a non-trivial factor of n, or None when it
+// gives up within its bound
+use crate::arithmetic::basic_op;
+use crate::paillier::PaillierKey;
+use num_bigint::{BigInt, RandBigInt};
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use std::fmt;
+
+// below this a modulus is weak whatever the audit finds
+pub const MIN_MODULUS_BITS: u64 = 2048;
+// the budgets of the cheap methods in `audit_modulus`
+pub const AUDIT_TRIAL_BOUND: u64 = 1 << 16;
+pub const AUDIT_RHO_ITERATIONS: u64 = 1 << 14;
+pub const AUDIT_SMOOTHNESS_BOUND: u64 = 1 << 14;
+pub const AUDIT_FERMAT_ITERATIONS: u64 = 1 << 14;
+// `find_factor` only sieves moduli up to this size
+pub const QS_MAX_BITS: u64 = 80;
+// seeds A of Williams' method, with A^2 - 4 in different square classes; each one works
+// for about half of the primes
+const LUCAS_SEEDS: [u64; 8] = [3, 4, 5, 6, 8, 9, 10, 11];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FactoringMethod {
+    TrialDivision,
+    PollardRho,
+    PollardPMinus1,
+    WilliamsPPlus1,
+    Fermat,
+    QuadraticSieve,
+}
+
+impl fmt::Display for FactoringMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FactoringMethod::TrialDivision => "trial division",
+            FactoringMethod::PollardRho => "Pollard's rho",
+            FactoringMethod::PollardPMinus1 => "Pollard's p - 1",
+            FactoringMethod::WilliamsPPlus1 => "Williams' p + 1",
+            FactoringMethod::Fermat => "Fermat's method",
+            FactoringMethod::QuadraticSieve => "the quadratic sieve",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// the primes up to bound (sieve of Eratosthenes)
+fn small_primes(bound: u64) -> Vec<u64> {
+    let bound = bound as usize;
+    let mut composite = vec![false; bound + 1];
+    let mut primes = Vec::new();
+    for i in 2..=bound {
+        if !composite[i] {
+            primes.push(i as u64);
+            for multiple in (i * i..=bound).step_by(i) {
+                composite[multiple] = true;
+            }
+        }
+    }
+    primes
+}
+
+// n < 4 has no proper factor, and the methods would sample from an empty range or
+// reduce modulo 0
+fn too_small(n: &BigInt) -> bool {
+    n < &BigInt::from(4)
+}
+
+// g when 1 < g < n
+fn proper(g: BigInt, n: &BigInt) -> Option<BigInt> {
+    let g = g.abs();
+    (g > BigInt::one() && &g < n).then_some(g)
+}
+
+// the smallest prime factor of n up to bound
+pub fn trial_division(n: &BigInt, bound: u64) -> Option<BigInt> {
+    if too_small(n) {
+        return None;
+    }
+    let mut d = 2u64;
+    while d <= bound && BigInt::from(d) * d <= *n {
+        if (n % d).is_zero() {
+            return Some(BigInt::from(d));
+        }
+        d += if d == 2 { 1 } else { 2 };
+    }
+    None
+}
+
+// Pollard's rho with Brent's cycle detection for at most `iterations` steps of x -> x^2 + c,
+// restarted with another c when a cycle closes without a factor
+pub fn pollard_rho(n: &BigInt, iterations: u64) -> Option<BigInt> {
+    if too_small(n) {
+        return None;
+    }
+    if (n % 2u32).is_zero() {
+        return proper(BigInt::from(2), n);
+    }
+    let mut rng = rand::thread_rng();
+    let mut left = iterations;
+    while left > 0 {
+        let c = rng.gen_bigint_range(&BigInt::one(), n);
+        let f = |x: &BigInt| (x * x + &c) % n;
+        let mut y = rng.gen_bigint_range(&BigInt::zero(), n);
+        let (mut r, mut g) = (1u64, BigInt::one());
+        while g.is_one() && left > 0 {
+            let x = y.clone();
+            for _ in 0..r {
+                y = f(&y);
+            }
+            // the differences of a batch share one gcd; a batch that reaches n is redone
+            // one difference at a time
+            let mut k = 0;
+            while k < r && g.is_one() && left > 0 {
+                let (start, batch) = (y.clone(), 64.min(r - k).min(left));
+                let mut product = BigInt::one();
+                for _ in 0..batch {
+                    y = f(&y);
+                    product = product * (&x - &y).abs() % n;
+                }
+                g = basic_op::gcd(&product, n);
+                if &g == n {
+                    y = start;
+                    for _ in 0..batch {
+                        y = f(&y);
+                        g = basic_op::gcd(&(&x - &y).abs(), n);
+                        if !g.is_one() {
+                            break;
+                        }
+                    }
+                }
+                k += batch;
+                left -= batch;
+            }
+            r *= 2;
+        }
+        if let Some(factor) = proper(g, n) {
+            return Some(factor);
+        }
+    }
+    None
+}
+
+// Pollard's p - 1: a = 2^E mod n, where E is the product of the prime powers up to bound,
+// is 1 modulo every prime p of n whose p - 1 divides E
+pub fn pollard_p_minus_1(n: &BigInt, bound: u64) -> Option<BigInt> {
+    if too_small(n) {
+        return None;
+    }
+    let mut a = BigInt::from(2);
+    for q in small_primes(bound) {
+        let mut power = q;
+        while power <= bound / q {
+            power *= q;
+        }
+        let next = a.modpow(&BigInt::from(power), n);
+        let g = basic_op::gcd(&(&next - 1), n);
+        if let Some(factor) = proper(g.clone(), n) {
+            return Some(factor);
+        }
+        if &g == n {
+            // every prime of n was reached with this q: take its factors one at a time
+            let mut b = a.clone();
+            for _ in 0..power.ilog(q) {
+                b = b.modpow(&BigInt::from(q), n);
+                if let Some(factor) = proper(basic_op::gcd(&(&b - 1), n), n) {
+                    return Some(factor);
+                }
+            }
+            return None;
+        }
+        a = next;
+    }
+    None
+}
+
+// V_m(v) for the Lucas sequence V_0 = 2, V_1 = v, V_k+1 = v V_k - V_k-1 modulo n, by
+// the ladder on (V_k, V_k+1)
+fn lucas_v(v: &BigInt, m: u64, n: &BigInt) -> BigInt {
+    let reduce = |a: BigInt| basic_op::reduce(&a, n);
+    let (mut x, mut y) = (v.clone(), reduce(v * v - 2));
+    for bit in (0..m.ilog2()).rev() {
+        if m >> bit & 1 == 1 {
+            x = reduce(&x * &y - v);
+            y = reduce(&y * &y - 2);
+        } else {
+            y = reduce(&x * &y - v);
+            x = reduce(&x * &x - 2);
+        }
+    }
+    x
+}
+
+// Williams' p + 1: V_E(A) - 2 shares the prime p with n when p + 1 divides E and A^2 - 4
+// is not a square modulo p (p - 1 when it is), so a few seeds A are tried
+pub fn williams_p_plus_1(n: &BigInt, bound: u64) -> Option<BigInt> {
+    if too_small(n) {
+        return None;
+    }
+    let primes = small_primes(bound);
+    for seed in LUCAS_SEEDS {
+        let mut v = BigInt::from(seed) % n;
+        for &q in &primes {
+            let mut power = q;
+            while power <= bound / q {
+                power *= q;
+            }
+            v = lucas_v(&v, power, n);
+        }
+        if let Some(factor) = proper(basic_op::gcd(&(&v - 2), n), n) {
+            return Some(factor);
+        }
+    }
+    None
+}
+
+// Fermat: n = a^2 - b^2 = (a - b)(a + b) for a = ceil(sqrt(n)), ceil(sqrt(n)) + 1, ...;
+// the first a works when the two primes are close
+pub fn fermat(n: &BigInt, iterations: u64) -> Option<BigInt> {
+    if too_small(n) {
+        return None;
+    }
+    if (n % 2u32).is_zero() {
+        return proper(BigInt::from(2), n);
+    }
+    let mut a = n.sqrt();
+    if &(&a * &a) < n {
+        a += 1;
+    }
+    for _ in 0..iterations {
+        let b2 = &a * &a - n;
+        let b = b2.sqrt();
+        if &b * &b == b2 {
+            return proper(&a - &b, n);
+        }
+        a += 1;
+    }
+    None
+}
+
+// the quadratic sieve for n up to about 80 bits: Q(x) = x^2 - n is sieved for x around
+// sqrt(n) over the primes p with (n / p) = 1, the smooth values are combined by Gaussian
+// elimination over GF(2) into X^2 = Y^2 mod n, and gcd(X - Y, n) is tried. A square or
+// a prime n has no proper factor to find this way
+pub fn quadratic_sieve(n: &BigInt) -> Option<BigInt> {
+    if too_small(n) {
+        return None;
+    }
+    if let Some(factor) = trial_division(n, 1000) {
+        return Some(factor);
+    }
+    let root = n.sqrt();
+    if &(&root * &root) == n {
+        return proper(root, n);
+    }
+    if basic_op::is_probable_prime(n) {
+        return None;
+    }
+    // B = exp(sqrt(ln n ln ln n) / 2), enlarged with the interval until enough relations appear
+    let ln_n = n.bits() as f64 * std::f64::consts::LN_2;
+    let mut bound = (0.5 * (ln_n * ln_n.ln()).sqrt()).exp().max(100.0) as u64;
+    for _ in 0..6 {
+        if let Some(factor) = sieve(n, &root, bound, bound * 50) {
+            return Some(factor);
+        }
+        bound *= 2;
+    }
+    None
+}
+
+// one attempt of the quadratic sieve with primes up to bound and x - sqrt(n) in [-half, half]
+fn sieve(n: &BigInt, root: &BigInt, bound: u64, half: u64) -> Option<BigInt> {
+    // the factor base: -1 for the sign, then the primes for which x^2 = n has roots
+    let base: Vec<(u64, Vec<u64>)> = small_primes(bound)
+        .into_iter()
+        .filter_map(|p| {
+            let big_p = BigInt::from(p);
+            let residue = (n % &big_p).to_u64().unwrap();
+            if p == 2 {
+                return Some((2, vec![residue % 2]));
+            }
+            if basic_op::legendre(&BigInt::from(residue), &big_p) != 1 {
+                return None;
+            }
+            let r = basic_op::sqrt_mod(&BigInt::from(residue), &big_p)?.to_u64().unwrap();
+            Some((p, vec![r, p - r]))
+        })
+        .collect();
+    let columns = base.len() + 1;
+
+    // log2 |Q(x)| is approached by adding log2 p at every x with p | Q(x)
+    let start = root - BigInt::from(half);
+    let length = 2 * half as usize + 1;
+    let mut logs = vec![0.0f32; length];
+    for (p, roots) in &base {
+        let offset = (&start % p).to_u64().unwrap();
+        let log_p = (*p as f32).log2();
+        for r in roots {
+            let mut i = ((r + p - offset) % p) as usize;
+            while i < length {
+                logs[i] += log_p;
+                i += *p as usize;
+            }
+        }
+    }
+    // prime powers are not sieved, so smooth values fall short by a few primes
+    let slack = 2.0 * (bound as f32).log2();
+
+    let mut relations: Vec<(BigInt, Vec<u32>)> = Vec::new();
+    for (i, log) in logs.iter().enumerate() {
+        let x = &start + i;
+        let q = &x * &x - n;
+        if q.is_zero() || (*log as f64) < q.bits() as f64 - slack as f64 {
+            continue;
+        }
+        let mut exponents = vec![0u32; columns];
+        let mut rest = q.abs();
+        exponents[0] = q.is_negative() as u32;
+        for (column, (p, _)) in base.iter().enumerate() {
+            while (&rest % p).is_zero() {
+                rest /= *p;
+                exponents[column + 1] += 1;
+            }
+        }
+        if rest.is_one() {
+            relations.push((x, exponents));
+            if relations.len() > columns + 10 {
+                break;
+            }
+        }
+    }
+    if relations.len() <= columns {
+        return None;
+    }
+
+    // rows of exponents mod 2, each with the set of relations it combines
+    let words = |bits: usize| bits.div_ceil(64);
+    let mut rows: Vec<(Vec<u64>, Vec<u64>)> = relations
+        .iter()
+        .enumerate()
+        .map(|(r, (_, exponents))| {
+            let mut parity = vec![0u64; words(columns)];
+            for (c, e) in exponents.iter().enumerate() {
+                if e % 2 == 1 {
+                    parity[c / 64] |= 1 << (c % 64);
+                }
+            }
+            let mut combination = vec![0u64; words(relations.len())];
+            combination[r / 64] |= 1 << (r % 64);
+            (parity, combination)
+        })
+        .collect();
+    let mut pivot_row = 0;
+    for c in 0..columns {
+        let bit = |row: &(Vec<u64>, Vec<u64>)| row.0[c / 64] >> (c % 64) & 1 == 1;
+        let Some(found) = (pivot_row..rows.len()).find(|&r| bit(&rows[r])) else { continue };
+        rows.swap(pivot_row, found);
+        let pivot = rows[pivot_row].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            if r != pivot_row && bit(row) {
+                row.0.iter_mut().zip(&pivot.0).for_each(|(a, b)| *a ^= b);
+                row.1.iter_mut().zip(&pivot.1).for_each(|(a, b)| *a ^= b);
+            }
+        }
+        pivot_row += 1;
+    }
+
+    // every row below the pivots is a product of relations whose Q is a square
+    for (_, combination) in &rows[pivot_row..] {
+        let mut x = BigInt::one();
+        let mut exponents = vec![0u32; columns];
+        for (r, (x_r, exponents_r)) in relations.iter().enumerate() {
+            if combination[r / 64] >> (r % 64) & 1 == 1 {
+                x = x * x_r % n;
+                exponents.iter_mut().zip(exponents_r).for_each(|(a, b)| *a += b);
+            }
+        }
+        let mut y = BigInt::one();
+        for (column, (p, _)) in base.iter().enumerate() {
+            y = y * BigInt::from(*p).modpow(&BigInt::from(exponents[column + 1] / 2), n) % n;
+        }
+        if let Some(factor) = proper(basic_op::gcd(&(&x - &y).abs(), n), n) {
+            return Some(factor);
+        }
+    }
+    None
+}
+
+// the cheap methods with the audit budgets, then the quadratic sieve when n is small enough
+pub fn find_factor(n: &BigInt) -> Option<(FactoringMethod, BigInt)> {
+    if too_small(n) {
+        return None;
+    }
+    let cheap = run_cheap_methods(n);
+    if let Some((method, factor)) = cheap.iter().find_map(|(method, factor)| Some((*method, factor.clone()?))) {
+        return Some((method, factor));
+    }
+    if n.bits() <= QS_MAX_BITS {
+        return quadratic_sieve(n).map(|factor| (FactoringMethod::QuadraticSieve, factor));
+    }
+    None
+}
+
+fn run_cheap_methods(n: &BigInt) -> Vec<(FactoringMethod, Option<BigInt>)> {
+    vec![
+        (FactoringMethod::TrialDivision, trial_division(n, AUDIT_TRIAL_BOUND)),
+        (FactoringMethod::Fermat, fermat(n, AUDIT_FERMAT_ITERATIONS)),
+        (FactoringMethod::PollardPMinus1, pollard_p_minus_1(n, AUDIT_SMOOTHNESS_BOUND)),
+        (FactoringMethod::WilliamsPPlus1, williams_p_plus_1(n, AUDIT_SMOOTHNESS_BOUND)),
+        (FactoringMethod::PollardRho, pollard_rho(n, AUDIT_RHO_ITERATIONS)),
+    ]
+}
+
+// the private key (λ, μ) of a Paillier public key (g, N), from the factors of N:
+// λ = lcm(p - 1, q - 1) and μ = L(g^λ mod N^2)^-1 mod N
+pub fn break_paillier(public_key: &(BigInt, BigInt)) -> Result<PaillierKey, String> {
+    let (g, n) = public_key;
+    let (_, p) = find_factor(n).ok_or_else(|| format!("no factor of N = {} was found", n))?;
+    let q = n / &p;
+    // a Paillier modulus is the product of two primes
+    if &p * &q != *n || !basic_op::is_probable_prime(&p) || !basic_op::is_probable_prime(&q) {
+        return Err(format!("N = {} is not the product of two primes", n));
+    }
+    let lambda = basic_op::lcm(&(&p - 1), &(&q - 1));
+    let n_square = n * n;
+    let l = (g.modpow(&lambda, &n_square) - 1) / n;
+    let mu = basic_op::inv_mod(&l, n).ok_or("L(g^λ mod N^2) is not invertible modulo N")?;
+    Ok(PaillierKey {
+        public_key: public_key.clone(),
+        private_key: (lambda, mu),
+    })
+}
+
+// what the cheap methods found against a modulus
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeakKeyReport {
+    pub modulus: BigInt,
+    pub bits: u64,
+    // one entry per method, with the factor it found if any
+    pub attempts: Vec<(FactoringMethod, Option<BigInt>)>,
+}
+
+impl WeakKeyReport {
+    // the first factor found, with its method
+    pub fn factor(&self) -> Option<(FactoringMethod, &BigInt)> {
+        self.attempts.iter().find_map(|(method, factor)| Some((*method, factor.as_ref()?)))
+    }
+
+    pub fn is_too_small(&self) -> bool {
+        self.bits < MIN_MODULUS_BITS
+    }
+
+    // factored, or too small to resist a serious attempt
+    pub fn is_weak(&self) -> bool {
+        self.factor().is_some() || self.is_too_small()
+    }
+}
+
+impl fmt::Display for WeakKeyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "modulus of {} bits", self.bits)?;
+        if self.is_too_small() {
+            writeln!(f, "  too small: at least {} bits are needed", MIN_MODULUS_BITS)?;
+        }
+        for (method, factor) in &self.attempts {
+            match factor {
+                Some(factor) => writeln!(f, "  {}: factor {}", method, factor)?,
+                None => writeln!(f, "  {}: nothing", method)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// the cheap methods against an RSA or Paillier modulus: small factors, close primes,
+// primes p with a smooth p - 1 or p + 1 and, for small moduli, a short rho
+pub fn audit_modulus(n: &BigInt) -> WeakKeyReport {
+    WeakKeyReport {
+        modulus: n.clone(),
+        bits: n.bits(),
+        attempts: run_cheap_methods(n),
+    }
+}
+
+pub fn audit_paillier(public_key: &(BigInt, BigInt)) -> WeakKeyReport {
+    audit_modulus(&public_key.1)
+}
@@ -0,0 +1,146 @@
+pub mod pedersen {
+    use crate::arithmetic::basic_op;
+    use crate::curve::ecc::{EcWei, Point};
+    use num_bigint::{BigInt, Sign};
+    use num_traits::Zero;
+    use sha2::{Digest, Sha256};
+
+    // C = m * G + r * H, where nobody knows log_G(H)
+    pub struct Pedersen<'a> {
+        pub new_ec: &'a EcWei,
+        pub point_g: Point,
+        pub point_h: Point,
+        pub n: BigInt,
+    }
+
+    // nothing-up-my-sleeve H: x = SHA-256(label || G || counter) mod p is tried until it
+    // lands on the curve and in the subgroup of order n, so its discrete log is unknown
+    pub fn derive_generator(new_ec: &EcWei, point_g: &Point, n: &BigInt, label: &[u8]) -> Point {
+        let p = new_ec.p();
+        let mut counter: u64 = 0;
+        loop {
+            let mut hasher = Sha256::new();
+            hasher.update(b"mpc-zk-paillier/pedersen-h");
+            hasher.update((label.len() as u64).to_be_bytes());
+            hasher.update(label);
+            if let Point::Affine(x, y) = point_g {
+                hasher.update(x.to_bytes_be().1);
+                hasher.update(y.to_bytes_be().1);
+            }
+            hasher.update(counter.to_be_bytes());
+            counter += 1;
+
+            let x = BigInt::from_bytes_be(Sign::Plus, &hasher.finalize()) % p;
+            let rhs = (&x * &x * &x + new_ec.a() * &x + new_ec.b()) % p;
+            let y = match basic_op::sqrt_mod(&rhs, p) {
+                Some(y) if !y.is_zero() => y,
+                _ => continue,
+            };
+            let candidate = Point::new(x, y);
+            let in_subgroup = new_ec.scalar_mul(&candidate, n).is_infinity();
+            if in_subgroup && &candidate != point_g {
+                return candidate;
+            }
+        }
+    }
+
+    impl<'a> Pedersen<'a> {
+        // H derived from G with `derive_generator`
+        pub fn new(new_ec: &'a EcWei, point_g: &Point, n: &BigInt) -> Self {
+            Self {
+                new_ec,
+                point_g: point_g.clone(),
+                point_h: derive_generator(new_ec, point_g, n, b"default"),
+                n: n.clone(),
+            }
+        }
+
+        pub fn with_generators(new_ec: &'a EcWei, point_g: &Point, point_h: &Point, n: &BigInt) -> Self {
+            Self {
+                new_ec,
+                point_g: point_g.clone(),
+                point_h: point_h.clone(),
+                n: n.clone(),
+            }
+        }
+
+        pub fn commit(&self, m: &BigInt, r: &BigInt) -> Point {
+            let m = basic_op::reduce(m, &self.n);
+            let r = basic_op::reduce(r, &self.n);
+            self.new_ec.point_add(
+                &self.new_ec.scalar_mul_ct(&self.point_g, &m),
+                &self.new_ec.scalar_mul_ct(&self.point_h, &r),
+            )
+        }
+
+        // commitment with a fresh blinding factor, returned for the opening
+        pub fn commit_random(&self, m: &BigInt) -> (Point, BigInt) {
+            let r = basic_op::random_scalar(&self.n);
+            (self.commit(m, &r), r)
+        }
+
+        pub fn verify_opening(&self, commitment: &Point, m: &BigInt, r: &BigInt) -> bool {
+            &self.commit(m, r) == commitment
+        }
+
+        // Com(m1, r1) + Com(m2, r2) = Com(m1 + m2, r1 + r2)
+        pub fn add(&self, c1: &Point, c2: &Point) -> Point {
+            self.new_ec.point_add(c1, c2)
+        }
+
+        // k * Com(m, r) = Com(k * m, k * r)
+        pub fn scale(&self, commitment: &Point, k: &BigInt) -> Point {
+            self.new_ec.scalar_mul(commitment, &basic_op::reduce(k, &self.n))
+        }
+
+        // opening of the sum of two commitments
+        pub fn add_openings(&self, opening_1: (&BigInt, &BigInt), opening_2: (&BigInt, &BigInt)) -> (BigInt, BigInt) {
+            (
+                (opening_1.0 + opening_2.0) % &self.n,
+                (opening_1.1 + opening_2.1) % &self.n,
+            )
+        }
+    }
+}
+
+pub mod commit_reveal {
+    use rand::RngCore;
+    use sha2::{Digest, Sha256};
+
+    // SHA-256(domain || blinding || message), published before the message itself
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Commitment(pub [u8; 32]);
+
+    // what is revealed afterwards so that anyone can recompute the commitment
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Decommitment {
+        pub blinding: [u8; 32],
+        pub message: Vec<u8>,
+    }
+
+    fn hash(blinding: &[u8; 32], message: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"mpc-zk-paillier/commit-reveal");
+        hasher.update(blinding);
+        hasher.update((message.len() as u64).to_be_bytes());
+        hasher.update(message);
+        hasher.finalize().into()
+    }
+
+    pub fn commit(message: &[u8]) -> (Commitment, Decommitment) {
+        let mut blinding = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut blinding);
+        let commitment = Commitment(hash(&blinding, message));
+        (
+            commitment,
+            Decommitment {
+                blinding,
+                message: message.to_vec(),
+            },
+        )
+    }
+
+    pub fn verify(commitment: &Commitment, decommitment: &Decommitment) -> bool {
+        hash(&decommitment.blinding, &decommitment.message) == commitment.0
+    }
+}
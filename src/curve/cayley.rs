@@ -0,0 +1,248 @@
+// the Cayley table of a finite group of points as data: the identity comes first, row i and
+// column j hold elements[i] + elements[j]. It renders as the ASCII grid printed by
+// `CurveModel::cayley_table` and exports to CSV, JSON, Markdown, LaTeX and HTML
+use crate::curve::ecc::Point;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CayleyTable {
+    pub elements: Vec<Point>,
+    pub entries: Vec<Vec<Point>>,
+}
+
+impl CayleyTable {
+    // the entries must be elements[i] + elements[j], with the identity as elements[0]
+    pub fn new(elements: Vec<Point>, entries: Vec<Vec<Point>>) -> Self {
+        Self { elements, entries }
+    }
+
+    pub fn identity(&self) -> &Point {
+        &self.elements[0]
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn get(&self, a: &Point, b: &Point) -> Option<&Point> {
+        let index = self.index();
+        Some(&self.entries[*index.get(a)?][*index.get(b)?])
+    }
+
+    fn index(&self) -> HashMap<&Point, usize> {
+        self.elements.iter().enumerate().map(|(i, point)| (point, i)).collect()
+    }
+
+    // the table as indices into `elements`, once every entry is known to be an element
+    fn indices(&self) -> Result<Vec<Vec<usize>>, String> {
+        let index = self.index();
+        if index.len() != self.len() {
+            return Err("the elements are not distinct".to_string());
+        }
+        if self.entries.len() != self.len() || self.entries.iter().any(|row| row.len() != self.len()) {
+            return Err("the table is not square".to_string());
+        }
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, entry)| {
+                        index.get(entry).copied().ok_or_else(|| {
+                            format!("closure fails: {} + {} = {} is not in the table", self.elements[i], self.elements[j], entry)
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // closure, identity, inverses and associativity, in that order; the first axiom that
+    // fails is reported with a counterexample. Associativity takes n^3 lookups
+    pub fn check_axioms(&self) -> Result<(), String> {
+        if self.is_empty() {
+            return Err("a group has at least one element".to_string());
+        }
+        let table = self.indices()?;
+        let n = self.len();
+        for (i, element) in self.elements.iter().enumerate() {
+            if table[0][i] != i || table[i][0] != i {
+                return Err(format!("{} is not an identity for {}", self.identity(), element));
+            }
+        }
+        for (i, element) in self.elements.iter().enumerate() {
+            if !(0..n).any(|j| table[i][j] == 0 && table[j][i] == 0) {
+                return Err(format!("{} has no inverse", element));
+            }
+        }
+        for i in 0..n {
+            for j in 0..n {
+                for k in 0..n {
+                    if table[table[i][j]][k] != table[i][table[j][k]] {
+                        return Err(format!(
+                            "associativity fails for {}, {} and {}",
+                            self.elements[i], self.elements[j], self.elements[k]
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_abelian(&self) -> bool {
+        (0..self.len()).all(|i| (0..i).all(|j| self.entries[i][j] == self.entries[j][i]))
+    }
+
+    // the header row followed by one row per element, each starting with its label
+    fn rows(&self, label: impl Fn(&Point) -> String) -> Vec<Vec<String>> {
+        let mut rows = vec![std::iter::once("+".to_string()).chain(self.elements.iter().map(&label)).collect()];
+        for (element, row) in self.elements.iter().zip(&self.entries) {
+            rows.push(std::iter::once(label(element)).chain(row.iter().map(&label)).collect());
+        }
+        rows
+    }
+
+    // RFC 4180: fields holding a comma or a quote are quoted
+    pub fn to_csv(&self) -> String {
+        let field = |point: &Point| {
+            let text = point.to_string();
+            if text.contains([',', '"']) {
+                format!("\"{}\"", text.replace('"', "\"\""))
+            } else {
+                text
+            }
+        };
+        self.rows(field).iter().map(|row| row.join(",") + "\r\n").collect()
+    }
+
+    // {"elements": [...], "table": [[...], ...]}, with ∞ as null and an affine point as
+    // {"x": "...", "y": "..."}; coordinates are strings so that no parser rounds them
+    pub fn to_json(&self) -> String {
+        let value = |point: &Point| match point {
+            Point::Infinity => "null".to_string(),
+            Point::Affine(x, y) => format!("{{\"x\": \"{}\", \"y\": \"{}\"}}", x, y),
+        };
+        let list = |points: &[Point]| format!("[{}]", points.iter().map(value).collect::<Vec<_>>().join(", "));
+        let rows: Vec<String> = self.entries.iter().map(|row| format!("    {}", list(row))).collect();
+        format!("{{\n  \"elements\": {},\n  \"table\": [\n{}\n  ]\n}}\n", list(&self.elements), rows.join(",\n"))
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let rows = self.rows(|point| point.to_string());
+        let line = |row: &Vec<String>| format!("| {} |\n", row.join(" | "));
+        let mut markdown = line(&rows[0]);
+        markdown += &format!("|{}\n", ":---:|".repeat(rows[0].len()));
+        for row in &rows[1..] {
+            markdown += &line(row);
+        }
+        markdown
+    }
+
+    // a tabular environment; the identity row and column are set apart by rules
+    pub fn to_latex(&self) -> String {
+        let label = |point: &Point| match point {
+            Point::Infinity => "$\\infty$".to_string(),
+            Point::Affine(x, y) => format!("$({}, {})$", x, y),
+        };
+        let rows = self.rows(label);
+        let mut latex = format!("\\begin{{tabular}}{{c|c|{}}}\n", "c".repeat(self.len().saturating_sub(1)));
+        for (i, row) in rows.iter().enumerate() {
+            latex += &format!("{} \\\\\n", row.join(" & "));
+            if i < 2 {
+                latex += "\\hline\n";
+            }
+        }
+        latex + "\\end{tabular}\n"
+    }
+
+    // a page with no external resources: the identity's row and column are shaded and
+    // every entry equal to the identity, i.e. every pair of inverses, is highlighted
+    pub fn to_html(&self) -> String {
+        let identity = self.identity();
+        let escape = |point: &Point| point.to_string().replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+        let cell = |tag: &str, point: &Point, class: &str| {
+            let class = if class.is_empty() { String::new() } else { format!(" class=\"{}\"", class) };
+            format!("<{tag}{class}>{}</{tag}>", escape(point))
+        };
+        let header_class = |point: &Point| if point == identity { "identity" } else { "" };
+        let mut html = String::from(concat!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Cayley table</title>\n<style>\n",
+            "table { border-collapse: collapse; font-family: monospace; }\n",
+            "th, td { border: 1px solid #999; padding: 4px 8px; text-align: center; }\n",
+            "th { background: #eee; }\n",
+            ".identity { background: #cde; font-weight: bold; }\n",
+            ".inverse { background: #fc9; }\n",
+            "</style>\n</head>\n<body>\n<table>\n",
+        ));
+        html += "<tr><th>+</th>";
+        for element in &self.elements {
+            html += &cell("th", element, header_class(element));
+        }
+        html += "</tr>\n";
+        for (i, (element, row)) in self.elements.iter().zip(&self.entries).enumerate() {
+            html += "<tr>";
+            html += &cell("th", element, header_class(element));
+            for (j, entry) in row.iter().enumerate() {
+                let class = if i == 0 || j == 0 {
+                    "identity"
+                } else if entry == identity {
+                    "inverse"
+                } else {
+                    ""
+                };
+                html += &cell("td", entry, class);
+            }
+            html += "</tr>\n";
+        }
+        html + "</table>\n</body>\n</html>\n"
+    }
+}
+
+// the ASCII grid, every cell as wide as the widest point
+impl fmt::Display for CayleyTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = |coordinate: fn(&Point) -> Option<&num_bigint::BigInt>| {
+            self.elements.iter().filter_map(coordinate).map(|c| c.to_string().len()).max().unwrap_or(0)
+        };
+        let (max_x_len, max_y_len) = (width(Point::x), width(Point::y));
+        let cell_width = max_x_len + max_y_len + 5; // adjustment for cell space
+
+        let border = |f: &mut fmt::Formatter<'_>| {
+            write!(f, "+")?;
+            for _ in 0..=self.len() {
+                write!(f, "{:-<width$}+", "", width = cell_width)?;
+            }
+            writeln!(f)
+        };
+        let cell = |f: &mut fmt::Formatter<'_>, point: &Point| match point {
+            // the infinity symbol, centered in the cell
+            Point::Infinity => write!(f, "| {:^width$} ", "∞", width = max_x_len + max_y_len + 3),
+            // format x and y coordinate with maximum size found
+            Point::Affine(x, y) => write!(f, "| ({:>width_x$},{:>width_y$}) ", x, y, width_x = max_x_len, width_y = max_y_len),
+        };
+
+        border(f)?;
+        write!(f, "| {:^width$} ", "+", width = max_x_len + max_y_len + 3)?;
+        for element in &self.elements {
+            cell(f, element)?;
+        }
+        writeln!(f, "|")?;
+        border(f)?;
+        for (element, row) in self.elements.iter().zip(&self.entries) {
+            cell(f, element)?;
+            for entry in row {
+                cell(f, entry)?;
+            }
+            writeln!(f, "|")?;
+            border(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,1424 @@
+use crate::arithmetic::basic_op;
+use crate::curve::schoof;
+extern crate rand;
+use num_bigint::{BigInt, RandBigInt, Sign};
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use rand::Rng;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+pub use crate::curve::models::{x25519, CurveModel, EcEdwards, EcMont};
+pub use crate::curve::structure::{GroupStructure, Subgroup};
+
+// a point of the curve: the point at infinity (the identity of the group) or an
+// affine point (x, y); the identity has no coordinates, so no affine point can be
+// mistaken for it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Point {
+    Infinity,
+    Affine(BigInt, BigInt),
+}
+
+impl Point {
+    pub fn new(new_x: BigInt, new_y: BigInt) -> Self {
+        Point::Affine(new_x, new_y)
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        matches!(self, Point::Infinity)
+    }
+
+    // None for the point at infinity
+    pub fn x(&self) -> Option<&BigInt> {
+        match self {
+            Point::Infinity => None,
+            Point::Affine(x, _) => Some(x),
+        }
+    }
+
+    pub fn y(&self) -> Option<&BigInt> {
+        match self {
+            Point::Infinity => None,
+            Point::Affine(_, y) => Some(y),
+        }
+    }
+
+    // the least k > 0 with k * P = ∞, found by dividing the prime factors of #E out of
+    // #E for as long as the point still vanishes
+    pub fn order(&self, curve: &EcWei) -> BigInt {
+        curve.order_of(self, &curve.order(), &basic_op::factorize(&curve.order()))
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Point::Infinity => write!(f, "∞"),
+            Point::Affine(x, y) => write!(f, "({}, {})", x, y),
+        }
+    }
+}
+
+// a point in Jacobian coordinates (X : Y : Z), i.e. the affine point (X / Z^2, Y / Z^3),
+// with Z = 0 for the point at infinity. Additions and doublings need no inversion, so
+// long chains of them only convert back to affine once, at the end
+#[derive(Debug, Clone)]
+pub(crate) struct Jacobian {
+    x: BigInt,
+    y: BigInt,
+    z: BigInt,
+}
+
+impl Jacobian {
+    pub(crate) fn infinity() -> Self {
+        Jacobian {
+            x: BigInt::one(),
+            y: BigInt::one(),
+            z: BigInt::zero(),
+        }
+    }
+
+    pub(crate) fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+}
+
+// fixed-base comb for k * G: row i holds the odd multiples j * 2^(w i) * G, j = 1, 3, ...,
+// 2^w - 1, so k * G is the sum of one entry per row and needs no doubling at all. k is
+// recoded into signed odd digits (never zero), every row is read in full and the entry
+// picked arithmetically, so the sequence of group operations does not depend on k
+#[derive(Clone)]
+pub struct FixedBaseTable {
+    curve: EcWei,
+    generator: Point,
+    n: BigInt,
+    window: u32,
+    rows: Vec<Vec<Jacobian>>,
+}
+
+impl fmt::Debug for FixedBaseTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "FixedBaseTable {{ generator: {}, window: {}, rows: {} }}",
+            self.generator,
+            self.window,
+            self.rows.len()
+        )
+    }
+}
+
+impl FixedBaseTable {
+    // generator of odd order n > 1 (the comb needs nothing more); window in 1..=8 bits
+    pub fn new(curve: &EcWei, generator: &Point, n: &BigInt, window: u32) -> Result<Self, String> {
+        if !(1..=8).contains(&window) {
+            return Err("the window must have between 1 and 8 bits".to_string());
+        }
+        if !n.bit(0) || n <= &BigInt::one() {
+            return Err("the order of the generator must be odd and greater than 1".to_string());
+        }
+        // an odd k < 2n has at most bits(n) + 1 bits, plus the final digit
+        let row_count = (n.bits() as usize + 1).div_ceil(window as usize) + 1;
+        let mut rows = Vec::with_capacity(row_count);
+        let mut base = curve.to_jacobian(generator);
+        for _ in 0..row_count {
+            let double = curve.jacobian_double(&base);
+            let mut row = vec![base.clone()];
+            for _ in 1..(1usize << (window - 1)) {
+                let next = curve.jacobian_add(row.last().unwrap(), &double);
+                row.push(next);
+            }
+            // entries are stored with Z = 1
+            let row: Vec<Jacobian> = row.iter().map(|entry| curve.to_jacobian(&curve.to_affine(entry))).collect();
+            for _ in 0..window {
+                base = curve.jacobian_double(&base);
+            }
+            rows.push(row);
+        }
+        Ok(FixedBaseTable {
+            curve: curve.clone(),
+            generator: generator.clone(),
+            n: n.clone(),
+            window,
+            rows,
+        })
+    }
+
+    pub fn generator(&self) -> &Point {
+        &self.generator
+    }
+
+    // k * G for any k, taken modulo n
+    pub fn mul(&self, k: &BigInt) -> Point {
+        if self.generator.is_infinity() {
+            return Point::Infinity;
+        }
+        let mut k = k % &self.n;
+        if k < BigInt::zero() {
+            k += &self.n;
+        }
+        // n is odd, so k + n is odd when k is even
+        let even = BigInt::from(u8::from(!k.bit(0)));
+        k += even * &self.n;
+
+        // signed odd digits: d = (k mod 2^(w+1)) - 2^w keeps k - d an odd multiple of 2^w
+        let half = 1i64 << self.window;
+        let modulus = BigInt::from(2 * half);
+        let mut acc = Jacobian::infinity();
+        for (i, row) in self.rows.iter().enumerate() {
+            let digit: i64 = if i + 1 == self.rows.len() {
+                i64::try_from(&k).unwrap()
+            } else {
+                let low = i64::try_from(&(&k % &modulus)).unwrap();
+                let digit = low - half;
+                k = (&k - digit) >> self.window;
+                digit
+            };
+            let entry = self.select(row, digit);
+            acc = self.curve.jacobian_add(&acc, &entry);
+        }
+        self.curve.to_affine(&acc)
+    }
+
+    // reads every entry of the row and keeps |digit| * 2^(w i) * G, negated when digit < 0
+    fn select(&self, row: &[Jacobian], digit: i64) -> Jacobian {
+        let negative = i64::from(digit < 0);
+        let index = (digit * (1 - 2 * negative) - 1) / 2;
+        let mut entry = Jacobian {
+            x: BigInt::zero(),
+            y: BigInt::zero(),
+            z: BigInt::zero(),
+        };
+        // Z is 1, or 0 for an entry that is ∞ on a tiny curve
+        for (j, candidate) in row.iter().enumerate() {
+            let mask = BigInt::from(u8::from(j as i64 == index));
+            entry.x += &mask * &candidate.x;
+            entry.y += &mask * &candidate.y;
+            entry.z += &mask * &candidate.z;
+        }
+        let p = self.curve.p();
+        // y or p - y
+        entry.y = (&entry.y + BigInt::from(negative) * (p - BigInt::from(2) * &entry.y)) % p;
+        entry
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyPair {
+    pub sk: BigInt,
+    pub pk: Point,
+}
+
+// domain parameters of a curve: E(F_p): y^2 = x^3 + ax + b, a generator G of prime
+// order n and the cofactor h = #E / n, together with the name and OID of the curve
+#[derive(Debug, Clone)]
+pub struct CurveParams {
+    pub name: &'static str,
+    pub oid: Option<&'static str>,
+    pub curve: EcWei,
+    pub point_g: Point,
+    pub n: BigInt,
+    pub h: BigInt,
+    // built by the first call to mul_g
+    table: OnceLock<FixedBaseTable>,
+}
+
+impl CurveParams {
+    // standard curves are written as in SEC 2, in hexadecimal
+    fn from_hex(
+        name: &'static str,
+        oid: &'static str,
+        (a, b, p): (&str, &str, &str),
+        (g_x, g_y): (&str, &str),
+        n: &str,
+    ) -> Self {
+        let hex = |s: &str| BigInt::parse_bytes(s.as_bytes(), 16).unwrap();
+        let n = hex(n);
+        // the cofactor of every SEC 2 curve registered here is 1, so #E = n
+        Self {
+            name,
+            oid: Some(oid),
+            curve: EcWei::with_order(hex(a), hex(b), hex(p), n.clone()).unwrap(),
+            point_g: Point::new(hex(g_x), hex(g_y)),
+            n,
+            h: BigInt::one(),
+            table: OnceLock::new(),
+        }
+    }
+
+    pub fn secp256k1() -> Self {
+        Self::from_hex(
+            "secp256k1",
+            "1.3.132.0.10",
+            (
+                "0",
+                "7",
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            ),
+            (
+                "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+                "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+            ),
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+        )
+    }
+
+    // also known as NIST P-256
+    pub fn secp256r1() -> Self {
+        Self::from_hex(
+            "secp256r1",
+            "1.2.840.10045.3.1.7",
+            (
+                "FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFC",
+                "5AC635D8AA3A93E7B3EBBD55769886BC651D06B0CC53B0F63BCE3C3E27D2604B",
+                "FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF",
+            ),
+            (
+                "6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296",
+                "4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5",
+            ),
+            "FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551",
+        )
+    }
+
+    // also known as NIST P-384
+    pub fn secp384r1() -> Self {
+        Self::from_hex(
+            "secp384r1",
+            "1.3.132.0.34",
+            (
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFF0000000000000000FFFFFFFC",
+                "B3312FA7E23EE7E4988E056BE3F82D19181D9C6EFE8141120314088F5013875AC656398D8A2ED19D2A85C8EDD3EC2AEF",
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFF0000000000000000FFFFFFFF",
+            ),
+            (
+                "AA87CA22BE8B05378EB1C71EF320AD746E1D3B628BA79B9859F741E082542A385502F25DBF55296C3A545E3872760AB7",
+                "3617DE4A96262C6F5D9E98BF9292DC29F8F41DBD289A147CE9DA3113B5F0B8C00A60B1CE1D7E819D7A431D7C90EA0E5F",
+            ),
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFC7634D81F4372DDF581A0DB248B0A77AECEC196ACCC52973",
+        )
+    }
+
+    // the educational curve E(F_17): y^2 = x^3 - 2x + 7, a cyclic group of 11 points
+    // in which every affine point is a generator
+    pub fn e17() -> Self {
+        Self {
+            name: "e17",
+            oid: None,
+            curve: EcWei::with_order(BigInt::from(-2), BigInt::from(7), BigInt::from(17), BigInt::from(11)).unwrap(),
+            point_g: Point::new(BigInt::from(7), BigInt::from(8)),
+            n: BigInt::from(11),
+            h: BigInt::one(),
+            table: OnceLock::new(),
+        }
+    }
+
+    // E(F_17): y^2 = x^3 - 3x + 4 has 15 points; G = (0, 2) generates the subgroup of
+    // prime order 5, so the cofactor is 3. It shows cofactors and subgroups; it is too
+    // small for the two-party wallet, where only R_i = ±2G has x(R_i) != 0 mod 5 and
+    // then the shared R = ±G always gives r = 0
+    pub fn e17_order_15() -> Self {
+        Self {
+            name: "e17-15",
+            oid: None,
+            curve: EcWei::with_order(BigInt::from(-3), BigInt::from(4), BigInt::from(17), BigInt::from(15)).unwrap(),
+            point_g: Point::new(BigInt::from(0), BigInt::from(2)),
+            n: BigInt::from(5),
+            h: BigInt::from(3),
+            table: OnceLock::new(),
+        }
+    }
+
+    // domain parameters of a curve that is not in the registry
+    pub fn new(name: &'static str, curve: EcWei, point_g: Point, n: BigInt, h: BigInt) -> Self {
+        Self {
+            name,
+            oid: None,
+            curve,
+            point_g,
+            n,
+            h,
+            table: OnceLock::new(),
+        }
+    }
+
+    // k * G from a cached fixed-base table with 6-bit windows, also for secret k
+    pub fn mul_g(&self, k: &BigInt) -> Point {
+        self.table
+            .get_or_init(|| {
+                FixedBaseTable::new(&self.curve, &self.point_g, &self.n, 6).expect("the order n of G is odd")
+            })
+            .mul(k)
+    }
+
+    pub fn gen_key_pair(&self) -> KeyPair {
+        let sk = basic_op::random_scalar(&self.n);
+        KeyPair {
+            pk: self.mul_g(&sk),
+            sk,
+        }
+    }
+
+    // a point of <G> with unknown discrete log, see hash_to_curve::hash_to_curve
+    pub fn hash_to_curve(&self, msg: &[u8], dst: &[u8]) -> Result<Point, String> {
+        crate::hash_to_curve::hash_to_curve(&self.curve, &self.h, msg, dst)
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::secp256k1(),
+            Self::secp256r1(),
+            Self::secp384r1(),
+            Self::e17(),
+            Self::e17_order_15(),
+        ]
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().into_iter().find(|params| params.name == name)
+    }
+
+    pub fn from_oid(oid: &str) -> Option<Self> {
+        Self::all().into_iter().find(|params| params.oid == Some(oid))
+    }
+
+    // EcWei::validate for the subgroup of G, which also checks n, h and G itself
+    pub fn validate(&self) -> ValidationReport {
+        let mut findings = self.curve.findings(Some(&self.n), Some(&self.h));
+        let generates = !self.point_g.is_infinity()
+            && self.curve.is_point(&self.point_g)
+            && self.curve.scalar_mul(&self.point_g, &self.n).is_infinity();
+        findings.push(if generates {
+            Finding {
+                check: Check::Generator,
+                severity: Severity::Info,
+                message: "G is on the curve and has order n".to_string(),
+            }
+        } else {
+            Finding {
+                check: Check::Generator,
+                severity: Severity::Error,
+                message: format!("G = {} is not a point of order n", self.point_g),
+            }
+        });
+        ValidationReport { findings }
+    }
+}
+
+// what a finding of `validate` is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Check {
+    Discriminant,
+    FieldPrime,
+    SubgroupOrder,
+    Cofactor,
+    Generator,
+    EmbeddingDegree,
+    Anomalous,
+    TwistSecurity,
+    JInvariant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    // a property worth knowing, e.g. j = 0
+    Info,
+    // a weakness, e.g. a subgroup small enough for Pollard's rho
+    Warning,
+    // wrong or broken parameters, e.g. a composite p or an anomalous curve
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub check: Check,
+    pub severity: Severity,
+    pub message: String,
+}
+
+// the findings of EcWei::validate or CurveParams::validate, one per check that applies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub findings: Vec<Finding>,
+}
+
+impl ValidationReport {
+    pub fn finding(&self, check: Check) -> Option<&Finding> {
+        self.findings.iter().find(|finding| finding.check == check)
+    }
+
+    // Info when there is nothing to report
+    pub fn worst(&self) -> Severity {
+        self.findings.iter().map(|finding| finding.severity).max().unwrap_or(Severity::Info)
+    }
+
+    // no warnings and no errors
+    pub fn is_secure(&self) -> bool {
+        self.worst() == Severity::Info
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for finding in &self.findings {
+            writeln!(f, "[{:?}] {:?}: {}", finding.severity, finding.check, finding.message)?;
+        }
+        Ok(())
+    }
+}
+
+// Pollard's rho on a group of prime order n takes about sqrt(n) steps; below 2^100 of
+// them the subgroup (or the twist) is reported as too small
+const MIN_RHO_BITS: u64 = 100;
+// embedding degrees up to this bound are reported as MOV-weak
+const MAX_WEAK_EMBEDDING_DEGREE: u32 = 100;
+
+// how `count_points_with` finds #E(F_p)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CountingMethod {
+    // p + 1 + the sum of (x^3 + ax + b / p) over F_p: O(p log p), small fields only
+    Legendre,
+    // baby-step giant-step in the Hasse interval with Mestre's twist trick: O(p^(1/4))
+    Mestre,
+    // Schoof's algorithm: t modulo small primes l from the l-torsion, polynomial in log p
+    Schoof,
+    // Schoof with Elkies primes, which only need a factor of the division polynomial
+    Sea,
+    // j = 0 or 1728: the Frobenius is a known element of Z[ω] or Z[i], found with
+    // Cornacchia up to a unit
+    ComplexMultiplication,
+}
+
+// below this size the Legendre sum is the cheapest count
+const LEGENDRE_MAX_BITS: u64 = 16;
+// the Legendre sum refuses larger fields
+const LEGENDRE_LIMIT_BITS: u64 = 24;
+
+impl CountingMethod {
+    // the method `point_count` uses for this curve
+    pub fn for_curve(new_ec: &EcWei) -> CountingMethod {
+        let bits = new_ec.p().bits();
+        if bits <= LEGENDRE_MAX_BITS {
+            CountingMethod::Legendre
+        } else if new_ec.a().is_zero() || new_ec.b().is_zero() {
+            CountingMethod::ComplexMultiplication
+        } else {
+            CountingMethod::Sea
+        }
+    }
+}
+
+// #E(F_p) = p + 1 - t together with the trace of Frobenius t
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PointCount {
+    pub order: BigInt,
+    pub trace: BigInt,
+    pub method: CountingMethod,
+}
+
+impl PointCount {
+    // Hasse: |t| <= 2 sqrt(p), i.e. t^2 <= 4p
+    pub fn satisfies_hasse(&self, p: &BigInt) -> bool {
+        &self.trace * &self.trace <= BigInt::from(4) * p
+    }
+
+    // #E' = p + 1 + t for the quadratic twist
+    pub fn twist_order(&self) -> BigInt {
+        &self.order + BigInt::from(2) * &self.trace
+    }
+}
+
+// definition of the EcWei structure to represent the elliptic curve
+#[derive(Debug, Clone)]
+pub struct EcWei {
+    a: BigInt,
+    b: BigInt,
+    p: BigInt,
+    // #E(F_p), counted the first time it is needed
+    order: OnceLock<BigInt>,
+}
+
+impl EcWei {
+    // refuses singular curves, 4a^3 + 27b^2 = 0 mod p, which have a cusp or a node and
+    // whose points do not form an elliptic curve group; see `validate` for the rest
+    pub fn new(new_a: BigInt, new_b: BigInt, new_p: BigInt) -> Result<Self, String> {
+        let discriminant = BigInt::from(4) * &new_a * &new_a * &new_a + BigInt::from(27) * &new_b * &new_b;
+        if (discriminant % &new_p).is_zero() {
+            return Err("the curve is singular: 4a^3 + 27b^2 = 0 mod p".to_string());
+        }
+        Ok(Self {
+            a: new_a,
+            b: new_b,
+            p: new_p,
+            order: OnceLock::new(),
+        })
+    }
+
+    // a curve whose number of points is already known, e.g. a standard curve
+    pub fn with_order(new_a: BigInt, new_b: BigInt, new_p: BigInt, order: BigInt) -> Result<Self, String> {
+        let curve = Self::new(new_a, new_b, new_p)?;
+        curve.order.get_or_init(|| order);
+        Ok(curve)
+    }
+
+    pub fn a(&self) -> &BigInt {
+        &self.a
+    }
+
+    pub fn b(&self) -> &BigInt {
+        &self.b
+    }
+
+    pub fn p(&self) -> &BigInt {
+        &self.p
+    }
+
+    // the point at infinity belongs to every curve
+    pub fn is_point(&self, point: &Point) -> bool {
+        match point {
+            Point::Infinity => true,
+            Point::Affine(x, y) => {
+                let lhs = (y * y) % &self.p;
+                let rhs = (x * x * x + &self.a * x + &self.b) % &self.p;
+                lhs == rhs
+            }
+        }
+    }
+
+    // length in bytes of an element of F_p
+    pub fn field_len(&self) -> usize {
+        self.p.bits().div_ceil(8) as usize
+    }
+
+    // SEC1 (section 2.3.3): 0x00 for ∞, 0x04 || x || y uncompressed, or 0x02 / 0x03 || x
+    // compressed, the tag carrying the parity of y; coordinates take field_len bytes. Only
+    // points of the curve with coordinates in [0, p) have an encoding
+    pub fn encode_point(&self, point: &Point, compressed: bool) -> Result<Vec<u8>, String> {
+        let (x, y) = match point {
+            Point::Infinity => return Ok(vec![0x00]),
+            Point::Affine(x, y) => (x, y),
+        };
+        if [x, y].iter().any(|value| value.is_negative() || *value >= &self.p) {
+            return Err("coordinate is not an element of F_p".to_string());
+        }
+        if !self.is_point(point) {
+            return Err("the point is not on the curve".to_string());
+        }
+        let field_len = self.field_len();
+        let fixed = |value: &BigInt| {
+            let (_, bytes) = value.to_bytes_be();
+            let mut out = vec![0u8; field_len - bytes.len()];
+            out.extend(bytes);
+            out
+        };
+        let mut out = if compressed {
+            vec![if y.bit(0) { 0x03 } else { 0x02 }]
+        } else {
+            vec![0x04]
+        };
+        out.extend(fixed(x));
+        if !compressed {
+            out.extend(fixed(y));
+        }
+        Ok(out)
+    }
+
+    // SEC1 (section 2.3.4): the inverse of encode_point. A compressed point gets y from a
+    // square root of x^3 + ax + b, and every decoded point is checked to lie on the curve
+    pub fn decode_point(&self, bytes: &[u8]) -> Result<Point, String> {
+        let field_len = self.field_len();
+        let element = |chunk: &[u8]| -> Result<BigInt, String> {
+            let value = BigInt::from_bytes_be(Sign::Plus, chunk);
+            if value >= self.p {
+                return Err("coordinate is not an element of F_p".to_string());
+            }
+            Ok(value)
+        };
+        let point = match (bytes.first(), bytes.len()) {
+            (Some(0x00), 1) => return Ok(Point::Infinity),
+            (Some(0x04), len) if len == 1 + 2 * field_len => {
+                let (x, y) = bytes[1..].split_at(field_len);
+                Point::new(element(x)?, element(y)?)
+            }
+            (Some(&tag @ (0x02 | 0x03)), len) if len == 1 + field_len => {
+                let x = element(&bytes[1..])?;
+                let rhs = self.reduce(&x * &x * &x + &self.a * &x + &self.b);
+                let root = basic_op::sqrt_mod(&rhs, &self.p)
+                    .ok_or_else(|| "x is not the abscissa of a point of the curve".to_string())?;
+                let odd = tag == 0x03;
+                let y = if root.bit(0) == odd {
+                    root
+                } else if root.is_zero() {
+                    return Err("y = 0 has no odd encoding".to_string());
+                } else {
+                    &self.p - root
+                };
+                Point::new(x, y)
+            }
+            (Some(0x00 | 0x02 | 0x03 | 0x04), _) => {
+                return Err("wrong length for the encoding of a point of this curve".to_string())
+            }
+            _ => return Err("unknown point encoding tag".to_string()),
+        };
+        if !self.is_point(&point) {
+            return Err("the point is not on the curve".to_string());
+        }
+        Ok(point)
+    }
+
+    pub fn point_add(&self, point_a: &Point, point_b: &Point) -> Point {
+        let (x1, y1, x2, y2) = match (point_a, point_b) {
+            (Point::Infinity, _) => return point_b.clone(),
+            (_, Point::Infinity) => return point_a.clone(),
+            (Point::Affine(x1, y1), Point::Affine(x2, y2)) => (x1, y1, x2, y2),
+        };
+
+        if x1 == x2 && (y1 != y2 || y1.is_zero()) {
+            // P + (-P), which includes doubling a point of order 2 (y = 0)
+            return Point::Infinity;
+        }
+
+        let l = if x1 != x2 {
+            // P != Q
+            let numerator = y2 - y1;
+            let denominator = x2 - x1;
+            basic_op::inv_mod(&denominator, &self.p).unwrap() * numerator % &self.p
+        } else {
+            // P == Q
+            let numerator = BigInt::from(3) * x1 * x1 + &self.a;
+            let denominator = BigInt::from(2) * y1;
+            basic_op::inv_mod(&denominator, &self.p).unwrap() * numerator % &self.p
+        };
+
+        let mut x3 = (&l * &l - x1 - x2) % &self.p;
+        let mut y3 = (&l * (x1 - &x3) - y1) % &self.p;
+
+        if x3 < BigInt::zero() {
+            x3 += &self.p;
+        }
+        if y3 < BigInt::zero() {
+            y3 += &self.p;
+        }
+
+        Point::new(x3, y3)
+    }
+
+    pub fn negate(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => Point::new(x.clone(), (&self.p - y) % &self.p),
+        }
+    }
+
+    // left-to-right double-and-add in Jacobian coordinates; d <= 0 gives the point at infinity
+    pub fn scalar_mul(&self, point: &Point, d: &BigInt) -> Point {
+        if d <= &BigInt::zero() {
+            return Point::Infinity;
+        }
+        let base = self.to_jacobian(point);
+        let mut acc = Jacobian::infinity();
+        for i in (0..d.bits()).rev() {
+            acc = self.jacobian_double(&acc);
+            if d.bit(i) {
+                acc = self.jacobian_add(&acc, &base);
+            }
+        }
+        self.to_affine(&acc)
+    }
+
+    // Montgomery ladder for secret scalars. d is taken modulo #E and padded to
+    // k = d + #E or d + 2 * #E, whichever has exactly bits(#E) + 1 bits, so the ladder
+    // always runs bits(#E) steps of one addition and one doubling, and the two
+    // registers are swapped arithmetically rather than by branching on the bits of d.
+    // BigInt arithmetic itself is not constant time; this removes the dependence of the
+    // sequence and number of group operations on the secret
+    pub fn scalar_mul_ct(&self, point: &Point, d: &BigInt) -> Point {
+        if point.is_infinity() {
+            return Point::Infinity;
+        }
+        let order = self.order();
+        let bits = order.bits();
+        let mut k = d % &order;
+        if k < BigInt::zero() {
+            k += &order;
+        }
+        k += &order;
+        // k + #E when k < 2^bits, otherwise k stays
+        let short = BigInt::from(u8::from(!k.bit(bits)));
+        k += short * &order;
+
+        let mut r0 = self.to_jacobian(point);
+        let mut r1 = self.jacobian_double(&r0);
+        for i in (0..bits).rev() {
+            let bit = BigInt::from(u8::from(k.bit(i)));
+            Self::cswap(&mut r0, &mut r1, &bit);
+            r1 = self.jacobian_add(&r0, &r1);
+            r0 = self.jacobian_double(&r0);
+            Self::cswap(&mut r0, &mut r1, &bit);
+        }
+        self.to_affine(&r0)
+    }
+
+    // swaps a and b when bit = 1 and leaves them when bit = 0, with the same operations
+    // in both cases: t = bit * (a - b), a -= t, b += t
+    fn cswap(a: &mut Jacobian, b: &mut Jacobian, bit: &BigInt) {
+        for (u, v) in [(&mut a.x, &mut b.x), (&mut a.y, &mut b.y), (&mut a.z, &mut b.z)] {
+            let t = bit * (&*u - &*v);
+            *u -= &t;
+            *v += t;
+        }
+    }
+
+    // width-w NAF of d > 0, least significant digit first: every non-zero digit is odd,
+    // |digit| < 2^(w-1), and any w consecutive digits hold at most one non-zero
+    fn wnaf(d: &BigInt, window: u32) -> Vec<i64> {
+        let modulus = BigInt::from(1i64 << window);
+        let half = 1i64 << (window - 1);
+        let mut k = d.clone();
+        let mut digits = Vec::with_capacity(d.bits() as usize + 1);
+        while k > BigInt::zero() {
+            let digit = if k.bit(0) {
+                let low = i64::try_from(&(&k % &modulus)).unwrap();
+                let digit = if low >= half { low - (1i64 << window) } else { low };
+                k -= digit;
+                digit
+            } else {
+                0
+            };
+            digits.push(digit);
+            k >>= 1;
+        }
+        digits
+    }
+
+    // wNAF with w = 5 for public scalars, e.g. in verification: about bits / 6 additions
+    // instead of bits / 2. Variable time, so never for secrets; d <= 0 gives ∞
+    pub fn scalar_mul_wnaf(&self, point: &Point, d: &BigInt) -> Point {
+        const WINDOW: u32 = 5;
+        if d <= &BigInt::zero() || point.is_infinity() {
+            return Point::Infinity;
+        }
+        // P, 3P, 5P, ..., 15P
+        let base = self.to_jacobian(point);
+        let double = self.jacobian_double(&base);
+        let mut odd = vec![base];
+        for _ in 1..(1usize << (WINDOW - 2)) {
+            let next = self.jacobian_add(odd.last().unwrap(), &double);
+            odd.push(next);
+        }
+        let mut acc = Jacobian::infinity();
+        for digit in Self::wnaf(d, WINDOW).into_iter().rev() {
+            acc = self.jacobian_double(&acc);
+            if digit > 0 {
+                acc = self.jacobian_add(&acc, &odd[(digit / 2) as usize]);
+            } else if digit < 0 {
+                acc = self.jacobian_add(&acc, &self.jacobian_negate(&odd[(-digit / 2) as usize]));
+            }
+        }
+        self.to_affine(&acc)
+    }
+
+    // sum(k_i * P_i) for public scalars of any sign. Straus' interleaved wNAF shares one
+    // chain of doublings between all terms and suits small sums (u1 * G + u2 * Q in ECDSA);
+    // above PIPPENGER_MIN terms Pippenger's buckets need fewer additions per term
+    pub fn multi_scalar_mul(&self, terms: &[(Point, BigInt)]) -> Point {
+        const PIPPENGER_MIN: usize = 80;
+        let terms: Vec<(Jacobian, BigInt)> = terms
+            .iter()
+            .filter(|(point, k)| !point.is_infinity() && !k.is_zero())
+            .map(|(point, k)| {
+                if k < &BigInt::zero() {
+                    (self.jacobian_negate(&self.to_jacobian(point)), -k)
+                } else {
+                    (self.to_jacobian(point), k.clone())
+                }
+            })
+            .collect();
+        let sum = if terms.len() < PIPPENGER_MIN {
+            self.straus(&terms)
+        } else {
+            self.pippenger(&terms)
+        };
+        self.to_affine(&sum)
+    }
+
+    // interleaved wNAF with w = 5: each term keeps P, 3P, ..., 15P
+    fn straus(&self, terms: &[(Jacobian, BigInt)]) -> Jacobian {
+        const WINDOW: u32 = 5;
+        let tables: Vec<Vec<Jacobian>> = terms
+            .iter()
+            .map(|(point, _)| {
+                let double = self.jacobian_double(point);
+                let mut odd = vec![point.clone()];
+                for _ in 1..(1usize << (WINDOW - 2)) {
+                    let next = self.jacobian_add(odd.last().unwrap(), &double);
+                    odd.push(next);
+                }
+                odd
+            })
+            .collect();
+        let digits: Vec<Vec<i64>> = terms.iter().map(|(_, k)| Self::wnaf(k, WINDOW)).collect();
+        let len = digits.iter().map(|d| d.len()).max().unwrap_or(0);
+        let mut acc = Jacobian::infinity();
+        for i in (0..len).rev() {
+            acc = self.jacobian_double(&acc);
+            for (table, digits) in tables.iter().zip(&digits) {
+                match digits.get(i) {
+                    Some(&digit) if digit > 0 => {
+                        acc = self.jacobian_add(&acc, &table[(digit / 2) as usize]);
+                    }
+                    Some(&digit) if digit < 0 => {
+                        acc = self.jacobian_add(&acc, &self.jacobian_negate(&table[(-digit / 2) as usize]));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        acc
+    }
+
+    // Pippenger: for every window of c bits, the points go into 2^c - 1 buckets by their
+    // digit, and the running sums of the buckets weight bucket j by j at no extra cost
+    fn pippenger(&self, terms: &[(Jacobian, BigInt)]) -> Jacobian {
+        // c ≈ log2(#terms) - 2 balances #terms additions against 2^(c+1) per window
+        let log_len = usize::BITS - terms.len().leading_zeros();
+        let c = log_len.saturating_sub(2).clamp(2, 16) as u64;
+        let bits = terms.iter().map(|(_, k)| k.bits()).max().unwrap_or(0);
+        let mut acc = Jacobian::infinity();
+        for window in (0..bits.div_ceil(c)).rev() {
+            for _ in 0..c {
+                acc = self.jacobian_double(&acc);
+            }
+            let mut buckets = vec![Jacobian::infinity(); (1usize << c) - 1];
+            for (point, k) in terms {
+                let digit = (0..c).fold(0usize, |digit, j| digit | (usize::from(k.bit(window * c + j)) << j));
+                if digit > 0 {
+                    buckets[digit - 1] = self.jacobian_add(&buckets[digit - 1], point);
+                }
+            }
+            let mut running = Jacobian::infinity();
+            let mut window_sum = Jacobian::infinity();
+            for bucket in buckets.iter().rev() {
+                running = self.jacobian_add(&running, bucket);
+                window_sum = self.jacobian_add(&window_sum, &running);
+            }
+            acc = self.jacobian_add(&acc, &window_sum);
+        }
+        acc
+    }
+
+    pub(crate) fn jacobian_negate(&self, point: &Jacobian) -> Jacobian {
+        Jacobian {
+            x: point.x.clone(),
+            y: self.reduce(-&point.y),
+            z: point.z.clone(),
+        }
+    }
+
+    // x mod p in [0, p)
+    fn reduce(&self, x: BigInt) -> BigInt {
+        basic_op::reduce(&x, &self.p)
+    }
+
+    pub(crate) fn to_jacobian(&self, point: &Point) -> Jacobian {
+        match point {
+            Point::Infinity => Jacobian::infinity(),
+            Point::Affine(x, y) => Jacobian {
+                x: self.reduce(x.clone()),
+                y: self.reduce(y.clone()),
+                z: BigInt::one(),
+            },
+        }
+    }
+
+    // the only inversion: x = X / Z^2, y = Y / Z^3
+    pub(crate) fn to_affine(&self, point: &Jacobian) -> Point {
+        if point.is_infinity() {
+            return Point::Infinity;
+        }
+        let z_inv = basic_op::inv_mod(&point.z, &self.p).unwrap();
+        let z_inv2 = (&z_inv * &z_inv) % &self.p;
+        let x = self.reduce(&point.x * &z_inv2);
+        let y = self.reduce(&point.y * z_inv2 * z_inv);
+        Point::new(x, y)
+    }
+
+    // dbl-2007-bl for any a: 4M + 4S plus the multiplication by a
+    pub(crate) fn jacobian_double(&self, point: &Jacobian) -> Jacobian {
+        if point.is_infinity() || point.y.is_zero() {
+            // 2P = ∞ for a point of order 2 (y = 0)
+            return Jacobian::infinity();
+        }
+        let p = &self.p;
+        let xx = (&point.x * &point.x) % p;
+        let yy = (&point.y * &point.y) % p;
+        let yyyy = (&yy * &yy) % p;
+        let zz = (&point.z * &point.z) % p;
+        let s = (BigInt::from(4) * &point.x * &yy) % p;
+        let m = self.reduce(BigInt::from(3) * xx + &self.a * &zz * &zz);
+        let x3 = self.reduce(&m * &m - BigInt::from(2) * &s);
+        let y3 = self.reduce(m * (s - &x3) - BigInt::from(8) * yyyy);
+        let z3 = (BigInt::from(2) * &point.y * &point.z) % p;
+        Jacobian { x: x3, y: y3, z: z3 }
+    }
+
+    // add-2007-bl, with the cases the formula cannot handle sent to their exact result:
+    // ∞ + Q = Q, P + P = 2P and P + (-P) = ∞
+    pub(crate) fn jacobian_add(&self, point_a: &Jacobian, point_b: &Jacobian) -> Jacobian {
+        if point_a.is_infinity() {
+            return point_b.clone();
+        }
+        if point_b.is_infinity() {
+            return point_a.clone();
+        }
+        let p = &self.p;
+        let z1z1 = (&point_a.z * &point_a.z) % p;
+        let z2z2 = (&point_b.z * &point_b.z) % p;
+        let u1 = (&point_a.x * &z2z2) % p;
+        let u2 = (&point_b.x * &z1z1) % p;
+        let s1 = (&point_a.y * &point_b.z * &z2z2) % p;
+        let s2 = (&point_b.y * &point_a.z * &z1z1) % p;
+        let h = self.reduce(u2 - &u1);
+        let r = self.reduce(s2 - &s1);
+        if h.is_zero() {
+            return if r.is_zero() {
+                self.jacobian_double(point_a)
+            } else {
+                Jacobian::infinity()
+            };
+        }
+        let hh = (&h * &h) % p;
+        let hhh = (&h * &hh) % p;
+        let v = (u1 * hh) % p;
+        let x3 = self.reduce(&r * &r - &hhh - BigInt::from(2) * &v);
+        let y3 = self.reduce(r * (v - &x3) - s1 * hhh);
+        let z3 = (&point_a.z * &point_b.z * h) % p;
+        Jacobian { x: x3, y: y3, z: z3 }
+    }
+
+    // generate a key pair: private and public
+    pub fn gen_key_pair(&self, generator: &Point) -> Result<KeyPair, String> {
+        let ord = generator.order(self);
+        if ord <= BigInt::one() {
+            return Err("the order of the generator must be greater than 1.".to_string());
+        }
+
+        // generate a random number between 1 and ord - 1
+        let private_key = basic_op::random_scalar(&ord);
+
+        // generate the public key using scalar multiplication
+        let public_key = self.scalar_mul_ct(generator, &private_key);
+
+        Ok(KeyPair {
+            sk: private_key,
+            pk: public_key,
+        })
+    }
+
+    // the points of `group_points` whose order is the order of the whole group, none when
+    // the group is not cyclic (see group_structure)
+    pub fn get_base_points(&self, group_points: &[Point]) -> Vec<Point> {
+        let n = self.order(); // the order of the group
+        let factors = basic_op::factorize(&n);
+
+        group_points
+            .iter()
+            .filter(|point| self.order_of(point, &n, &factors) == n)
+            .cloned()
+            .collect()
+    }
+
+    // number of points of E(F_p), the point at infinity included, counted with the
+    // method `CountingMethod::for_curve` picks. The result is cached.
+    pub fn order(&self) -> BigInt {
+        match self.order.get() {
+            Some(order) => order.clone(),
+            // count_points_with fills the cache
+            None => self.point_count().order,
+        }
+    }
+
+    // t = p + 1 - #E
+    pub fn trace(&self) -> BigInt {
+        &self.p + 1 - self.order()
+    }
+
+    // #E and t with the method chosen by `CountingMethod::for_curve`
+    pub fn point_count(&self) -> PointCount {
+        self.count_points_with(CountingMethod::for_curve(self))
+            .expect("the default counting method applies to every curve")
+    }
+
+    // #E and t with the given method. The count is checked against Hasse's bound and a
+    // random point before it is returned, and then fills the cache used by `order`
+    pub fn count_points_with(&self, method: CountingMethod) -> Result<PointCount, String> {
+        let p = &self.p;
+        if p <= &BigInt::from(3) || !basic_op::is_probable_prime(p) {
+            return Err(format!("p = {} must be a prime above 3", p));
+        }
+        let order = match method {
+            CountingMethod::Legendre if p.bits() > LEGENDRE_LIMIT_BITS => {
+                return Err(format!("the Legendre sum takes p steps; p has {} bits", p.bits()));
+            }
+            CountingMethod::Legendre => self.count_points_legendre(),
+            // Mestre's argument needs p > 229
+            CountingMethod::Mestre | CountingMethod::Sea if p <= &BigInt::from(229) => {
+                return Err("baby-step giant-step in the Hasse interval needs p > 229".to_string());
+            }
+            CountingMethod::Mestre => self.order_from_trace(&BigInt::zero(), &BigInt::one()),
+            CountingMethod::Schoof => schoof::count_points(self),
+            CountingMethod::Sea => schoof::count_points_sea(self),
+            CountingMethod::ComplexMultiplication => self
+                .count_points_cm()
+                .ok_or_else(|| "complex multiplication needs j = 0 or 1728, i.e. a = 0 or b = 0".to_string())?,
+        };
+        let count = PointCount {
+            trace: p + 1 - &order,
+            order,
+            method,
+        };
+        if !count.satisfies_hasse(p) {
+            return Err(format!("t = {} violates Hasse's bound |t| <= 2 sqrt(p)", count.trace));
+        }
+        if !self.scalar_mul(&self.random_point(), &count.order).is_infinity() {
+            return Err(format!("{} does not annihilate a random point", count.order));
+        }
+        self.order.get_or_init(|| count.order.clone());
+        Ok(count)
+    }
+
+    // j = 1728 4a^3 / (4a^3 + 27b^2)
+    pub fn j_invariant(&self) -> BigInt {
+        let four_a3 = BigInt::from(4) * &self.a * &self.a * &self.a;
+        let denominator = self.reduce(&four_a3 + BigInt::from(27) * &self.b * &self.b);
+        self.reduce(BigInt::from(1728) * four_a3 * basic_op::inv_mod(&denominator, &self.p).unwrap())
+    }
+
+    // the same number through Schoof's algorithm, polynomial in log p; it also fills
+    // the cache used by `order`
+    pub fn order_schoof(&self) -> BigInt {
+        let order = schoof::count_points(self);
+        self.order.get_or_init(|| order.clone());
+        order
+    }
+
+    // #E / ord(G), the index of the subgroup generated by G
+    pub fn cofactor(&self, generator: &Point) -> BigInt {
+        self.order() / generator.order(self)
+    }
+
+    // whether the point is on the curve and in the subgroup of order n
+    pub fn is_in_subgroup(&self, point: &Point, n: &BigInt) -> bool {
+        self.is_point(point) && self.scalar_mul(point, n).is_infinity()
+    }
+
+    // h * P, which lands in the subgroup of order #E / h
+    pub fn clear_cofactor(&self, point: &Point, cofactor: &BigInt) -> Point {
+        self.scalar_mul(point, cofactor)
+    }
+
+    // checks the curve and its largest prime-order subgroup: discriminant, primality of p
+    // and of n, cofactor, embedding degree, anomalous trace, security of the quadratic
+    // twist and j-invariant. It needs #E, which SEA counts in seconds up to about 128 bits;
+    // larger curves with j != 0, 1728 should come with their order (`with_order`)
+    pub fn validate(&self) -> ValidationReport {
+        ValidationReport {
+            findings: self.findings(None, None),
+        }
+    }
+
+    // the findings for the subgroup of order n with cofactor h, or for the largest
+    // prime-order subgroup when they are not given
+    fn findings(&self, n: Option<&BigInt>, h: Option<&BigInt>) -> Vec<Finding> {
+        let p = &self.p;
+        let mut findings = Vec::new();
+        let mut add = |check, severity, message: String| findings.push(Finding { check, severity, message });
+
+        let four_a3 = BigInt::from(4) * &self.a * &self.a * &self.a;
+        let singular_part = self.reduce(&four_a3 + BigInt::from(27) * &self.b * &self.b);
+        let discriminant = self.reduce(BigInt::from(-16) * &singular_part);
+        if p <= &BigInt::from(3) {
+            add(Check::Discriminant, Severity::Error, "y^2 = x^3 + ax + b only covers all curves when p > 3".to_string());
+        } else {
+            add(Check::Discriminant, Severity::Info, format!("-16 (4a^3 + 27b^2) = {} != 0", discriminant));
+        }
+
+        if !basic_op::is_probable_prime(p) {
+            // nothing below makes sense over a ring that is not a field
+            add(Check::FieldPrime, Severity::Error, format!("p = {} is not prime", p));
+            return findings;
+        }
+        add(Check::FieldPrime, Severity::Info, format!("p is a {}-bit prime", p.bits()));
+
+        let order = self.order();
+        let n = match n {
+            Some(n) => n.clone(),
+            None => basic_op::factorize(&order).last().map(|(q, _)| q.clone()).unwrap_or_else(BigInt::one),
+        };
+        if !basic_op::is_probable_prime(&n) || !(&order % &n).is_zero() {
+            add(Check::SubgroupOrder, Severity::Error, format!("n = {} is not a prime divisor of #E = {}", n, order));
+            return findings;
+        }
+        let rho_bits = n.bits() / 2;
+        if rho_bits < MIN_RHO_BITS {
+            add(
+                Check::SubgroupOrder,
+                Severity::Warning,
+                format!("n has {} bits: Pollard's rho needs about 2^{} steps", n.bits(), rho_bits),
+            );
+        } else {
+            add(Check::SubgroupOrder, Severity::Info, format!("n is a {}-bit prime", n.bits()));
+        }
+
+        let cofactor = &order / &n;
+        match h {
+            Some(h) if h != &cofactor => add(
+                Check::Cofactor,
+                Severity::Error,
+                format!("h = {} but #E / n = {}", h, cofactor),
+            ),
+            _ if cofactor > BigInt::from(4) => add(
+                Check::Cofactor,
+                Severity::Warning,
+                format!("h = {}: points must be checked for small-subgroup components", cofactor),
+            ),
+            _ => add(Check::Cofactor, Severity::Info, format!("h = {}", cofactor)),
+        }
+
+        // the least k with n | p^k - 1: pairings (MOV, Frey-Ruck) move the DLP into F_(p^k)
+        if &n != p {
+            let base = p % &n;
+            let mut power = base.clone();
+            let degree = (1..=MAX_WEAK_EMBEDDING_DEGREE).find(|_| {
+                let found = power.is_one();
+                power = &power * &base % &n;
+                found
+            });
+            match degree {
+                Some(k) => add(
+                    Check::EmbeddingDegree,
+                    Severity::Warning,
+                    format!("embedding degree {}: the MOV attack moves the DLP into F_p^{}", k, k),
+                ),
+                None => add(
+                    Check::EmbeddingDegree,
+                    Severity::Info,
+                    format!("embedding degree above {}", MAX_WEAK_EMBEDDING_DEGREE),
+                ),
+            }
+        }
+
+        // #E = p, or a subgroup of order p: Smart's attack lifts the DLP to the p-adics
+        let trace: BigInt = p + 1 - &order;
+        if trace.is_one() || &n == p {
+            add(
+                Check::Anomalous,
+                Severity::Error,
+                "anomalous: the subgroup has order p and Smart's attack solves the DLP in linear time".to_string(),
+            );
+        } else {
+            add(Check::Anomalous, Severity::Info, format!("trace of Frobenius t = {}", trace));
+        }
+
+        // an x-only ladder that skips the on-curve check also computes on the twist
+        let twist_order = BigInt::from(2) * p + 2 - &order;
+        let twist_prime = basic_op::factorize(&twist_order).last().map(|(q, _)| q.clone()).unwrap_or_else(BigInt::one);
+        if twist_prime.bits() / 2 < MIN_RHO_BITS {
+            add(
+                Check::TwistSecurity,
+                Severity::Warning,
+                format!(
+                    "#E' = {} has largest prime factor {}: rho takes about 2^{} steps on the twist",
+                    twist_order,
+                    twist_prime,
+                    twist_prime.bits() / 2
+                ),
+            );
+        } else {
+            add(
+                Check::TwistSecurity,
+                Severity::Info,
+                format!("the twist has a {}-bit prime-order subgroup", twist_prime.bits()),
+            );
+        }
+
+        // 0 and 1728 have extra automorphisms (GLV)
+        let j = self.j_invariant();
+        let note = if j.is_zero() || j == BigInt::from(1728) % p {
+            ": extra automorphisms speed up both scalar multiplication and rho"
+        } else {
+            ""
+        };
+        add(Check::JInvariant, Severity::Info, format!("j = {}{}", j, note));
+        findings
+    }
+
+    // a point of order exactly n, if the group has one; n must divide #E
+    pub fn subgroup_generator(&self, n: &BigInt) -> Option<Point> {
+        let order = self.order();
+        if n <= &BigInt::one() || !(&order % n).is_zero() {
+            return None;
+        }
+        let factors = basic_op::factorize(n);
+        let cofactor = &order / n;
+        for _ in 0..64 {
+            let candidate = self.clear_cofactor(&self.random_point(), &cofactor);
+            if self.order_of(&candidate, n, &factors) == *n {
+                return Some(candidate);
+            }
+        }
+        None
+    }
+
+    // a uniformly chosen affine point: random x until x^3 + ax + b is a square
+    pub fn random_point(&self) -> Point {
+        let mut rng = rand::thread_rng();
+        loop {
+            let x = rng.gen_bigint_range(&BigInt::zero(), &self.p);
+            let rhs = (&x * &x * &x + &self.a * &x + &self.b) % &self.p;
+            if let Some(y) = basic_op::sqrt_mod(&rhs, &self.p) {
+                let y = if rng.gen::<bool>() { y } else { (&self.p - y) % &self.p };
+                return Point::new(x, y);
+            }
+        }
+    }
+
+    // order of a point given a multiple n of it and the factorization of n
+    fn order_of(&self, point: &Point, n: &BigInt, factors: &[(BigInt, u32)]) -> BigInt {
+        let mut order = n.clone();
+        for (prime, _) in factors {
+            while (&order % prime).is_zero() && self.scalar_mul(point, &(&order / prime)).is_infinity() {
+                order /= prime;
+            }
+        }
+        order
+    }
+
+    // #E = p + 1 + sum over x of (x^3 + ax + b / p)
+    fn count_points_legendre(&self) -> BigInt {
+        let mut sum = BigInt::zero();
+        let mut x = BigInt::zero();
+        while x < self.p {
+            let rhs = &x * &x * &x + &self.a * &x + &self.b;
+            sum += basic_op::legendre(&rhs, &self.p);
+            x += 1;
+        }
+        &self.p + 1 + sum
+    }
+
+    // y^2 = x^3 + a d^2 x + b d^3 for a non-residue d
+    fn quadratic_twist(&self) -> EcWei {
+        let mut d = BigInt::from(2);
+        while basic_op::legendre(&d, &self.p) != -1 {
+            d += 1;
+        }
+        let d2 = (&d * &d) % &self.p;
+        // the discriminant only gains the factor d^6
+        EcWei::new(
+            (&self.a * &d2) % &self.p,
+            (&self.b * d2 * d) % &self.p,
+            self.p.clone(),
+        )
+        .unwrap()
+    }
+
+    // #E, given t mod m: baby-step giant-step over the values of t left in the Hasse
+    // interval, on random points of E and of its twist. With m = 1 this is Mestre's
+    // method; #E + #E' = 2p + 2, and for p > 229 one of the two groups always has a
+    // point whose order has a single multiple among the candidates
+    pub(crate) fn order_from_trace(&self, trace: &BigInt, modulus: &BigInt) -> BigInt {
+        let twist = self.quadratic_twist();
+        let twist_trace = (modulus - trace % modulus) % modulus;
+        loop {
+            if let Some(order) = self.unique_hasse_multiple(&self.random_point(), trace, modulus) {
+                return order;
+            }
+            if let Some(order) = twist.unique_hasse_multiple(&twist.random_point(), &twist_trace, modulus) {
+                return BigInt::from(2) * &self.p + 2 - order;
+            }
+        }
+    }
+
+    // the only m = p + 1 - t with |t| <= 2 sqrt(p), t = trace (mod modulus) and m * P = ∞,
+    // found by baby-step giant-step; None if there are several
+    fn unique_hasse_multiple(&self, point: &Point, trace: &BigInt, modulus: &BigInt) -> Option<BigInt> {
+        let width: BigInt = (&self.p * 4u32).sqrt() + 1u32;
+        // the smallest candidate m = p + 1 - t comes from the largest t <= width
+        let top = &width - basic_op::reduce(&(&width - trace), modulus);
+        let low = &self.p + 1 - &top;
+        let count: BigInt = (&top + &width) / modulus + 1;
+        let steps: BigInt = count.sqrt() + 1u32;
+        let stride = self.scalar_mul(point, modulus);
+
+        // baby steps j * (modulus P) for 0 <= j < steps
+        let mut baby = HashMap::new();
+        let mut current = Point::Infinity;
+        for j in 0..steps.to_u64()? {
+            if j > 0 && current.is_infinity() {
+                return None; // the order is tiny, so it has many multiples among the candidates
+            }
+            baby.insert(current.clone(), j);
+            current = self.point_add(&current, &stride);
+        }
+
+        // giant steps (low + i * steps * modulus) * P, matched against -j * modulus * P
+        let mut found: Option<BigInt> = None;
+        let mut giant = self.scalar_mul(point, &low);
+        for i in 0..=steps.to_u64()? {
+            if let Some(j) = baby.get(&self.negate(&giant)) {
+                let k = i * &steps + j;
+                if k < count {
+                    let m = &low + k * modulus;
+                    if found.as_ref().is_some_and(|other| other != &m) {
+                        return None;
+                    }
+                    found = Some(m);
+                }
+            }
+            giant = self.point_add(&giant, &current);
+        }
+        found
+    }
+
+    // j = 0 (a = 0) or j = 1728 (b = 0). If p = 2 mod 3, resp. 3 mod 4, the curve is
+    // supersingular and #E = p + 1. Otherwise p = c^2 + 3d^2, resp. c^2 + d^2, the
+    // Frobenius is c + d sqrt(-3), resp. c + di, times a unit, and the six (four)
+    // candidates for t are told apart on random points of E and of its twist
+    fn count_points_cm(&self) -> Option<BigInt> {
+        let p = &self.p;
+        let (d, residue) = if self.a.is_zero() {
+            (BigInt::from(3), p % 3u32)
+        } else if self.b.is_zero() {
+            (BigInt::one(), p % 4u32)
+        } else {
+            return None;
+        };
+        if !residue.is_one() {
+            return Some(p + 1);
+        }
+        let (c, e) = basic_op::cornacchia(&d, p)?;
+        let traces = if d.is_one() {
+            vec![BigInt::from(2) * &c, BigInt::from(2) * &e]
+        } else {
+            vec![BigInt::from(2) * &c, &c + BigInt::from(3) * &e, &c - BigInt::from(3) * &e]
+        };
+        let mut candidates: Vec<BigInt> = traces.iter().flat_map(|t| [p + 1 - t, p + 1 + t]).collect();
+        let twist = self.quadratic_twist();
+        for _ in 0..64 {
+            let point = self.random_point();
+            let twist_point = twist.random_point();
+            candidates.retain(|m| {
+                self.scalar_mul(&point, m).is_infinity()
+                    && twist.scalar_mul(&twist_point, &(BigInt::from(2) * p + 2 - m)).is_infinity()
+            });
+            if candidates.len() <= 1 {
+                break;
+            }
+        }
+        match candidates.as_slice() {
+            [order] => Some(order.clone()),
+            _ => None,
+        }
+    }
+}
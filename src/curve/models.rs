@@ -0,0 +1,517 @@
+// the Montgomery and twisted Edwards models beside the short Weierstrass one, with the
+// birational maps between them, and the CurveModel operations all three share
+use crate::arithmetic::basic_op::{self, reduce};
+use crate::arithmetic::poly_op;
+use crate::curve::cayley::CayleyTable;
+use crate::curve::ecc::{EcWei, Point};
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, ToPrimitive, Zero};
+
+// the operations the group listing and the Cayley table need, shared by the
+// Weierstrass, Montgomery and Edwards models
+pub trait CurveModel {
+    fn p(&self) -> &BigInt;
+
+    fn is_point(&self, point: &Point) -> bool;
+
+    // ∞, except on Edwards curves where the identity is the affine point (0, 1)
+    fn identity(&self) -> Point;
+
+    fn point_add(&self, point_a: &Point, point_b: &Point) -> Point;
+
+    // every point but the identity, which the Cayley table always prints first
+    fn group_points(&self) -> Vec<Point> {
+        let identity = self.identity();
+        let mut points = Vec::new();
+        for x in 0..self.p().to_i64().unwrap() {
+            for y in 0..self.p().to_i64().unwrap() {
+                let point = Point::new(BigInt::from(x), BigInt::from(y));
+                if point != identity && self.is_point(&point) {
+                    points.push(point);
+                }
+            }
+        }
+
+        points
+    }
+
+    // the Cayley table of the identity and `points`, whatever the identity's position in
+    // `points` (or its absence)
+    fn cayley(&self, points: &[Point]) -> CayleyTable {
+        let identity = self.identity();
+        let elements: Vec<Point> = std::iter::once(identity.clone())
+            .chain(points.iter().filter(|point| **point != identity).cloned())
+            .collect();
+        let entries = elements
+            .iter()
+            .map(|a| elements.iter().map(|b| self.point_add(a, b)).collect())
+            .collect();
+        CayleyTable::new(elements, entries)
+    }
+
+    // prints the table under a banner
+    fn cayley_table(&self, points: &[Point]) {
+        let ascii_title = r#"
+ $$$$$$\   $$$$$$\ $$\     $$\ $$\       $$$$$$$$\ $$\     $$\ $$\  $$$$$$\        $$$$$$$$\  $$$$$$\  $$$$$$$\  $$\       $$$$$$$$\ 
+$$  __$$\ $$  __$$\\$$\   $$  |$$ |      $$  _____|\$$\   $$  |$  |$$  __$$\       \__$$  __|$$  __$$\ $$  __$$\ $$ |      $$  _____|
+$$ /  \__|$$ /  $$ |\$$\ $$  / $$ |      $$ |       \$$\ $$  / \_/ $$ /  \__|         $$ |   $$ /  $$ |$$ |  $$ |$$ |      $$ |      
+$$ |      $$$$$$$$ | \$$$$  /  $$ |      $$$$$\      \$$$$  /      \$$$$$$\           $$ |   $$$$$$$$ |$$$$$$$\ |$$ |      $$$$$\    
+$$ |      $$  __$$ |  \$$  /   $$ |      $$  __|      \$$  /        \____$$\          $$ |   $$  __$$ |$$  __$$\ $$ |      $$  __|   
+$$ |  $$\ $$ |  $$ |   $$ |    $$ |      $$ |          $$ |        $$\   $$ |         $$ |   $$ |  $$ |$$ |  $$ |$$ |      $$ |      
+\$$$$$$  |$$ |  $$ |   $$ |    $$$$$$$$\ $$$$$$$$\     $$ |        \$$$$$$  |         $$ |   $$ |  $$ |$$$$$$$  |$$$$$$$$\ $$$$$$$$\ 
+ \______/ \__|  \__|   \__|    \________|\________|    \__|         \______/          \__|   \__|  \__|\_______/ \________|\________|
+    "#;
+        println!("{}", ascii_title);
+        print!("{}", self.cayley(points));
+    }
+}
+
+fn div_mod(x: BigInt, y: &BigInt, p: &BigInt) -> BigInt {
+    reduce(&(x * basic_op::inv_mod(y, p).unwrap()), p)
+}
+
+impl CurveModel for EcWei {
+    fn p(&self) -> &BigInt {
+        EcWei::p(self)
+    }
+
+    fn is_point(&self, point: &Point) -> bool {
+        EcWei::is_point(self, point)
+    }
+
+    fn identity(&self) -> Point {
+        Point::Infinity
+    }
+
+    fn point_add(&self, point_a: &Point, point_b: &Point) -> Point {
+        EcWei::point_add(self, point_a, point_b)
+    }
+}
+
+// Montgomery curve B y^2 = x^3 + A x^2 + x over F_p, with B (A^2 - 4) != 0. Every such
+// curve has the point (0, 0) of order 2, and the u-coordinate (x) of k * P can be computed
+// from the u-coordinate of P alone
+#[derive(Debug, Clone)]
+pub struct EcMont {
+    a: BigInt,
+    b: BigInt,
+    p: BigInt,
+}
+
+impl EcMont {
+    // refuses singular curves, B (A^2 - 4) = 0 mod p
+    pub fn new(new_a: BigInt, new_b: BigInt, new_p: BigInt) -> Result<Self, String> {
+        let (a, b) = (reduce(&new_a, &new_p), reduce(&new_b, &new_p));
+        if reduce(&(&b * (&a * &a - 4)), &new_p).is_zero() {
+            return Err("the curve is singular: B (A^2 - 4) = 0 mod p".to_string());
+        }
+        Ok(Self { a, b, p: new_p })
+    }
+
+    // Curve25519 (RFC 7748): A = 486662, B = 1 over 2^255 - 19; the base point has u = 9
+    pub fn curve25519() -> Self {
+        let p = (BigInt::one() << 255) - 19;
+        Self::new(BigInt::from(486662), BigInt::one(), p).unwrap()
+    }
+
+    pub fn a(&self) -> &BigInt {
+        &self.a
+    }
+
+    pub fn b(&self) -> &BigInt {
+        &self.b
+    }
+
+    pub fn p(&self) -> &BigInt {
+        &self.p
+    }
+
+    pub fn is_point(&self, point: &Point) -> bool {
+        match point {
+            Point::Infinity => true,
+            Point::Affine(x, y) => {
+                let lhs = reduce(&(&self.b * y * y), &self.p);
+                let rhs = reduce(&(x * x * x + &self.a * x * x + x), &self.p);
+                lhs == rhs
+            }
+        }
+    }
+
+    pub fn point_add(&self, point_a: &Point, point_b: &Point) -> Point {
+        let (x1, y1, x2, y2) = match (point_a, point_b) {
+            (Point::Infinity, _) => return point_b.clone(),
+            (_, Point::Infinity) => return point_a.clone(),
+            (Point::Affine(x1, y1), Point::Affine(x2, y2)) => (x1, y1, x2, y2),
+        };
+        if x1 == x2 && (y1 != y2 || y1.is_zero()) {
+            return Point::Infinity;
+        }
+        let p = &self.p;
+        let l = if x1 != x2 {
+            div_mod(y2 - y1, &(x2 - x1), p)
+        } else {
+            div_mod(BigInt::from(3) * x1 * x1 + BigInt::from(2) * &self.a * x1 + 1, &(BigInt::from(2) * &self.b * y1), p)
+        };
+        let x3 = reduce(&(&self.b * &l * &l - &self.a - x1 - x2), p);
+        let y3 = reduce(&(&l * (x1 - &x3) - y1), p);
+        Point::new(x3, y3)
+    }
+
+    pub fn negate(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => Point::new(x.clone(), (&self.p - y) % &self.p),
+        }
+    }
+
+    // affine double-and-add; d <= 0 gives the point at infinity
+    pub fn scalar_mul(&self, point: &Point, d: &BigInt) -> Point {
+        let mut acc = Point::Infinity;
+        if d <= &BigInt::zero() {
+            return acc;
+        }
+        for i in (0..d.bits()).rev() {
+            acc = self.point_add(&acc, &acc);
+            if d.bit(i) {
+                acc = self.point_add(&acc, point);
+            }
+        }
+        acc
+    }
+
+    // x-only Montgomery ladder (RFC 7748, section 5) on (X : Z) with u = X / Z: the
+    // u-coordinate of k * P from the u-coordinate of P, which is the same for P and -P.
+    // Every bit of k costs one differential addition and one doubling, and the registers
+    // are swapped arithmetically. None when k * P = ∞
+    pub fn ladder_x(&self, u: &BigInt, k: &BigInt) -> Option<BigInt> {
+        let p = &self.p;
+        let k = k.magnitude();
+        let x1 = reduce(u, p);
+        // (0, 0) has order 2 and breaks the differential addition
+        if x1.is_zero() {
+            return if k.bit(0) { Some(BigInt::zero()) } else { None };
+        }
+        let a24 = div_mod(&self.a - 2, &BigInt::from(4), p);
+        let mut r0 = (BigInt::one(), BigInt::zero());
+        let mut r1 = (x1.clone(), BigInt::one());
+        for i in (0..k.bits()).rev() {
+            let bit = BigInt::from(u8::from(k.bit(i)));
+            Self::cswap(&mut r0, &mut r1, &bit);
+            let (x2, z2) = &r0;
+            let (x3, z3) = &r1;
+            let aa = (x2 + z2) * (x2 + z2) % p;
+            let bb = reduce(&((x2 - z2) * (x2 - z2)), p);
+            let e = reduce(&(&aa - &bb), p);
+            let da = reduce(&((x3 - z3) * (x2 + z2)), p);
+            let cb = reduce(&((x3 + z3) * (x2 - z2)), p);
+            r1 = ((&da + &cb) * (&da + &cb) % p, reduce(&(&x1 * (&da - &cb) * (&da - &cb)), p));
+            r0 = (&aa * &bb % p, &e * (&aa + &a24 * &e) % p);
+            Self::cswap(&mut r0, &mut r1, &bit);
+        }
+        if r0.1.is_zero() {
+            None
+        } else {
+            Some(div_mod(r0.0, &r0.1, p))
+        }
+    }
+
+    fn cswap(a: &mut (BigInt, BigInt), b: &mut (BigInt, BigInt), bit: &BigInt) {
+        for (u, v) in [(&mut a.0, &mut b.0), (&mut a.1, &mut b.1)] {
+            let t = bit * (&*u - &*v);
+            *u -= &t;
+            *v += t;
+        }
+    }
+
+    // the short Weierstrass form y^2 = x^3 + ax + b with a = (3 - A^2) / (3 B^2) and
+    // b = (2 A^3 - 9 A) / (27 B^3), reached by (u, v) -> (u / B + A / (3 B), v / B); p > 3
+    pub fn to_weierstrass(&self) -> EcWei {
+        let (a, b, p) = (&self.a, &self.b, &self.p);
+        let new_a = div_mod(3 - a * a, &(3 * b * b), p);
+        let new_b = div_mod(2 * a * a * a - 9 * a, &(27 * b * b * b), p);
+        // non-singular as the Montgomery curve is
+        EcWei::new(new_a, new_b, p.clone()).unwrap()
+    }
+
+    pub fn point_to_weierstrass(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(u, v) => {
+                let p = &self.p;
+                let x = div_mod(3 * u + &self.a, &(3 * &self.b), p);
+                Point::new(x, div_mod(v.clone(), &self.b, p))
+            }
+        }
+    }
+
+    // the inverse of point_to_weierstrass: (x, y) -> (B x - A / 3, B y)
+    pub fn point_from_weierstrass(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => {
+                let p = &self.p;
+                let u = reduce(&(&self.b * x - div_mod(self.a.clone(), &BigInt::from(3), p)), p);
+                Point::new(u, reduce(&(&self.b * y), p))
+            }
+        }
+    }
+
+    // a Weierstrass curve has a Montgomery form when it has a point (alpha, 0) of order 2
+    // with 3 alpha^2 + a a square; with s = 1 / sqrt(3 alpha^2 + a) it is A = 3 alpha s,
+    // B = s, and the maps above become (x, y) <-> (s (x - alpha), s y)
+    pub fn from_weierstrass(curve: &EcWei) -> Result<EcMont, String> {
+        let p = curve.p();
+        let cubic = poly_op::from_coefficients(&[curve.b().clone(), curve.a().clone(), BigInt::zero(), BigInt::one()], p);
+        for alpha in poly_op::roots(&cubic, p) {
+            let t = reduce(&(3 * &alpha * &alpha + curve.a()), p);
+            if t.is_zero() {
+                continue;
+            }
+            if let Some(root) = basic_op::sqrt_mod(&t, p) {
+                let s = basic_op::inv_mod(&root, p).unwrap();
+                return EcMont::new(3 * alpha * &s, s, p.clone());
+            }
+        }
+        Err("the curve has no Montgomery form: no point (alpha, 0) with 3 alpha^2 + a a square".to_string())
+    }
+
+    // the twisted Edwards form with a = (A + 2) / B and d = (A - 2) / B, non-singular
+    // because A != +-2; refused when it is not complete (see EcEdwards::new)
+    pub fn to_edwards(&self) -> Result<EcEdwards, String> {
+        let p = &self.p;
+        EcEdwards::new(div_mod(&self.a + 2, &self.b, p), div_mod(&self.a - 2, &self.b, p), p.clone())
+    }
+
+    // (u, v) -> (u / v, (u - 1) / (u + 1)), with ∞ -> (0, 1) and (0, 0) -> (0, -1). The other
+    // points with v = 0 or u = -1 go to points at infinity of the Edwards curve, which only
+    // exist when the Edwards addition law is not complete
+    pub fn point_to_edwards(&self, point: &Point) -> Result<Point, String> {
+        let p = &self.p;
+        let (u, v) = match point {
+            Point::Infinity => return Ok(Point::new(BigInt::zero(), BigInt::one())),
+            Point::Affine(u, v) if u.is_zero() && v.is_zero() => {
+                return Ok(Point::new(BigInt::zero(), p - 1));
+            }
+            Point::Affine(u, v) => (u, v),
+        };
+        if v.is_zero() || reduce(&(u + 1), p).is_zero() {
+            return Err("the point maps to a point at infinity of the Edwards curve".to_string());
+        }
+        Ok(Point::new(div_mod(u.clone(), v, p), div_mod(u - 1, &(u + 1), p)))
+    }
+}
+
+impl CurveModel for EcMont {
+    fn p(&self) -> &BigInt {
+        EcMont::p(self)
+    }
+
+    fn is_point(&self, point: &Point) -> bool {
+        EcMont::is_point(self, point)
+    }
+
+    fn identity(&self) -> Point {
+        Point::Infinity
+    }
+
+    fn point_add(&self, point_a: &Point, point_b: &Point) -> Point {
+        EcMont::point_add(self, point_a, point_b)
+    }
+}
+
+// X25519 (RFC 7748, section 5): the clamped scalar times the point with u-coordinate u,
+// both little-endian; the top bit of u is ignored and the point at infinity encodes as 0
+pub fn x25519(scalar: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+    let mut k = *scalar;
+    k[0] &= 248;
+    k[31] &= 127;
+    k[31] |= 64;
+    let mut u = *u;
+    u[31] &= 127;
+    let curve = EcMont::curve25519();
+    let u = BigInt::from_bytes_le(Sign::Plus, &u);
+    let k = BigInt::from_bytes_le(Sign::Plus, &k);
+    let (_, mut bytes) = curve.ladder_x(&u, &k).unwrap_or_default().to_bytes_le();
+    bytes.resize(32, 0);
+    bytes.try_into().unwrap()
+}
+
+// twisted Edwards curve a x^2 + y^2 = 1 + d x^2 y^2 over F_p, with a d (a - d) != 0. The
+// identity is the affine point (0, 1) and -(x, y) = (-x, y). Only complete curves are
+// accepted: a is a square and d is not, so one addition formula is valid for every pair
+// of points, doublings included
+#[derive(Debug, Clone)]
+pub struct EcEdwards {
+    a: BigInt,
+    d: BigInt,
+    p: BigInt,
+}
+
+impl EcEdwards {
+    // refuses singular curves, a d (a - d) = 0 mod p, and incomplete ones: those have
+    // points at infinity, which an affine Point cannot hold, so their affine points are
+    // not closed under addition. Such curves are handled in their Montgomery form
+    pub fn new(new_a: BigInt, new_d: BigInt, new_p: BigInt) -> Result<Self, String> {
+        let (a, d) = (reduce(&new_a, &new_p), reduce(&new_d, &new_p));
+        if reduce(&(&a * &d * (&a - &d)), &new_p).is_zero() {
+            return Err("the curve is singular: a d (a - d) = 0 mod p".to_string());
+        }
+        let curve = Self { a, d, p: new_p };
+        if !curve.is_complete() {
+            return Err("the curve is not complete: a must be a square and d a non-square mod p".to_string());
+        }
+        Ok(curve)
+    }
+
+    // Ed25519 (RFC 8032): a = -1, d = -121665 / 121666 over 2^255 - 19
+    pub fn ed25519() -> Self {
+        let p = (BigInt::one() << 255) - 19;
+        let d = div_mod(BigInt::from(-121665), &BigInt::from(121666), &p);
+        Self::new(BigInt::from(-1), d, p).unwrap()
+    }
+
+    // the base point of Ed25519: y = 4 / 5 and x even
+    pub fn ed25519_base_point() -> Point {
+        let curve = Self::ed25519();
+        let p = &curve.p;
+        let y = div_mod(BigInt::from(4), &BigInt::from(5), p);
+        let y2 = &y * &y % p;
+        // x^2 = (y^2 - 1) / (d y^2 - a)
+        let x2 = div_mod(&y2 - 1, &reduce(&(&curve.d * &y2 - &curve.a), p), p);
+        let x = basic_op::sqrt_mod(&x2, p).unwrap();
+        let x = if x.bit(0) { p - x } else { x };
+        Point::new(x, y)
+    }
+
+    pub fn a(&self) -> &BigInt {
+        &self.a
+    }
+
+    pub fn d(&self) -> &BigInt {
+        &self.d
+    }
+
+    pub fn p(&self) -> &BigInt {
+        &self.p
+    }
+
+    pub fn identity(&self) -> Point {
+        Point::new(BigInt::zero(), BigInt::one())
+    }
+
+    // a square and d not: the denominators 1 +- d x1 x2 y1 y2 never vanish on the curve.
+    // Always true for a curve built by new
+    pub fn is_complete(&self) -> bool {
+        basic_op::legendre(&self.a, &self.p) == 1 && basic_op::legendre(&self.d, &self.p) == -1
+    }
+
+    // Point::Infinity is not on an Edwards curve
+    pub fn is_point(&self, point: &Point) -> bool {
+        match point {
+            Point::Infinity => false,
+            Point::Affine(x, y) => {
+                let (x2, y2) = (x * x, y * y);
+                let lhs = reduce(&(&self.a * &x2 + &y2), &self.p);
+                let rhs = reduce(&(1 + &self.d * x2 * y2), &self.p);
+                lhs == rhs
+            }
+        }
+    }
+
+    // x3 = (x1 y2 + y1 x2) / (1 + d x1 x2 y1 y2), y3 = (y1 y2 - a x1 x2) / (1 - d x1 x2 y1 y2);
+    // the curve is complete, so neither denominator vanishes. Point::Infinity, which the
+    // other models use as their identity, is read as (0, 1)
+    pub fn point_add(&self, point_a: &Point, point_b: &Point) -> Point {
+        let (x1, y1, x2, y2) = match (point_a, point_b) {
+            (Point::Infinity, _) => return self.point_add(&self.identity(), point_b),
+            (_, Point::Infinity) => return self.point_add(point_a, &self.identity()),
+            (Point::Affine(x1, y1), Point::Affine(x2, y2)) => (x1, y1, x2, y2),
+        };
+        let p = &self.p;
+        let t = reduce(&(&self.d * x1 * x2 * y1 * y2), p);
+        let den_x = reduce(&(1 + &t), p);
+        let den_y = reduce(&(1 - &t), p);
+        let x3 = div_mod(x1 * y2 + y1 * x2, &den_x, p);
+        let y3 = div_mod(y1 * y2 - &self.a * x1 * x2, &den_y, p);
+        Point::new(x3, y3)
+    }
+
+    pub fn negate(&self, point: &Point) -> Point {
+        match point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => Point::new((&self.p - x) % &self.p, y.clone()),
+        }
+    }
+
+    // double-and-add with the unified formula; d <= 0 gives the identity
+    pub fn scalar_mul(&self, point: &Point, d: &BigInt) -> Point {
+        let mut acc = self.identity();
+        if d <= &BigInt::zero() {
+            return acc;
+        }
+        for i in (0..d.bits()).rev() {
+            acc = self.point_add(&acc, &acc);
+            if d.bit(i) {
+                acc = self.point_add(&acc, point);
+            }
+        }
+        acc
+    }
+
+    // the Montgomery form with A = 2 (a + d) / (a - d) and B = 4 / (a - d), non-singular
+    // because A^2 - 4 = 16 a d / (a - d)^2
+    pub fn to_montgomery(&self) -> EcMont {
+        let p = &self.p;
+        let diff = reduce(&(&self.a - &self.d), p);
+        EcMont::new(
+            div_mod(2 * (&self.a + &self.d), &diff, p),
+            div_mod(BigInt::from(4), &diff, p),
+            p.clone(),
+        )
+        .unwrap()
+    }
+
+    // (x, y) -> ((1 + y) / (1 - y), (1 + y) / ((1 - y) x)), with (0, 1) -> ∞ and
+    // (0, -1) -> (0, 0); the inverse of EcMont::point_to_edwards
+    pub fn point_to_montgomery(&self, point: &Point) -> Point {
+        let p = &self.p;
+        let (x, y) = match point {
+            Point::Infinity => return Point::Infinity,
+            Point::Affine(x, y) => (x, y),
+        };
+        if x.is_zero() {
+            return if y.is_one() { Point::Infinity } else { Point::new(BigInt::zero(), BigInt::zero()) };
+        }
+        let u = div_mod(1 + y, &reduce(&(1 - y), p), p);
+        let v = div_mod(u.clone(), x, p);
+        Point::new(u, v)
+    }
+
+    // through the Montgomery form
+    pub fn to_weierstrass(&self) -> EcWei {
+        self.to_montgomery().to_weierstrass()
+    }
+
+    pub fn point_to_weierstrass(&self, point: &Point) -> Point {
+        let mont = self.to_montgomery();
+        mont.point_to_weierstrass(&self.point_to_montgomery(point))
+    }
+}
+
+impl CurveModel for EcEdwards {
+    fn p(&self) -> &BigInt {
+        EcEdwards::p(self)
+    }
+
+    fn is_point(&self, point: &Point) -> bool {
+        EcEdwards::is_point(self, point)
+    }
+
+    fn identity(&self) -> Point {
+        EcEdwards::identity(self)
+    }
+
+    fn point_add(&self, point_a: &Point, point_b: &Point) -> Point {
+        EcEdwards::point_add(self, point_a, point_b)
+    }
+}
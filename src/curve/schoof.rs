@@ -0,0 +1,592 @@
+// Schoof's point counting: #E = p + 1 - t, where the trace t is recovered modulo small primes
+// l from the action of Frobenius (x, y) -> (x^p, y^p) on the l-torsion points, then by the
+// CRT once the product of the primes exceeds the width 4 sqrt(p) of the Hasse interval
+use crate::arithmetic::basic_op;
+use crate::arithmetic::poly_op::{self, Poly};
+use crate::curve::ecc::EcWei;
+use num_bigint::BigInt;
+use num_traits::{One, ToPrimitive, Zero};
+use std::collections::HashMap;
+
+// a point (X(x), Y(x) * y) with coordinates in F_p[x] / (h), or the point at infinity
+#[derive(Clone, PartialEq)]
+enum TorsionPoint {
+    Infinity,
+    Affine(Poly, Poly),
+}
+
+// curve arithmetic modulo a factor h of the division polynomial; when a denominator is
+// not invertible, the proper factor gcd(denominator, h) is returned as the error
+struct Ring<'a> {
+    p: &'a BigInt,
+    a: Poly,
+    h: Poly,
+    // x^3 + ax + b, which stands for y^2
+    f: Poly,
+}
+
+impl Ring<'_> {
+    fn add(&self, pt_a: &TorsionPoint, pt_b: &TorsionPoint) -> Result<TorsionPoint, Poly> {
+        let p = self.p;
+        let (x1, y1, x2, y2) = match (pt_a, pt_b) {
+            (TorsionPoint::Infinity, _) => return Ok(pt_b.clone()),
+            (_, TorsionPoint::Infinity) => return Ok(pt_a.clone()),
+            (TorsionPoint::Affine(x1, y1), TorsionPoint::Affine(x2, y2)) => (x1, y1, x2, y2),
+        };
+        if x1 == x2 {
+            if y1 == y2 {
+                return self.double(pt_a);
+            }
+            if poly_op::add(y1, y2, p).is_empty() {
+                return Ok(TorsionPoint::Infinity);
+            }
+            // the points agree on some roots of h and not on others
+            return Err(poly_op::gcd(&poly_op::sub(y1, y2, p), &self.h, p));
+        }
+        // lambda = y * L with L = (Y1 - Y2) / (X1 - X2)
+        let inv = poly_op::inv_mod(&poly_op::sub(x1, x2, p), &self.h, p)?;
+        let l = poly_op::mul_mod(&poly_op::sub(y1, y2, p), &inv, &self.h, p);
+        Ok(self.finish(&l, x1, y1, x2))
+    }
+
+    fn double(&self, pt: &TorsionPoint) -> Result<TorsionPoint, Poly> {
+        let p = self.p;
+        let (x, y) = match pt {
+            TorsionPoint::Affine(x, y) if !y.is_empty() => (x, y),
+            _ => return Ok(TorsionPoint::Infinity),
+        };
+        // lambda = (3X^2 + a) / (2Y y) = y * (3X^2 + a) / (2Y f)
+        let numerator = poly_op::add(
+            &poly_op::scale(&poly_op::mul(x, x, p), &BigInt::from(3), p),
+            &self.a,
+            p,
+        );
+        let denominator = poly_op::mul_mod(&poly_op::scale(y, &BigInt::from(2), p), &self.f, &self.h, p);
+        let inv = poly_op::inv_mod(&denominator, &self.h, p)?;
+        let l = poly_op::mul_mod(&numerator, &inv, &self.h, p);
+        Ok(self.finish(&l, x, y, x))
+    }
+
+    // X3 = f L^2 - X1 - X2, Y3 = L (X1 - X3) - Y1
+    fn finish(&self, l: &Poly, x1: &Poly, y1: &Poly, x2: &Poly) -> TorsionPoint {
+        let p = self.p;
+        let l2 = poly_op::mul_mod(l, l, &self.h, p);
+        let x3 = poly_op::sub(&poly_op::sub(&poly_op::mul_mod(&self.f, &l2, &self.h, p), x1, p), x2, p);
+        let y3 = poly_op::sub(&poly_op::mul_mod(l, &poly_op::sub(x1, &x3, p), &self.h, p), y1, p);
+        TorsionPoint::Affine(x3, y3)
+    }
+
+    fn mul_small(&self, pt: &TorsionPoint, k: u64) -> Result<TorsionPoint, Poly> {
+        let mut result = TorsionPoint::Infinity;
+        for i in (0..u64::BITS - k.leading_zeros()).rev() {
+            result = self.double(&result)?;
+            if (k >> i) & 1 == 1 {
+                result = self.add(&result, pt)?;
+            }
+        }
+        Ok(result)
+    }
+
+    // t mod l from phi^2(P) + (p mod l) * P = t * phi(P) for the generic point P = (x, y)
+    fn trace(&self, l: u64) -> Result<u64, Poly> {
+        let (p, h) = (self.p, &self.h);
+        let x = poly_op::rem(&vec![BigInt::zero(), BigInt::one()], h, p);
+        // y^p = y * f^((p - 1) / 2) and y^(p^2) = y * f^((p^2 - 1) / 2)
+        let y_p = poly_op::pow_mod(&self.f, &((p - 1) / 2), h, p);
+        let x_p = poly_op::pow_mod(&x, p, h, p);
+        let x_p2 = poly_op::pow_mod(&x_p, p, h, p);
+        let y_p2 = poly_op::mul_mod(&poly_op::pow_mod(&y_p, p, h, p), &y_p, h, p);
+        let frobenius = TorsionPoint::Affine(x_p, y_p);
+        let frobenius_2 = TorsionPoint::Affine(x_p2, y_p2);
+
+        let q_bar = (p % l).to_u64().unwrap();
+        let generic = TorsionPoint::Affine(x, vec![BigInt::one()]);
+        let lhs = self.add(&frobenius_2, &self.mul_small(&generic, q_bar)?)?;
+        if lhs == TorsionPoint::Infinity {
+            return Ok(0);
+        }
+        let mut rhs = frobenius.clone();
+        for t in 1..l {
+            if rhs == lhs {
+                return Ok(t);
+            }
+            rhs = self.add(&rhs, &frobenius)?;
+        }
+        unreachable!("Frobenius satisfies its characteristic equation for some t mod {}", l)
+    }
+}
+
+// f_n with psi_n = f_n for odd n and psi_n = 2y f_n for even n, so that only
+// polynomials in x appear
+fn division_poly(n: u64, curve: &EcWei, memo: &mut HashMap<u64, Poly>) -> Poly {
+    if let Some(f_n) = memo.get(&n) {
+        return f_n.clone();
+    }
+    let (a, b, p) = (curve.a(), curve.b(), curve.p());
+    let c = |coefficients: &[BigInt]| poly_op::from_coefficients(coefficients, p);
+    let f_n = match n {
+        0 => Poly::new(),
+        1 | 2 => vec![BigInt::one()],
+        3 => c(&[-(a * a), 12 * b, 6 * a, BigInt::zero(), BigInt::from(3)]),
+        4 => c(&[
+            -16 * b * b - 2 * a * a * a,
+            -8 * a * b,
+            -10 * a * a,
+            40 * b,
+            10 * a,
+            BigInt::zero(),
+            BigInt::from(2),
+        ]),
+        _ => {
+            let m = n / 2;
+            let mut f = |k: u64| division_poly(k, curve, memo);
+            let mul = |x: &Poly, y: &Poly| poly_op::mul(x, y, p);
+            let cube = |x: &Poly| mul(&mul(x, x), x);
+            if n % 2 == 1 {
+                // psi_(2m+1) = psi_(m+2) psi_m^3 - psi_(m-1) psi_(m+1)^3, with (2y)^4 = 16 F^2
+                let curve_poly = c(&[b.clone(), a.clone(), BigInt::zero(), BigInt::one()]);
+                let y4 = poly_op::scale(&mul(&curve_poly, &curve_poly), &BigInt::from(16), p);
+                let (f_m2, f_m, f_m1, f_m_1) = (f(m + 2), f(m), f(m + 1), f(m - 1));
+                let (mut left, mut right) = (mul(&f_m2, &cube(&f_m)), mul(&f_m_1, &cube(&f_m1)));
+                if m.is_multiple_of(2) {
+                    left = mul(&y4, &left);
+                } else {
+                    right = mul(&y4, &right);
+                }
+                poly_op::sub(&left, &right, p)
+            } else {
+                // psi_2m = psi_m (psi_(m+2) psi_(m-1)^2 - psi_(m-2) psi_(m+1)^2) / 2y
+                let (f_m, f_m2, f_m_1, f_m_2, f_m1) = (f(m), f(m + 2), f(m - 1), f(m - 2), f(m + 1));
+                let inner = poly_op::sub(
+                    &mul(&f_m2, &mul(&f_m_1, &f_m_1)),
+                    &mul(&f_m_2, &mul(&f_m1, &f_m1)),
+                    p,
+                );
+                mul(&f_m, &inner)
+            }
+        }
+    };
+    memo.insert(n, f_n.clone());
+    f_n
+}
+
+fn curve_poly(curve: &EcWei) -> Poly {
+    let (a, b, p) = (curve.a(), curve.b(), curve.p());
+    poly_op::from_coefficients(&[b.clone(), a.clone(), BigInt::zero(), BigInt::one()], p)
+}
+
+// t mod 2: t is even exactly when there is a point of order 2, i.e. x^3 + ax + b has a root
+fn trace_mod_2(curve: &EcWei) -> u64 {
+    let p = curve.p();
+    let curve_poly = curve_poly(curve);
+    let x = vec![BigInt::zero(), BigInt::one()];
+    let x_p = poly_op::pow_mod(&x, p, &curve_poly, p);
+    let has_root = poly_op::degree(&poly_op::gcd(&poly_op::sub(&x_p, &x, p), &curve_poly, p)) > 0;
+    if has_root {
+        0
+    } else {
+        1
+    }
+}
+
+// t mod l by Schoof's method, modulo the l-th division polynomial
+fn schoof_trace(curve: &EcWei, l: u64, memo: &mut HashMap<u64, Poly>) -> u64 {
+    let p = curve.p();
+    let curve_poly = curve_poly(curve);
+    let mut ring = Ring {
+        p,
+        a: poly_op::from_coefficients(std::slice::from_ref(curve.a()), p),
+        h: poly_op::monic(&division_poly(l, curve, memo), p),
+        f: Poly::new(),
+    };
+    loop {
+        ring.f = poly_op::rem(&curve_poly, &ring.h, p);
+        match ring.trace(l) {
+            Ok(t_l) => return t_l,
+            Err(factor) => ring.h = factor,
+        }
+    }
+}
+
+// CRT: trace + modulus * k = t_l (mod l)
+fn crt_step(trace: &mut BigInt, modulus: &mut BigInt, t_l: u64, l: u64) {
+    let l_big = BigInt::from(l);
+    let inv = basic_op::inv_mod(&(&*modulus % &l_big), &l_big).unwrap();
+    let k = basic_op::reduce(&(BigInt::from(t_l) - &*trace), &l_big) * inv % &l_big;
+    *trace += &*modulus * k;
+    *modulus *= l_big;
+}
+
+pub fn count_points(curve: &EcWei) -> BigInt {
+    let p = curve.p();
+    let mut trace = BigInt::from(trace_mod_2(curve));
+    let mut modulus = BigInt::from(2);
+
+    let mut memo = HashMap::new();
+    let bound = BigInt::from(16) * p; // modulus^2 > 16 p means modulus > 4 sqrt(p)
+    let mut l = 3u64;
+    while &modulus * &modulus <= bound {
+        if basic_op::is_probable_prime(&BigInt::from(l)) && &BigInt::from(l) != p {
+            let t_l = schoof_trace(curve, l, &mut memo);
+            crt_step(&mut trace, &mut modulus, t_l, l);
+        }
+        l += 2;
+    }
+    // |t| <= 2 sqrt(p) < modulus / 2
+    if BigInt::from(2) * &trace > modulus {
+        trace -= &modulus;
+    }
+    p + 1 - trace
+}
+
+// ----- Schoof-Elkies-Atkin -----
+
+// Atkin primes (and Elkies primes whose formulas break down) up to this bound still go
+// through Schoof; larger ones are skipped
+const SCHOOF_MAX_PRIME: u64 = 13;
+// primes are collected until at most 2^SEA_BSGS_BITS values of t are left
+const SEA_BSGS_BITS: u64 = 32;
+
+// Schoof-Elkies-Atkin. For an Elkies prime l, Φ_l(j, Y) has a root in F_p, the Frobenius
+// fixes a subgroup of order l, and t mod l comes from its kernel polynomial, a factor of
+// degree (l - 1) / 2 of the division polynomial, instead of the whole (l^2 - 1) / 2 of
+// Schoof's method. Primes are collected until the values of t left in the Hasse interval
+// are few enough for baby-step giant-step, which picks #E among them. The modular
+// polynomials are computed on the fly, which is what bounds the usable l
+pub fn count_points_sea(curve: &EcWei) -> BigInt {
+    let p = curve.p();
+    let j = curve.j_invariant();
+    // j = 0 and j = 1728 make the normalisation of the isogenous curve divide by zero
+    let elkies_possible = !j.is_zero() && j != BigInt::from(1728) % p;
+    let mut trace = BigInt::from(trace_mod_2(curve));
+    let mut modulus = BigInt::from(2);
+
+    let mut memo = HashMap::new();
+    // number of values of t in [-2 sqrt(p), 2 sqrt(p)]
+    let width: BigInt = (BigInt::from(16) * p).sqrt() + 1;
+    let mut l = 3u64;
+    while &width / &modulus > BigInt::one() << SEA_BSGS_BITS {
+        if basic_op::is_probable_prime(&BigInt::from(l)) && &BigInt::from(l) != p {
+            let elkies = if elkies_possible { elkies_trace(curve, l, &j) } else { None };
+            let t_l = match elkies {
+                Some(t_l) => Some(t_l),
+                None if l <= SCHOOF_MAX_PRIME => Some(schoof_trace(curve, l, &mut memo)),
+                None => None,
+            };
+            if let Some(t_l) = t_l {
+                crt_step(&mut trace, &mut modulus, t_l, l);
+            }
+        }
+        l += 2;
+    }
+    curve.order_from_trace(&trace, &modulus)
+}
+
+// a * b truncated to `len` terms, for Laurent series stored from q^-offset up
+fn series_mul(a: &[BigInt], b: &[BigInt], offset: usize, len: usize, p: &BigInt) -> Vec<BigInt> {
+    let mut out = vec![BigInt::zero(); len];
+    for (i, a_i) in a.iter().enumerate().filter(|(_, a_i)| !a_i.is_zero()) {
+        // index i + k - offset of the product must lie in [0, len)
+        let first = offset.saturating_sub(i);
+        let last = (len + offset - i).min(b.len());
+        for (k, b_k) in b.iter().enumerate().take(last).skip(first) {
+            out[i + k - offset] += a_i * b_k;
+        }
+    }
+    out.into_iter().map(|c| c % p).collect()
+}
+
+// the coefficients of q^-1, q^0, ..., q^max in j(q) = E4(q)^3 / Δ(q) modulo p, with
+// E4 = 1 + 240 sum sigma_3(n) q^n and Δ = q prod (1 - q^n)^24
+fn j_series(max: usize, p: &BigInt) -> Vec<BigInt> {
+    let len = max + 2;
+    let mut sigma_3 = vec![0u128; len];
+    for d in 1..len {
+        for m in (d..len).step_by(d) {
+            sigma_3[m] += (d as u128).pow(3);
+        }
+    }
+    let e4: Vec<BigInt> = (0..len)
+        .map(|n| if n == 0 { BigInt::one() } else { BigInt::from(240 * sigma_3[n]) % p })
+        .collect();
+    // prod (1 - q^n) = sum (-1)^k q^(k (3k - 1) / 2) over all integers k (Euler)
+    let mut eta = vec![BigInt::zero(); len];
+    eta[0] = BigInt::one();
+    for k in 1.. {
+        let pentagonal = k * (3 * k - 1) / 2;
+        if pentagonal >= len {
+            break;
+        }
+        let sign = if k.is_multiple_of(2) { BigInt::one() } else { p - 1 };
+        eta[pentagonal] = sign.clone();
+        if pentagonal + k < len {
+            eta[pentagonal + k] = sign;
+        }
+    }
+    let eta_2 = series_mul(&eta, &eta, 0, len, p);
+    let eta_4 = series_mul(&eta_2, &eta_2, 0, len, p);
+    let eta_8 = series_mul(&eta_4, &eta_4, 0, len, p);
+    let eta_16 = series_mul(&eta_8, &eta_8, 0, len, p);
+    let eta_24 = series_mul(&eta_16, &eta_8, 0, len, p);
+    // 1 / prod (1 - q^n)^24, whose constant term is 1
+    let mut inverse = vec![BigInt::zero(); len];
+    inverse[0] = BigInt::one();
+    for n in 1..len {
+        let sum = (1..=n).fold(BigInt::zero(), |acc, i| acc + &eta_24[i] * &inverse[n - i]);
+        inverse[n] = (p - sum % p) % p;
+    }
+    let e4_cubed = series_mul(&series_mul(&e4, &e4, 0, len, p), &e4, 0, len, p);
+    series_mul(&e4_cubed, &inverse, 0, len, p)
+}
+
+// the classical modular polynomial Φ_l(X, Y) modulo p, as the coefficients of X^0, ...,
+// X^(l+1), each a polynomial in Y. The roots of Φ_l(X, j(q)) are j(q^l) and j(ζ^k q^(1/l))
+// for k < l; their power sums are q-series, Newton's identities turn them into the
+// elementary symmetric functions, and each of those is a polynomial of degree at most
+// l + 1 in j(q), read off its principal part
+fn modular_polynomial(l: usize, p: &BigInt) -> Vec<Poly> {
+    // series run from q^-offset to q^top: the powers of the symmetric functions up to
+    // q^(il) meet the powers of j(q^(1/l)) up to q^(l+1) in the products below
+    let offset = l + 1;
+    let top = l * (l + 1);
+    let len = offset + top + 1;
+    let at = |exponent: i64| (exponent + offset as i64) as usize;
+
+    let mut j = vec![BigInt::zero(); len];
+    for (n, c) in j_series(top, p).into_iter().enumerate() {
+        j[offset - 1 + n] = c;
+    }
+    let mut powers = vec![vec![BigInt::zero(); len]];
+    powers[0][offset] = BigInt::one();
+    for d in 1..=l + 1 {
+        let next = series_mul(&powers[d - 1], &j, offset, len, p);
+        powers.push(next);
+    }
+
+    let l_big = BigInt::from(l);
+    let (l_i, l1) = (l as i64, (l + 1) as i64);
+    // e[k] is the k-th elementary symmetric function as a q-series, coefficients[k] the
+    // same as a polynomial in j
+    let mut e = vec![powers[0].clone()];
+    let mut coefficients = vec![vec![BigInt::one()]];
+    for k in 1..=l + 1 {
+        // k e_k = sum (-1)^(i-1) e_(k-i) S_i, only for the exponents -(l+1)..0
+        let mut principal = vec![BigInt::zero(); l + 2];
+        for i in 1..=k {
+            let (e_prev, power) = (&e[k - i], &powers[i]);
+            let coefficient = |exponent: i64| {
+                if exponent < -l1 {
+                    BigInt::zero()
+                } else {
+                    e_prev[at(exponent)].clone()
+                }
+            };
+            for m in -l1..=0 {
+                // S_i = sum a_n q^(nl) + l sum a_(sl) q^s, where a_n is the coefficient
+                // of q^n in j(q)^i
+                let mut sum = BigInt::zero();
+                for n in -(i as i64)..=1 {
+                    sum += &power[at(n)] * coefficient(m - n * l_i);
+                }
+                let mut fractional = BigInt::zero();
+                for s in -1..=l1 {
+                    if s * l_i >= -(i as i64) {
+                        fractional += &power[at(s * l_i)] * coefficient(m - s);
+                    }
+                }
+                sum += &l_big * fractional;
+                let slot = &mut principal[(m + l1) as usize];
+                if i % 2 == 1 {
+                    *slot += sum;
+                } else {
+                    *slot -= sum;
+                }
+            }
+        }
+        let inv_k = basic_op::inv_mod(&BigInt::from(k), p).unwrap();
+        let mut principal: Vec<BigInt> = principal.into_iter().map(|c| basic_op::reduce(&c, p) * &inv_k % p).collect();
+
+        // peel off c_d j^d from the highest pole down
+        let mut in_j = vec![BigInt::zero(); l + 2];
+        for d in (1..=l + 1).rev() {
+            let c = principal[l + 1 - d].clone();
+            if !c.is_zero() {
+                for m in -(d as i64)..=0 {
+                    let slot = &mut principal[(m + l1) as usize];
+                    *slot = basic_op::reduce(&(&*slot - &c * &powers[d][at(m)]), p);
+                }
+            }
+            in_j[d] = c;
+        }
+        in_j[0] = principal[l + 1].clone();
+
+        let mut series = vec![BigInt::zero(); len];
+        for (d, c) in in_j.iter().enumerate().filter(|(_, c)| !c.is_zero()) {
+            for (slot, x) in series.iter_mut().zip(powers[d].iter()) {
+                *slot += c * x;
+            }
+        }
+        e.push(series.into_iter().map(|c| c % p).collect());
+        coefficients.push(poly_op::trim(in_j));
+    }
+    // Φ_l(X, Y) = sum (-1)^k e_k(Y) X^(l+1-k)
+    (0..=l + 1)
+        .map(|i| {
+            let k = l + 1 - i;
+            if k.is_multiple_of(2) {
+                coefficients[k].clone()
+            } else {
+                poly_op::scale(&coefficients[k], &BigInt::from(-1), p)
+            }
+        })
+        .collect()
+}
+
+// c_1, ..., c_count in ℘(z) = z^-2 + sum c_k z^(2k) for y^2 = x^3 + ax + b (x = ℘,
+// y = ℘' / 2), at index k
+fn weierstrass_coefficients(a: &BigInt, b: &BigInt, count: usize, p: &BigInt) -> Option<Vec<BigInt>> {
+    let mut c = vec![BigInt::zero(); count + 1];
+    let reduce = |x: BigInt| basic_op::reduce(&x, p);
+    if count >= 1 {
+        c[1] = reduce(-a * basic_op::inv_mod(&BigInt::from(5), p)?);
+    }
+    if count >= 2 {
+        c[2] = reduce(-b * basic_op::inv_mod(&BigInt::from(7), p)?);
+    }
+    for k in 3..=count {
+        let sum = (1..=k - 2).fold(BigInt::zero(), |acc, h| acc + &c[h] * &c[k - 1 - h]);
+        let denominator = BigInt::from((k - 2) * (2 * k + 3)) % p;
+        if denominator.is_zero() {
+            return None;
+        }
+        c[k] = reduce(sum * 3 * basic_op::inv_mod(&denominator, p)?);
+    }
+    Some(c)
+}
+
+// the kernel polynomial of the normalised l-isogeny E -> E~ with j(E~) = j_tilde. Elkies:
+// the derivatives of Φ_l at (j, j~) give the Eisenstein series of E~ and so a model
+// y^2 = x^3 + a~x + b~ with ℘~(z) = I(℘(z)); I(x) = x + sum e_k x^-k is then read off
+// the two ℘-expansions, and its denominator D(x)^2 comes out of Berlekamp-Massey
+fn kernel_polynomial(curve: &EcWei, l: u64, phi: &[Poly], j: &BigInt, j_tilde: &BigInt) -> Option<Poly> {
+    let (a, b, p) = (curve.a(), curve.b(), curve.p());
+    let reduce = |x: BigInt| basic_op::reduce(&x, p);
+    let inv = |x: BigInt| {
+        let x = reduce(x);
+        if x.is_zero() {
+            None
+        } else {
+            basic_op::inv_mod(&x, p)
+        }
+    };
+    let l_big = BigInt::from(l);
+
+    // partial derivatives of Φ_l(X, Y) at X = j, Y = j~
+    let (mut phi_x, mut phi_y) = (BigInt::zero(), BigInt::zero());
+    let mut j_power = BigInt::one();
+    for (i, coefficient) in phi.iter().enumerate() {
+        phi_y += poly_op::eval(&poly_op::derivative(coefficient, p), j_tilde, p) * &j_power;
+        if i + 1 < phi.len() {
+            phi_x += poly_op::eval(&phi[i + 1], j_tilde, p) * BigInt::from(i + 1) * &j_power;
+        }
+        j_power = (j_power * j) % p;
+    }
+
+    // a = -3 E4 and b = -2 E6, j' = -j E6 / E4, and the same for E~ with the lattice
+    // scaled by l: a~ = -3 l^4 E4~ and b~ = -2 l^6 E6~
+    let e4 = reduce(-a * inv(BigInt::from(3))?);
+    let e6 = reduce(-b * inv(BigInt::from(2))?);
+    let j_prime = reduce(-j * &e6 * inv(e4)?);
+    let j_tilde_prime = reduce(-&j_prime * phi_x * inv(&l_big * phi_y)?);
+    let e4_tilde = reduce(&j_tilde_prime * &j_tilde_prime * inv(j_tilde * (j_tilde - 1728))?);
+    let e6_tilde = reduce(-&j_tilde_prime * &e4_tilde * inv(j_tilde.clone())?);
+    let a_tilde = reduce(BigInt::from(-3) * l_big.pow(4) * e4_tilde);
+    let b_tilde = reduce(BigInt::from(-2) * l_big.pow(6) * e6_tilde);
+
+    // 2 (l - 1) terms of I(x) - x, whose denominator has degree l - 1
+    let count = 2 * (l as usize - 1);
+    let c = weierstrass_coefficients(a, b, count, p)?;
+    let c_tilde = weierstrass_coefficients(&a_tilde, &b_tilde, count, p)?;
+    // in Z = z^2, Z ℘ = 1 + sum c_k Z^(k+1), so w = 1 / ℘ = Z / (Z ℘)
+    let mut z_wp = vec![BigInt::zero(); count + 1];
+    z_wp[0] = BigInt::one();
+    z_wp[2..].clone_from_slice(&c[1..count]);
+    let mut w = vec![BigInt::zero(); count + 1];
+    let mut inverse = vec![BigInt::one()];
+    for n in 1..count {
+        let sum = (1..=n).fold(BigInt::zero(), |acc, i| acc + &z_wp[i] * &inverse[n - i]);
+        inverse.push(reduce(-sum));
+    }
+    w[1..].clone_from_slice(&inverse);
+    // ℘~ - ℘ = sum e_k w^k, peeled off one power of Z at a time
+    let mut rest: Vec<BigInt> = (0..=count).map(|k| reduce(&c_tilde[k] - &c[k])).collect();
+    let mut w_power = w.clone();
+    let mut e = Vec::with_capacity(count);
+    for k in 1..=count {
+        let e_k = rest[k].clone();
+        for (slot, x) in rest.iter_mut().zip(w_power.iter()) {
+            *slot = reduce(&*slot - &e_k * x);
+        }
+        e.push(e_k);
+        w_power = series_mul(&w_power, &w, 0, count + 1, p);
+    }
+
+    let square = poly_op::min_poly(&e, p);
+    if poly_op::degree(&square) != l as usize - 1 {
+        return None;
+    }
+    let kernel = poly_op::gcd(&square, &poly_op::derivative(&square, p), p);
+    if 2 * poly_op::degree(&kernel) == l as usize - 1 {
+        Some(kernel)
+    } else {
+        None
+    }
+}
+
+// t mod l for an Elkies prime l: the Frobenius acts on the kernel as a scalar λ, the
+// first k with k P = (x^p, y^p) for P = (x, y) modulo the kernel polynomial, and then
+// t = λ + p / λ (mod l). None when l is an Atkin prime or the computation degenerates
+fn elkies_trace(curve: &EcWei, l: u64, j: &BigInt) -> Option<u64> {
+    let p = curve.p();
+    let phi = modular_polynomial(l as usize, p);
+    // Φ_l(j, Y)
+    let mut at_j = Poly::new();
+    let mut j_power = BigInt::one();
+    for coefficient in &phi {
+        at_j = poly_op::add(&at_j, &poly_op::scale(coefficient, &j_power, p), p);
+        j_power = (j_power * j) % p;
+    }
+    let kernel = poly_op::roots(&at_j, p)
+        .iter()
+        .find_map(|j_tilde| kernel_polynomial(curve, l, &phi, j, j_tilde))?;
+
+    let ring = Ring {
+        p,
+        a: poly_op::from_coefficients(std::slice::from_ref(curve.a()), p),
+        f: poly_op::rem(&curve_poly(curve), &kernel, p),
+        h: kernel,
+    };
+    let x = poly_op::rem(&vec![BigInt::zero(), BigInt::one()], &ring.h, p);
+    let x_p = poly_op::pow_mod(&x, p, &ring.h, p);
+    let y_p = poly_op::pow_mod(&ring.f, &((p - 1) / 2), &ring.h, p);
+    let generic = TorsionPoint::Affine(x, vec![BigInt::one()]);
+    let mut multiple = generic.clone();
+    let mut eigenvalue = None;
+    for k in 1..=(l - 1) / 2 {
+        if let TorsionPoint::Affine(x_k, y_k) = &multiple {
+            if x_k == &x_p {
+                if y_k == &y_p {
+                    eigenvalue = Some(k);
+                } else if poly_op::add(y_k, &y_p, p).is_empty() {
+                    eigenvalue = Some(l - k);
+                }
+                break;
+            }
+        }
+        multiple = ring.add(&multiple, &generic).ok()?;
+    }
+    let lambda = BigInt::from(eigenvalue?);
+    let l_big = BigInt::from(l);
+    let t_l = (&lambda + p * basic_op::inv_mod(&lambda, &l_big)?) % &l_big;
+    t_l.to_u64()
+}
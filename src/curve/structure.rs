@@ -0,0 +1,286 @@
+// the structure of E(F_p) as an abstract group: the invariant factors Z_n1 × Z_n2 with a
+// basis, found one Sylow subgroup at a time, and from it every subgroup and torsion group
+use crate::arithmetic::basic_op;
+use crate::curve::ecc::{EcWei, Point};
+use num_bigint::BigInt;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+// E(F_p) ≅ Z_n1 × Z_n2 with n2 | n1 (and n2 | p - 1): every point is a P1 + b P2 for
+// exactly one a mod n1 and b mod n2. The group is cyclic when n2 = 1, and then P2 = ∞
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupStructure {
+    pub n1: BigInt,
+    pub n2: BigInt,
+    pub point_p1: Point,
+    pub point_p2: Point,
+}
+
+impl GroupStructure {
+    pub fn is_cyclic(&self) -> bool {
+        self.n2.is_one()
+    }
+
+    pub fn order(&self) -> BigInt {
+        &self.n1 * &self.n2
+    }
+
+    // a P1 + b P2
+    pub fn point(&self, new_ec: &EcWei, a: &BigInt, b: &BigInt) -> Point {
+        let a = basic_op::reduce(a, &self.n1);
+        let b = basic_op::reduce(b, &self.n2);
+        new_ec.point_add(&new_ec.scalar_mul(&self.point_p1, &a), &new_ec.scalar_mul(&self.point_p2, &b))
+    }
+
+    // E[n](F_p) ≅ Z_gcd(n, n1) × Z_gcd(n, n2)
+    pub fn torsion_structure(&self, n: &BigInt) -> (BigInt, BigInt) {
+        (basic_op::gcd(n, &self.n1), basic_op::gcd(n, &self.n2))
+    }
+
+    // how many elements have order d, for each d | n1: of the d gcd(d, n2) elements
+    // killed by d, those not killed by any d / q (inclusion-exclusion over the primes q | d)
+    pub fn order_distribution(&self) -> BTreeMap<BigInt, BigInt> {
+        let primes: Vec<BigInt> = basic_op::factorize(&self.n1).into_iter().map(|(q, _)| q).collect();
+        let killed_by = |m: &BigInt| m * basic_op::gcd(m, &self.n2);
+        basic_op::divisors(&self.n1)
+            .into_iter()
+            .map(|d| {
+                let dividing: Vec<&BigInt> = primes.iter().filter(|q| (&d % *q).is_zero()).collect();
+                let mut count = BigInt::zero();
+                for subset in 0..1u32 << dividing.len() {
+                    let mut m = d.clone();
+                    for (i, q) in dividing.iter().enumerate() {
+                        if subset >> i & 1 == 1 {
+                            m /= *q;
+                        }
+                    }
+                    if subset.count_ones() % 2 == 0 {
+                        count += killed_by(&m);
+                    } else {
+                        count -= killed_by(&m);
+                    }
+                }
+                (d, count)
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for GroupStructure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_cyclic() {
+            write!(f, "Z_{}", self.n1)
+        } else {
+            write!(f, "Z_{} × Z_{}", self.n1, self.n2)
+        }
+    }
+}
+
+// a subgroup H ≅ Z_h1 × Z_h2 (h2 | h1) of E(F_p) with a basis: a single generator when H
+// is cyclic (∞ for the trivial subgroup), otherwise generators of orders h1 and h2 whose
+// multiples only meet in ∞
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subgroup {
+    pub order: BigInt,
+    pub structure: (BigInt, BigInt),
+    pub generators: Vec<Point>,
+}
+
+// random points drawn per attempt when looking for a basis of a Sylow subgroup
+const SYLOW_SAMPLES: usize = 40;
+
+// the Smith normal form diag(s1, s2), s1 | s2, of a non-singular integer matrix m, and
+// the matrix whose rows generate Z^2 / (rows of m) with orders s1 and s2: column
+// operations change the basis of Z^2, row operations only the basis of the relations
+fn smith_normal_form(mut m: [[BigInt; 2]; 2]) -> ([BigInt; 2], [[BigInt; 2]; 2]) {
+    // w is the inverse of the accumulated column operations
+    let mut w = [[BigInt::one(), BigInt::zero()], [BigInt::zero(), BigInt::one()]];
+    loop {
+        if !m[0][1].is_zero() {
+            // (m00, m01) C = (g, 0) with C = [[s, -m01 / g], [t, m00 / g]]
+            let (g, s, t) = basic_op::ext_gcd(&m[0][0], &m[0][1]);
+            let (x, y) = (&m[0][0] / &g, &m[0][1] / &g);
+            for row in m.iter_mut() {
+                let (c0, c1) = (&row[0] * &s + &row[1] * &t, &row[1] * &x - &row[0] * &y);
+                *row = [c0, c1];
+            }
+            // w <- C^-1 w with C^-1 = [[x, y], [-t, s]]
+            let (r0, r1) = (w[0].clone(), w[1].clone());
+            w[0] = [&x * &r0[0] + &y * &r1[0], &x * &r0[1] + &y * &r1[1]];
+            w[1] = [&s * &r1[0] - &t * &r0[0], &s * &r1[1] - &t * &r0[1]];
+        }
+        if !m[1][0].is_zero() {
+            // the same on the first column with a row operation
+            let (g, s, t) = basic_op::ext_gcd(&m[0][0], &m[1][0]);
+            let (x, y) = (&m[0][0] / &g, &m[1][0] / &g);
+            let (r0, r1) = (m[0].clone(), m[1].clone());
+            m[0] = [&s * &r0[0] + &t * &r1[0], &s * &r0[1] + &t * &r1[1]];
+            m[1] = [&x * &r1[0] - &y * &r0[0], &x * &r1[1] - &y * &r0[1]];
+            continue;
+        }
+        if !(&m[1][1] % &m[0][0]).is_zero() {
+            // s1 must divide s2: bring s2 into the first row and start over
+            m[0][1] = m[1][1].clone();
+            continue;
+        }
+        return ([m[0][0].abs(), m[1][1].abs()], w);
+    }
+}
+
+impl EcWei {
+    // E(F_p) ≅ Z_n1 × Z_n2, assembled from bases of the Sylow subgroups
+    pub fn group_structure(&self) -> GroupStructure {
+        let order = self.order();
+        let (mut n1, mut n2) = (BigInt::one(), BigInt::one());
+        let (mut point_p1, mut point_p2) = (Point::Infinity, Point::Infinity);
+        for (q, e) in basic_op::factorize(&order) {
+            let (sylow_1, a, sylow_2, b) = self.sylow_basis(&q, e, &(&order / q.pow(e)));
+            n1 *= q.pow(a);
+            n2 *= q.pow(b);
+            point_p1 = self.point_add(&point_p1, &sylow_1);
+            point_p2 = self.point_add(&point_p2, &sylow_2);
+        }
+        GroupStructure {
+            n1,
+            n2,
+            point_p1,
+            point_p2,
+        }
+    }
+
+    // a basis (P1, P2) of the Sylow q-subgroup S ≅ Z_(q^a) × Z_(q^b), a >= b, a + b = e,
+    // whose elements are #E / q^e times random points. An element of maximal order
+    // spans a direct summand, and the elements of smaller order form a proper subgroup,
+    // so each sample misses it with probability at most 1 / q. For a random R, x with
+    // q^b R = x q^b P1 leaves C = R - x P1 of order at most q^b, and C completes the
+    // basis when q^(b-1) C is not in <P1>
+    fn sylow_basis(&self, q: &BigInt, e: u32, cofactor: &BigInt) -> (Point, u32, Point, u32) {
+        let sample = || self.scalar_mul(&self.random_point(), cofactor);
+        let log_order = |point: &Point| {
+            let (mut k, mut multiple) = (0u32, point.clone());
+            while !multiple.is_infinity() {
+                multiple = self.scalar_mul(&multiple, q);
+                k += 1;
+            }
+            k
+        };
+        loop {
+            let (mut point_p1, mut a) = (Point::Infinity, 0u32);
+            for _ in 0..SYLOW_SAMPLES {
+                let candidate = sample();
+                let k = log_order(&candidate);
+                if k > a {
+                    (point_p1, a) = (candidate, k);
+                }
+                if a == e {
+                    return (point_p1, e, Point::Infinity, 0);
+                }
+            }
+            // the exponent of S is at least sqrt(|S|), so a is wrong; draw again
+            if 2 * a < e {
+                continue;
+            }
+            let b = e - a;
+            let q_b = q.pow(b);
+            let base = self.scalar_mul(&point_p1, &q_b);
+            let bottom = self.scalar_mul(&point_p1, &q.pow(a - 1));
+            for _ in 0..SYLOW_SAMPLES {
+                let r = sample();
+                let x = match self.discrete_log(&base, &self.scalar_mul(&r, &q_b), &q.pow(a - b)) {
+                    Some(x) => x,
+                    None => continue,
+                };
+                let c = self.point_add(&r, &self.negate(&self.scalar_mul(&point_p1, &x)));
+                let top = self.scalar_mul(&c, &q.pow(b - 1));
+                if !top.is_infinity() && self.discrete_log(&bottom, &top, q).is_none() {
+                    return (point_p1, a, c, b);
+                }
+            }
+        }
+    }
+
+    // x in [0, order) with x G = T by baby-step giant-step, if T is in <G>; order is a
+    // multiple of the order of G
+    fn discrete_log(&self, point_g: &Point, target: &Point, order: &BigInt) -> Option<BigInt> {
+        let steps: BigInt = order.sqrt() + 1u32;
+        let mut baby = HashMap::new();
+        let mut current = Point::Infinity;
+        for j in 0..steps.to_u64()? {
+            baby.entry(current.clone()).or_insert(j);
+            current = self.point_add(&current, point_g);
+        }
+        // T - i * steps * G, matched against j * G
+        let giant_step = self.negate(&current);
+        let mut giant = target.clone();
+        for i in 0..=steps.to_u64()? {
+            if let Some(j) = baby.get(&giant) {
+                return Some((i * &steps + j) % order);
+            }
+            giant = self.point_add(&giant, &giant_step);
+        }
+        None
+    }
+
+    // every subgroup of E(F_p), by increasing order. In Z_n1 × Z_n2 they are the images
+    // of the lattices between Z^2 and n1 Z × n2 Z, whose Hermite bases (a, b), (0, d)
+    // have a | n1, d | n2, 0 <= b < d and d | (n1 / a) b. The Smith normal form of
+    // n1 Z × n2 Z written in such a basis gives the structure of the subgroup and a basis
+    pub fn subgroups(&self) -> Vec<Subgroup> {
+        let structure = self.group_structure();
+        let (n1, n2) = (&structure.n1, &structure.n2);
+        let mut subgroups = Vec::new();
+        for a in basic_op::divisors(n1) {
+            let m = n1 / &a;
+            for d in basic_op::divisors(n2) {
+                let step = &d / basic_op::gcd(&d, &m);
+                let mut b = BigInt::zero();
+                while b < d {
+                    let relations = [[m.clone(), -(&m * &b / &d)], [BigInt::zero(), n2 / &d]];
+                    let ([s1, s2], basis) = smith_normal_form(relations);
+                    // a row (u, v) of the basis is u (a, b) + v (0, d) in Z_n1 × Z_n2
+                    let point = |row: &[BigInt; 2]| structure.point(self, &(&row[0] * &a), &(&row[0] * &b + &row[1] * &d));
+                    let generators = if s1.is_one() {
+                        vec![point(&basis[1])]
+                    } else {
+                        vec![point(&basis[1]), point(&basis[0])]
+                    };
+                    subgroups.push(Subgroup {
+                        order: &s1 * &s2,
+                        structure: (s2, s1),
+                        generators,
+                    });
+                    b += &step;
+                }
+            }
+        }
+        subgroups.sort_by(|x, y| (&x.order, &x.structure).cmp(&(&y.order, &y.structure)));
+        subgroups
+    }
+
+    // the points P with n P = ∞, ∞ included. E[n](F_p) ≅ Z_gcd(n, n1) × Z_gcd(n, n2),
+    // and every point is listed, so on large curves n should share little with #E
+    pub fn torsion(&self, n: &BigInt) -> Vec<Point> {
+        let structure = self.group_structure();
+        let (g1, g2) = structure.torsion_structure(n);
+        let t1 = self.scalar_mul(&structure.point_p1, &(&structure.n1 / &g1));
+        let t2 = self.scalar_mul(&structure.point_p2, &(&structure.n2 / &g2));
+        let (g1, g2) = (g1.to_u64().expect("too many torsion points"), g2.to_u64().expect("too many torsion points"));
+        let mut points = Vec::new();
+        let mut row = Point::Infinity;
+        for _ in 0..g1 {
+            let mut point = row.clone();
+            for _ in 0..g2 {
+                points.push(point.clone());
+                point = self.point_add(&point, &t2);
+            }
+            row = self.point_add(&row, &t1);
+        }
+        points
+    }
+
+    // how many points have order d, for each d | n1
+    pub fn order_distribution(&self) -> BTreeMap<BigInt, BigInt> {
+        self.group_structure().order_distribution()
+    }
+}
@@ -0,0 +1,16 @@
+// Diffie-Hellman over any group: each party sends g^x and raises the other's element to
+// its own secret, so both end with g^(x1 x2)
+use crate::group::Group;
+use num_bigint::BigInt;
+
+// (g^x2)^x1, refusing an element outside <g> or the identity, which would pin the
+// shared secret to a small subgroup or to 1
+pub fn shared_secret<G: Group>(group: &G, secret: &BigInt, peer_public: &G::Element) -> Result<G::Element, String> {
+    if !group.is_element(peer_public) {
+        return Err("the peer's public element is not in the group".to_string());
+    }
+    if *peer_public == group.identity() {
+        return Err("the peer's public element is the identity".to_string());
+    }
+    Ok(group.exp(peer_public, secret))
+}
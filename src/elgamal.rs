@@ -0,0 +1,77 @@
+// additive EC-ElGamal: m is encrypted as (k G, m G + k Q) for the public key Q = s G, so
+// ciphertexts add up to an encryption of the sum. Decryption recovers m G, and m itself
+// only when it lies in a small range, where the kangaroo finds it in O(sqrt(range)) steps.
+// The `_in` functions are the same scheme in any group, (g^k, g^m y^k) for y = g^s, and
+// decrypt by baby-step giant-step
+use crate::arithmetic::basic_op;
+use crate::attacks::dlog;
+use crate::curve::ecc::{EcWei, Point};
+use crate::group::{bounded_log, EcGroup, Group};
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElGamalCiphertext<E = Point> {
+    pub c1: E,
+    pub c2: E,
+}
+
+// (k G, M + k Q) for a fresh k in [1, n)
+pub fn encrypt_point(new_ec: &EcWei, point_g: &Point, n: &BigInt, public_key: &Point, message: &Point) -> ElGamalCiphertext {
+    encrypt_element_in(&EcGroup::new(new_ec, point_g, n), public_key, message)
+}
+
+pub fn encrypt(new_ec: &EcWei, point_g: &Point, n: &BigInt, public_key: &Point, m: &BigInt) -> ElGamalCiphertext {
+    encrypt_in(&EcGroup::new(new_ec, point_g, n), public_key, m)
+}
+
+// M = c2 - s c1
+pub fn decrypt_point(new_ec: &EcWei, point_g: &Point, n: &BigInt, secret_key: &BigInt, ciphertext: &ElGamalCiphertext) -> Point {
+    decrypt_element_in(&EcGroup::new(new_ec, point_g, n), secret_key, ciphertext)
+}
+
+// m in [0, bound], found by the kangaroo from m G
+pub fn decrypt(new_ec: &EcWei, point_g: &Point, n: &BigInt, secret_key: &BigInt, ciphertext: &ElGamalCiphertext, bound: &BigInt) -> Result<BigInt, String> {
+    let message = decrypt_point(new_ec, point_g, n, secret_key, ciphertext);
+    dlog::kangaroo(new_ec, point_g, &message, &BigInt::zero(), bound)
+        .map_err(|_| format!("the plaintext is not in [0, {}]", bound))
+}
+
+// an encryption of m1 + m2
+pub fn add(new_ec: &EcWei, point_g: &Point, n: &BigInt, first: &ElGamalCiphertext, second: &ElGamalCiphertext) -> ElGamalCiphertext {
+    add_in(&EcGroup::new(new_ec, point_g, n), first, second)
+}
+
+// ----- any group -----
+
+// (g^k, M y^k) for a fresh k in [1, q)
+pub fn encrypt_element_in<G: Group>(group: &G, public_key: &G::Element, message: &G::Element) -> ElGamalCiphertext<G::Element> {
+    let k = basic_op::random_scalar(&group.order());
+    ElGamalCiphertext {
+        c1: group.exp_g(&k),
+        c2: group.op(message, &group.exp(public_key, &k)),
+    }
+}
+
+pub fn encrypt_in<G: Group>(group: &G, public_key: &G::Element, m: &BigInt) -> ElGamalCiphertext<G::Element> {
+    encrypt_element_in(group, public_key, &group.exp_g(m))
+}
+
+// M = c2 / c1^s
+pub fn decrypt_element_in<G: Group>(group: &G, secret_key: &BigInt, ciphertext: &ElGamalCiphertext<G::Element>) -> G::Element {
+    group.op(&ciphertext.c2, &group.inverse(&group.exp(&ciphertext.c1, secret_key)))
+}
+
+// m in [0, bound] from g^m
+pub fn decrypt_in<G: Group>(group: &G, secret_key: &BigInt, ciphertext: &ElGamalCiphertext<G::Element>, bound: &BigInt) -> Result<BigInt, String> {
+    let message = decrypt_element_in(group, secret_key, ciphertext);
+    bounded_log(group, &message, bound).ok_or_else(|| format!("the plaintext is not in [0, {}]", bound))
+}
+
+// an encryption of m1 + m2
+pub fn add_in<G: Group>(group: &G, first: &ElGamalCiphertext<G::Element>, second: &ElGamalCiphertext<G::Element>) -> ElGamalCiphertext<G::Element> {
+    ElGamalCiphertext {
+        c1: group.op(&first.c1, &second.c1),
+        c2: group.op(&first.c2, &second.c2),
+    }
+}
@@ -0,0 +1,270 @@
+// a cyclic group of prime order q with a fixed generator, as the discrete-log protocols
+// of the crate (Diffie-Hellman, Schnorr proofs, ElGamal, Feldman VSS) see it. Two backends:
+// the subgroup <G> of an elliptic curve, written additively, and the subgroup of order q
+// of Z_p^* (a Schnorr group), written multiplicatively. The trait uses the multiplicative
+// names, so op is + on the curve and exp(a, k) is k * a
+use crate::arithmetic::basic_op::{self, reduce};
+use crate::curve::ecc::{CurveParams, EcWei, Point};
+use crate::zk::transcript::Transcript;
+use crate::zk::{byte_len, point_from_fixed_bytes, point_to_fixed_bytes, to_fixed_bytes};
+use num_bigint::{BigInt, RandBigInt, Sign};
+use num_traits::{One, Zero};
+use std::collections::HashMap;
+use std::fmt;
+
+pub trait Group {
+    type Element: Clone + PartialEq + fmt::Debug;
+
+    fn identity(&self) -> Self::Element;
+
+    fn op(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    fn inverse(&self, a: &Self::Element) -> Self::Element;
+
+    // a^k, for any integer k (the exponent only matters modulo the order)
+    fn exp(&self, a: &Self::Element, k: &BigInt) -> Self::Element;
+
+    fn generator(&self) -> Self::Element;
+
+    // the prime order q of the generator
+    fn order(&self) -> BigInt;
+
+    // a is in the subgroup generated by the generator
+    fn is_element(&self, a: &Self::Element) -> bool;
+
+    // fixed width encoding, and its inverse for elements of the subgroup only
+    fn serialize(&self, a: &Self::Element) -> Vec<u8>;
+
+    fn deserialize(&self, bytes: &[u8]) -> Option<Self::Element>;
+
+    // how Fiat-Shamir binds an element
+    fn append_element(&self, transcript: &mut Transcript, label: &[u8], a: &Self::Element) {
+        transcript.append_message(label, &self.serialize(a));
+    }
+
+    // g^k
+    fn exp_g(&self, k: &BigInt) -> Self::Element {
+        self.exp(&self.generator(), k)
+    }
+
+    // a secret x in [1, q) and its public element g^x
+    fn key_pair(&self) -> (BigInt, Self::Element) {
+        let secret = basic_op::random_scalar(&self.order());
+        let public = self.exp_g(&secret);
+        (secret, public)
+    }
+}
+
+// ----- the subgroup <G> of order n of an elliptic curve -----
+
+#[derive(Debug, Clone)]
+pub struct EcGroup<'a> {
+    pub new_ec: &'a EcWei,
+    pub point_g: Point,
+    pub n: BigInt,
+}
+
+impl<'a> EcGroup<'a> {
+    pub fn new(new_ec: &'a EcWei, point_g: &Point, n: &BigInt) -> Self {
+        Self {
+            new_ec,
+            point_g: point_g.clone(),
+            n: n.clone(),
+        }
+    }
+}
+
+impl<'a> From<&'a CurveParams> for EcGroup<'a> {
+    fn from(params: &'a CurveParams) -> Self {
+        EcGroup::new(&params.curve, &params.point_g, &params.n)
+    }
+}
+
+impl Group for EcGroup<'_> {
+    type Element = Point;
+
+    fn identity(&self) -> Point {
+        Point::Infinity
+    }
+
+    fn op(&self, a: &Point, b: &Point) -> Point {
+        self.new_ec.point_add(a, b)
+    }
+
+    fn inverse(&self, a: &Point) -> Point {
+        self.new_ec.negate(a)
+    }
+
+    // the ladder, since k is often secret
+    fn exp(&self, a: &Point, k: &BigInt) -> Point {
+        self.new_ec.scalar_mul_ct(a, &reduce(k, &self.n))
+    }
+
+    fn generator(&self) -> Point {
+        self.point_g.clone()
+    }
+
+    fn order(&self) -> BigInt {
+        self.n.clone()
+    }
+
+    fn is_element(&self, a: &Point) -> bool {
+        self.new_ec.is_point(a) && self.new_ec.scalar_mul(a, &self.n).is_infinity()
+    }
+
+    fn serialize(&self, a: &Point) -> Vec<u8> {
+        point_to_fixed_bytes(a, byte_len(self.new_ec.p()))
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> Option<Point> {
+        let point = point_from_fixed_bytes(bytes, byte_len(self.new_ec.p()))?;
+        self.is_element(&point).then_some(point)
+    }
+
+    // as the curve-only protocols have always bound points
+    fn append_element(&self, transcript: &mut Transcript, label: &[u8], a: &Point) {
+        transcript.append_point(label, a);
+    }
+}
+
+// ----- the subgroup of order q of Z_p^*, for primes q | p - 1 -----
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchnorrGroup {
+    pub p: BigInt,
+    pub q: BigInt,
+    pub g: BigInt,
+}
+
+impl SchnorrGroup {
+    pub fn new(p: &BigInt, q: &BigInt, g: &BigInt) -> Result<Self, String> {
+        if !basic_op::is_probable_prime(p) || !basic_op::is_probable_prime(q) {
+            return Err("p and q must be prime".to_string());
+        }
+        if !((p - 1u32) % q).is_zero() {
+            return Err(format!("q = {} does not divide p - 1 = {}", q, p - 1u32));
+        }
+        if g <= &BigInt::one() || g >= p || !g.modpow(q, p).is_one() {
+            return Err(format!("g = {} does not have order q = {} modulo p = {}", g, q, p));
+        }
+        Ok(Self {
+            p: p.clone(),
+            q: q.clone(),
+            g: g.clone(),
+        })
+    }
+
+    // p = 23, q = 11, g = 4: the same order as the generator of the e17 curve
+    pub fn toy() -> Self {
+        Self::new(&BigInt::from(23), &BigInt::from(11), &BigInt::from(4)).unwrap()
+    }
+
+    // a prime q of q_bits bits, a prime p = k q + 1 of p_bits bits and g = h^k for the
+    // first h = 2, 3, ... with h^k != 1
+    pub fn random(p_bits: u64, q_bits: u64) -> Result<Self, String> {
+        if q_bits < 2 || p_bits < q_bits + 2 {
+            return Err("p needs at least two more bits than q".to_string());
+        }
+        let q = basic_op::random_prime(q_bits);
+        let mut rng = rand::thread_rng();
+        let (low, high) = (BigInt::one() << (p_bits - 1), BigInt::one() << p_bits);
+        loop {
+            // an even k in [2^(p_bits - 1) / q, 2^p_bits / q)
+            let k = rng.gen_bigint_range(&(&low / &q + 1u32), &(&high / &q)) >> 1 << 1;
+            let p = &k * &q + 1u32;
+            if p < low || p >= high || !basic_op::is_probable_prime(&p) {
+                continue;
+            }
+            let mut h = BigInt::from(2);
+            loop {
+                let g = h.modpow(&k, &p);
+                if !g.is_one() {
+                    return Self::new(&p, &q, &g);
+                }
+                h += 1u32;
+            }
+        }
+    }
+}
+
+impl fmt::Display for SchnorrGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}> of order {} in Z_{}^*", self.g, self.q, self.p)
+    }
+}
+
+impl Group for SchnorrGroup {
+    type Element = BigInt;
+
+    fn identity(&self) -> BigInt {
+        BigInt::one()
+    }
+
+    fn op(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        a * b % &self.p
+    }
+
+    // a^(q - 1), since a^q = 1
+    fn inverse(&self, a: &BigInt) -> BigInt {
+        a.modpow(&(&self.q - 1u32), &self.p)
+    }
+
+    fn exp(&self, a: &BigInt, k: &BigInt) -> BigInt {
+        a.modpow(&reduce(k, &self.q), &self.p)
+    }
+
+    fn generator(&self) -> BigInt {
+        self.g.clone()
+    }
+
+    fn order(&self) -> BigInt {
+        self.q.clone()
+    }
+
+    fn is_element(&self, a: &BigInt) -> bool {
+        a > &BigInt::zero() && a < &self.p && a.modpow(&self.q, &self.p).is_one()
+    }
+
+    fn serialize(&self, a: &BigInt) -> Vec<u8> {
+        to_fixed_bytes(a, byte_len(&self.p))
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> Option<BigInt> {
+        if bytes.len() != byte_len(&self.p) {
+            return None;
+        }
+        let a = BigInt::from_bytes_be(Sign::Plus, bytes);
+        self.is_element(&a).then_some(a)
+    }
+}
+
+// x in [0, bound] with g^x = a, by baby-step giant-step in O(sqrt(bound)) operations;
+// the exponential ElGamal of any group decrypts with it
+pub fn bounded_log<G: Group>(group: &G, a: &G::Element, bound: &BigInt) -> Option<BigInt> {
+    if bound < &BigInt::zero() {
+        return None;
+    }
+    let m = (bound + 1u32).sqrt() + 1u32;
+    // the baby steps g^j, keyed by their encoding
+    let mut table = HashMap::new();
+    let mut baby = group.identity();
+    let mut j = BigInt::zero();
+    while j < m {
+        table.entry(group.serialize(&baby)).or_insert_with(|| j.clone());
+        baby = group.op(&baby, &group.generator());
+        j += 1u32;
+    }
+    // the giant steps a g^(-i m)
+    let giant = group.inverse(&group.exp_g(&m));
+    let mut current = a.clone();
+    let mut i = BigInt::zero();
+    while &(&i * &m) <= bound {
+        if let Some(j) = table.get(&group.serialize(&current)) {
+            let x = &i * &m + j;
+            return (&x <= bound).then_some(x);
+        }
+        current = group.op(&current, &giant);
+        i += 1u32;
+    }
+    None
+}
@@ -0,0 +1,239 @@
+// hashing to elliptic curves (RFC 9380): expand_message_xmd with SHA-256, hash_to_field,
+// the simplified SWU map (through a 3-isogeny on secp256k1, whose a = 0) and, on the small
+// curves for which the RFC defines no suite, try-and-increment
+use crate::arithmetic::basic_op::{self, reduce};
+use crate::curve::ecc::{CurveParams, EcWei, Point};
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Zero};
+use sha2::{Digest, Sha256};
+
+// output and block size of SHA-256 in bytes
+const B_IN_BYTES: usize = 32;
+const S_IN_BYTES: usize = 64;
+// security parameter k of hash_to_field
+const SECURITY_BITS: u64 = 128;
+
+// section 5.3.1: len_in_bytes uniform bytes from msg, bound to the domain separation tag
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, String> {
+    let ell = len_in_bytes.div_ceil(B_IN_BYTES);
+    if ell > 255 || len_in_bytes > 65535 {
+        return Err("expand_message_xmd cannot produce that many bytes".to_string());
+    }
+    if dst.is_empty() {
+        return Err("the domain separation tag must not be empty".to_string());
+    }
+    // section 5.3.3: tags longer than 255 bytes are hashed first
+    let mut dst_prime = if dst.len() > 255 {
+        Sha256::new()
+            .chain_update(b"H2C-OVERSIZE-DST-")
+            .chain_update(dst)
+            .finalize()
+            .to_vec()
+    } else {
+        dst.to_vec()
+    };
+    dst_prime.push(dst_prime.len() as u8);
+
+    let b_0 = Sha256::new()
+        .chain_update([0u8; S_IN_BYTES])
+        .chain_update(msg)
+        .chain_update((len_in_bytes as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    let mut b_i = Sha256::new()
+        .chain_update(b_0)
+        .chain_update([1u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    let mut uniform_bytes = b_i.to_vec();
+    for i in 2..=ell {
+        let mixed: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(x, y)| x ^ y).collect();
+        b_i = Sha256::new()
+            .chain_update(mixed)
+            .chain_update([i as u8])
+            .chain_update(&dst_prime)
+            .finalize();
+        uniform_bytes.extend(b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+// section 5.2 for a prime field (m = 1): count elements of F_p, each reduced from
+// L = ceil((ceil(log2 p) + k) / 8) bytes so that the bias is below 2^-k
+pub fn hash_to_field(msg: &[u8], dst: &[u8], count: usize, p: &BigInt) -> Result<Vec<BigInt>, String> {
+    let len = (p.bits() + SECURITY_BITS).div_ceil(8) as usize;
+    let uniform_bytes = expand_message_xmd(msg, dst, count * len)?;
+    Ok(uniform_bytes
+        .chunks(len)
+        .map(|chunk| BigInt::from_bytes_be(Sign::Plus, chunk) % p)
+        .collect())
+}
+
+// section 4.1: the "sign" of an element of F_p is its parity
+fn sgn0(x: &BigInt) -> bool {
+    x.bit(0)
+}
+
+// section 6.6.2: simplified SWU onto y^2 = x^3 + ax + b with a * b != 0, for a non-square z
+// such that g(b / (z a)) is a square
+pub fn map_to_curve_simple_swu(u: &BigInt, a: &BigInt, b: &BigInt, z: &BigInt, p: &BigInt) -> Point {
+    let g = |x: &BigInt| reduce(&(x * x * x + a * x + b), p);
+    let u2 = (u * u) % p;
+    // inv0(z^2 u^4 + z u^2), where inv0(0) = 0
+    let tv1 = basic_op::inv_mod(&reduce(&(z * z * &u2 * &u2 + z * &u2), p), p).unwrap();
+    let x1 = if tv1.is_zero() {
+        reduce(&(b * basic_op::inv_mod(&reduce(&(z * a), p), p).unwrap()), p)
+    } else {
+        reduce(&(-b * basic_op::inv_mod(a, p).unwrap() * (BigInt::one() + tv1)), p)
+    };
+    let gx1 = g(&x1);
+    let (x, y) = match basic_op::sqrt_mod(&gx1, p) {
+        Some(y) => (x1, y),
+        None => {
+            let x2 = reduce(&(z * u2 * x1), p);
+            let y = basic_op::sqrt_mod(&g(&x2), p).unwrap();
+            (x2, y)
+        }
+    };
+    let y = if sgn0(u) != sgn0(&y) { reduce(&-y, p) } else { y };
+    Point::new(x, y)
+}
+
+// a curve with a hash-to-curve suite in RFC 9380 (section 8)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suite {
+    Secp256k1,
+    P256,
+}
+
+impl Suite {
+    pub fn for_curve(new_ec: &EcWei) -> Option<Suite> {
+        let same = |params: CurveParams| {
+            params.curve.a() == new_ec.a() && params.curve.b() == new_ec.b() && params.curve.p() == new_ec.p()
+        };
+        if same(CurveParams::secp256k1()) {
+            Some(Suite::Secp256k1)
+        } else if same(CurveParams::secp256r1()) {
+            Some(Suite::P256)
+        } else {
+            None
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            Suite::Secp256k1 => "secp256k1_XMD:SHA-256_SSWU_RO_",
+            Suite::P256 => "P256_XMD:SHA-256_SSWU_RO_",
+        }
+    }
+
+    fn map_to_curve(&self, new_ec: &EcWei, u: &BigInt) -> Point {
+        let p = new_ec.p();
+        match self {
+            // section 8.2, Z = -10
+            Suite::P256 => map_to_curve_simple_swu(u, new_ec.a(), new_ec.b(), &BigInt::from(-10), p),
+            // section 8.7: SSWU onto E': y^2 = x^3 + A'x + B', Z = -11, then the 3-isogeny to E
+            Suite::Secp256k1 => {
+                let a_prime = hex("3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533");
+                let point = map_to_curve_simple_swu(u, &a_prime, &BigInt::from(1771), &BigInt::from(-11), p);
+                iso_map_secp256k1(&point, p)
+            }
+        }
+    }
+
+    // section 3: hash_to_curve, u0 and u1 mapped and added; the cofactor of both curves is 1
+    pub fn hash_to_curve(&self, new_ec: &EcWei, msg: &[u8], dst: &[u8]) -> Result<Point, String> {
+        let u = hash_to_field(msg, dst, 2, new_ec.p())?;
+        let q0 = self.map_to_curve(new_ec, &u[0]);
+        let q1 = self.map_to_curve(new_ec, &u[1]);
+        Ok(new_ec.point_add(&q0, &q1))
+    }
+}
+
+fn hex(s: &str) -> BigInt {
+    BigInt::parse_bytes(s.as_bytes(), 16).unwrap()
+}
+
+// appendix E.1: the 3-isogeny E' -> secp256k1, as the rational maps
+// x = x_num / x_den and y = y' * y_num / y_den in x'
+fn iso_map_secp256k1(point: &Point, p: &BigInt) -> Point {
+    let (x, y) = match point {
+        Point::Infinity => return Point::Infinity,
+        Point::Affine(x, y) => (x, y),
+    };
+    let k = |coefficients: &[&str]| -> Vec<BigInt> { coefficients.iter().map(|c| hex(c)).collect() };
+    let x_num = k(&[
+        "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa8c7",
+        "07d3d4c80bc321d5b9f315cea7fd44c5d595d2fc0bf63b92dfff1044f17c6581",
+        "534c328d23f234e6e2a413deca25caece4506144037c40314ecbd0b53d9dd262",
+        "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa88c",
+    ]);
+    let x_den = k(&[
+        "d35771193d94918a9ca34ccbb7b640dd86cd409542f8487d9fe6b745781eb49b",
+        "edadc6f64383dc1df7c4b2d51b54225406d36b641f5e41bbc52a56612a8c6d14",
+        "1",
+    ]);
+    let y_num = k(&[
+        "4bda12f684bda12f684bda12f684bda12f684bda12f684bda12f684b8e38e23c",
+        "c75e0c32d5cb7c0fa9d0a54b12a0a6d5647ab046d686da6fdffc90fc201d71a3",
+        "29a6194691f91a73715209ef6512e576722830a201be2018a765e85a9ecee931",
+        "2f684bda12f684bda12f684bda12f684bda12f684bda12f684bda12f38e38d84",
+    ]);
+    let y_den = k(&[
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffff93b",
+        "7a06534bb8bdb49fd5e9e6632722c2989467c1bfc8e8d978dfb425d2685c2573",
+        "6484aa716545ca2cf3a70c3fa8fe337e0a3d21162f0d6299a7bf8192bfd2a76f",
+        "1",
+    ]);
+    // Horner, coefficients from the constant term up
+    let eval = |coefficients: &[BigInt]| {
+        coefficients
+            .iter()
+            .rev()
+            .fold(BigInt::zero(), |acc, c| (acc * x + c) % p)
+    };
+    let den_x = eval(&x_den);
+    let den_y = eval(&y_den);
+    // the denominators vanish only at the points of the kernel, which go to ∞
+    let (inv_x, inv_y) = match (basic_op::inv_mod(&den_x, p), basic_op::inv_mod(&den_y, p)) {
+        (Some(inv_x), Some(inv_y)) if !den_x.is_zero() && !den_y.is_zero() => (inv_x, inv_y),
+        _ => return Point::Infinity,
+    };
+    let x_out = (eval(&x_num) * inv_x) % p;
+    let y_out = (y * eval(&y_num) % p * inv_y) % p;
+    Point::new(x_out, y_out)
+}
+
+// x = hash_to_field(msg || ctr) is tried for ctr = 0, 1, ... until x^3 + ax + b is a square;
+// a second field element picks the sign of y, and multiplying by the cofactor h moves the
+// point into the subgroup of order #E / h. Not constant time: the number of tries depends
+// on msg, which is fine for public inputs such as generator labels
+pub fn try_and_increment(new_ec: &EcWei, h: &BigInt, msg: &[u8], dst: &[u8]) -> Result<Point, String> {
+    let p = new_ec.p();
+    for ctr in 0..=255u8 {
+        let mut input = msg.to_vec();
+        input.push(ctr);
+        let u = hash_to_field(&input, dst, 2, p)?;
+        let rhs = reduce(&(&u[0] * &u[0] * &u[0] + new_ec.a() * &u[0] + new_ec.b()), p);
+        let y = match basic_op::sqrt_mod(&rhs, p) {
+            Some(y) => y,
+            None => continue,
+        };
+        let y = if sgn0(&y) != sgn0(&u[1]) { reduce(&-y, p) } else { y };
+        let point = new_ec.scalar_mul(&Point::new(u[0].clone(), y), h);
+        if !point.is_infinity() {
+            return Ok(point);
+        }
+    }
+    Err("no point found after 256 tries".to_string())
+}
+
+// the RFC 9380 suite when the curve has one, try-and-increment otherwise; h is the cofactor
+pub fn hash_to_curve(new_ec: &EcWei, h: &BigInt, msg: &[u8], dst: &[u8]) -> Result<Point, String> {
+    match Suite::for_curve(new_ec) {
+        Some(suite) => suite.hash_to_curve(new_ec, msg, dst),
+        None => try_and_increment(new_ec, h, msg, dst),
+    }
+}
@@ -1,4 +1,13 @@
 pub mod curve;
 pub mod paillier;
 pub mod arithmetic;
-pub mod mpc_wallet;
\ No newline at end of file
+pub mod commitment;
+pub mod mpc_wallet;
+pub mod zk;
+pub mod hash_to_curve;
+pub mod plot;
+pub mod attacks;
+pub mod elgamal;
+pub mod group;
+pub mod dh;
+pub mod vss;
@@ -1,62 +1,576 @@
-use crate::{arithmetic::basic_op::*, curve::ecc::EcWei, curve::ecc::Point, paillier::*};
-use num_bigint::BigInt;
-use num_traits::{FromPrimitive, One, Zero};
-use rand::Rng;
-use sha2::{Digest, Sha256};
+use crate::{arithmetic::basic_op::*, curve::ecc::CurveParams, curve::ecc::Point, paillier::*};
+use crate::commitment::commit_reveal::{self, Commitment, Decommitment};
+use crate::dh;
+use crate::group::EcGroup;
+use crate::zk::paillier_dlog::{self, PaillierDlogProof, PaillierDlogStatement};
+use crate::zk::schnorr::{self, SchnorrProof};
+use crate::zk::{byte_len, point_from_fixed_bytes, point_to_fixed_bytes};
+use crate::zk::transcript::Transcript;
+use num_bigint::{BigInt, RandBigInt};
+use num_traits::{One, Zero};
+use std::fmt;
 
-pub fn ecdsa_mpc(new_ec: &EcWei, group_add: &Vec<crate::curve::ecc::Point>, points_g: &Vec<Point>) {
+// the protocol rounds in which an incoming message is checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Round {
+    // DKG: each party posts its public share Q_i = d_i * G
+    KeyShare,
+    // DKG: Party 1 posts its Paillier public key, c_key = Enc(d_1) and a proof that c_key
+    // encrypts the d_1 behind Q_1
+    PaillierKey,
+    // Signing: each party commits to R_i = k_i * G and its proof, then opens the commitment
+    Nonce,
+    // Signing: Party 2 sends the homomorphically computed c3 to Party 1
+    PartialSignature,
+}
+
+// the check that an incoming message failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    PointOnCurve,
+    PointNotInfinity,
+    PointInSubgroup,
+    NonceXNonZero,
+    ProofOfKnowledge,
+    Decommitment,
+    CiphertextInRange,
+    EncryptedShare,
+    PartialSignatureOpening,
+}
+
+// public data that lets any honest party repeat the failing check on its own
+#[derive(Debug, Clone, PartialEq)]
+pub enum Evidence {
+    Point(Point),
+    Schnorr {
+        point: Point,
+        proof: SchnorrProof,
+    },
+    Decommitment {
+        commitment: Commitment,
+        decommitment: Decommitment,
+    },
+    Ciphertext {
+        c: BigInt,
+        public_key: (BigInt, BigInt),
+    },
+    EncryptedShare {
+        statement: PaillierDlogStatement,
+        proof: PaillierDlogProof,
+    },
+    PartialSignature(Box<PartialSignatureOpening>),
+}
+
+// Party 1 opens c3 = Enc(s'; rand) and reveals the one-time nonce k1 of R1,
+// so that everyone can see that s = s' * k1^-1 does not verify. The proof that c_key
+// encrypts the d1 behind Q1 is attached: with it, an honest c3 always gives a valid
+// signature, so a failure is Part 2's. The verifier compares c3, R2, Q and H(M) with the
+// values both parties hold
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialSignatureOpening {
+    pub c3: BigInt,
+    pub key_share: PaillierDlogStatement,
+    pub key_share_proof: PaillierDlogProof,
+    pub s_prime: BigInt,
+    pub randomness: BigInt,
+    pub k1: BigInt,
+    pub point_r1: Point,
+    pub point_r2: Point,
+    pub point_q: Point,
+    pub hash: BigInt,
+}
+
+// identifiable abort: who misbehaved, where, which check failed and why
+#[derive(Debug, Clone, PartialEq)]
+pub struct Abort {
+    pub culprit: usize,
+    pub round: Round,
+    pub check: Check,
+    pub evidence: Box<Evidence>,
+}
+
+impl fmt::Display for Abort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Part {} misbehaved in round {:?}: check {:?} failed",
+            self.culprit, self.round, self.check
+        )
+    }
+}
+
+impl Abort {
+    // re-run the failing check on the published evidence; true means the blame holds
+    pub fn verify(&self, params: &CurveParams) -> bool {
+        let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+        match (&self.check, self.evidence.as_ref()) {
+            (Check::PointOnCurve, Evidence::Point(point)) => !new_ec.is_point(point),
+            (Check::PointNotInfinity, Evidence::Point(point)) => point.is_infinity(),
+            (Check::PointInSubgroup, Evidence::Point(point)) => {
+                new_ec.is_point(point) && !new_ec.scalar_mul(point, n).is_infinity()
+            }
+            (Check::NonceXNonZero, Evidence::Point(point)) => {
+                point.x().is_some_and(|x| (x % n).is_zero())
+            }
+            (Check::ProofOfKnowledge, Evidence::Schnorr { point, proof }) => {
+                let mut transcript = proof_transcript(params, self.round, self.culprit);
+                !schnorr::verify(new_ec, point_g, n, point, proof, &mut transcript)
+            }
+            (Check::Decommitment, Evidence::Decommitment { commitment, decommitment }) => {
+                let opened = NonceMsg::from_bytes(params, &decommitment.message);
+                !commit_reveal::verify(commitment, decommitment)
+                    || opened.is_none_or(|msg| msg.party != self.culprit)
+            }
+            (Check::CiphertextInRange, Evidence::Ciphertext { c, public_key }) => {
+                !is_valid_cipher_paillier(public_key, c)
+            }
+            (Check::EncryptedShare, Evidence::EncryptedShare { statement, proof }) => {
+                !encrypted_share_holds(params, self.culprit, statement, proof)
+            }
+            // c3 comes from Part 2, and c_key from Part 1
+            (Check::PartialSignatureOpening, Evidence::PartialSignature(opening)) => {
+                if self.culprit != 2
+                    || !encrypted_share_holds(params, 1, &opening.key_share, &opening.key_share_proof)
+                    || cipher_paillier_with_r(&opening.key_share.public_key, &opening.s_prime, &opening.randomness)
+                        != opening.c3
+                    || new_ec.scalar_mul(point_g, &opening.k1) != opening.point_r1
+                {
+                    return false;
+                }
+                let r = match new_ec.scalar_mul(&opening.point_r2, &opening.k1) {
+                    Point::Affine(x, _) => x % n,
+                    Point::Infinity => return false,
+                };
+                let s = match inv_mod(&opening.k1, n) {
+                    Some(inv_k1) => (&opening.s_prime * inv_k1) % n,
+                    None => return false,
+                };
+                !verify_ecdsa(params, &opening.point_q, &opening.hash, &r, &s)
+            }
+            _ => false,
+        }
+    }
+}
+
+// message of the DKG round: the public share of a party
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyShareMsg {
+    pub party: usize,
+    pub point_q: Point,
+    // proof of knowledge of d_i
+    pub proof: SchnorrProof,
+}
+
+// message of the DKG round: Party 1 Paillier public key and its encrypted secret
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaillierKeyMsg {
+    pub party: usize,
+    pub public_key: (BigInt, BigInt),
+    pub c_key: BigInt,
+    // proof that c_key encrypts log_G(Q_1)
+    pub proof: PaillierDlogProof,
+}
+
+// message of the first signing round: the nonce point of a party
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonceMsg {
+    pub party: usize,
+    pub point_r: Point,
+    // proof of knowledge of k_i
+    pub proof: SchnorrProof,
+}
+
+impl NonceMsg {
+    // party || R || proof, with the fixed widths of the field and the order
+    pub fn to_bytes(&self, params: &CurveParams) -> Vec<u8> {
+        let field_len = byte_len(params.curve.p());
+        let mut out = (self.party as u64).to_be_bytes().to_vec();
+        out.extend(point_to_fixed_bytes(&self.point_r, field_len));
+        out.extend(self.proof.to_bytes(&params.curve, &params.n));
+        out
+    }
+
+    pub fn from_bytes(params: &CurveParams, bytes: &[u8]) -> Option<NonceMsg> {
+        let field_len = byte_len(params.curve.p());
+        let point_len = 1 + 2 * field_len;
+        if bytes.len() < 8 + point_len {
+            return None;
+        }
+        let (party, rest) = bytes.split_at(8);
+        let (point_r, proof) = rest.split_at(point_len);
+        Some(NonceMsg {
+            party: u64::from_be_bytes(party.try_into().ok()?) as usize,
+            point_r: point_from_fixed_bytes(point_r, field_len)?,
+            proof: SchnorrProof::from_bytes(&params.curve, &params.n, proof)?,
+        })
+    }
+}
+
+// first half of the nonce round: a commitment to the serialized NonceMsg, so that no
+// party can choose its R_i after seeing the other one
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonceCommitmentMsg {
+    pub party: usize,
+    pub commitment: Commitment,
+}
+
+// second half of the nonce round: the opening of that commitment
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonceRevealMsg {
+    pub party: usize,
+    pub decommitment: Decommitment,
+}
+
+pub fn commit_nonce(params: &CurveParams, msg: &NonceMsg) -> (NonceCommitmentMsg, NonceRevealMsg) {
+    let (commitment, decommitment) = commit_reveal::commit(&msg.to_bytes(params));
+    (
+        NonceCommitmentMsg {
+            party: msg.party,
+            commitment,
+        },
+        NonceRevealMsg {
+            party: msg.party,
+            decommitment,
+        },
+    )
+}
+
+// message of the second signing round: c3 from Party 2
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialSignatureMsg {
+    pub party: usize,
+    pub c3: BigInt,
+}
+
+fn check_point(params: &CurveParams, party: usize, round: Round, point: &Point) -> Result<(), Abort> {
+    let failed = if point.is_infinity() {
+        Some(Check::PointNotInfinity)
+    } else if !params.curve.is_point(point) {
+        Some(Check::PointOnCurve)
+    } else {
+        None
+    };
+    match failed {
+        Some(check) => Err(Abort {
+            culprit: party,
+            round,
+            check,
+            evidence: Box::new(Evidence::Point(point.clone())),
+        }),
+        None => Ok(()),
+    }
+}
+
+// n * Q = ∞: with a cofactor, a point outside <G> would leak d_i mod a small order
+// through the Diffie-Hellman secret
+fn check_subgroup(params: &CurveParams, party: usize, round: Round, point: &Point) -> Result<(), Abort> {
+    if params.curve.scalar_mul(point, &params.n).is_infinity() {
+        Ok(())
+    } else {
+        Err(subgroup_abort(party, round, point))
+    }
+}
+
+fn subgroup_abort(party: usize, round: Round, point: &Point) -> Abort {
+    Abort {
+        culprit: party,
+        round,
+        check: Check::PointInSubgroup,
+        evidence: Box::new(Evidence::Point(point.clone())),
+    }
+}
+
+// Q_j * d_i for the share of the other party; verify_key_share has already ruled out ∞
+// and points off the curve, so dh can only refuse a point outside <G>
+fn key_share_dh(group: &EcGroup, secret: &BigInt, share: &KeyShareMsg) -> Result<Point, Abort> {
+    dh::shared_secret(group, secret, &share.point_q)
+        .map_err(|_| subgroup_abort(share.party, Round::KeyShare, &share.point_q))
+}
+
+fn check_proof(
+    params: &CurveParams,
+    party: usize,
+    round: Round,
+    point: &Point,
+    proof: &SchnorrProof,
+) -> Result<(), Abort> {
+    let mut transcript = proof_transcript(params, round, party);
+    if schnorr::verify(&params.curve, &params.point_g, &params.n, point, proof, &mut transcript) {
+        Ok(())
+    } else {
+        Err(Abort {
+            culprit: party,
+            round,
+            check: Check::ProofOfKnowledge,
+            evidence: Box::new(Evidence::Schnorr {
+                point: point.clone(),
+                proof: proof.clone(),
+            }),
+        })
+    }
+}
+
+// binds each proof to the curve, the generator, its round and its sender so it cannot be
+// replayed elsewhere
+pub fn proof_transcript(params: &CurveParams, round: Round, party: usize) -> Transcript {
+    let mut transcript = Transcript::new(b"ecdsa-mpc");
+    transcript.append_scalar(b"a", params.curve.a());
+    transcript.append_scalar(b"b", params.curve.b());
+    transcript.append_scalar(b"p", params.curve.p());
+    transcript.append_point(b"G", &params.point_g);
+    transcript.append_scalar(b"n", &params.n);
+    transcript.append_scalar(b"h", &params.h);
+    transcript.append_message(b"round", format!("{:?}", round).as_bytes());
+    transcript.append_u64(b"party", party as u64);
+    transcript
+}
+
+fn check_ciphertext(
+    party: usize,
+    round: Round,
+    public_key: &(BigInt, BigInt),
+    c: &BigInt,
+) -> Result<(), Abort> {
+    if is_valid_cipher_paillier(public_key, c) {
+        Ok(())
+    } else {
+        Err(Abort {
+            culprit: party,
+            round,
+            check: Check::CiphertextInRange,
+            evidence: Box::new(Evidence::Ciphertext {
+                c: c.clone(),
+                public_key: public_key.clone(),
+            }),
+        })
+    }
+}
+
+pub fn verify_key_share(params: &CurveParams, msg: &KeyShareMsg) -> Result<(), Abort> {
+    check_point(params, msg.party, Round::KeyShare, &msg.point_q)?;
+    check_subgroup(params, msg.party, Round::KeyShare, &msg.point_q)?;
+    check_proof(params, msg.party, Round::KeyShare, &msg.point_q, &msg.proof)
+}
+
+// c_key must be proven to encrypt the d1 behind Q1 = point_q, the share the same party
+// posted in the KeyShare round
+pub fn verify_paillier_key(params: &CurveParams, msg: &PaillierKeyMsg, point_q: &Point) -> Result<(), Abort> {
+    check_ciphertext(msg.party, Round::PaillierKey, &msg.public_key, &msg.c_key)?;
+    let statement = PaillierDlogStatement {
+        public_key: msg.public_key.clone(),
+        c_key: msg.c_key.clone(),
+        point_x: point_q.clone(),
+    };
+    if encrypted_share_holds(params, msg.party, &statement, &msg.proof) {
+        Ok(())
+    } else {
+        Err(Abort {
+            culprit: msg.party,
+            round: Round::PaillierKey,
+            check: Check::EncryptedShare,
+            evidence: Box::new(Evidence::EncryptedShare {
+                statement,
+                proof: msg.proof.clone(),
+            }),
+        })
+    }
+}
+
+// the proof holds, and N leaves room for s' = Enc(k2^-1 H(M) mod n) + v * Dec(c_key) with
+// v < n: the proof bounds the plaintext below response_bound(n), so s' never wraps modulo N
+// once N > n * response_bound(n). When n divides N, as on the teaching curves, a wrap
+// leaves s' mod n unchanged
+fn encrypted_share_holds(
+    params: &CurveParams,
+    party: usize,
+    statement: &PaillierDlogStatement,
+    proof: &PaillierDlogProof,
+) -> bool {
+    let (n, modulus) = (&params.n, &statement.public_key.1);
+    let room = (modulus % n).is_zero() || modulus > &(n * paillier_dlog::response_bound(n));
+    let mut transcript = proof_transcript(params, Round::PaillierKey, party);
+    room && paillier_dlog::verify(&params.curve, &params.point_g, n, statement, proof, &mut transcript)
+}
+
+pub fn verify_nonce(params: &CurveParams, msg: &NonceMsg) -> Result<(), Abort> {
+    check_point(params, msg.party, Round::Nonce, &msg.point_r)?;
+    check_proof(params, msg.party, Round::Nonce, &msg.point_r, &msg.proof)?;
+    if msg.point_r.x().is_some_and(|x| (x % &params.n).is_zero()) {
+        return Err(Abort {
+            culprit: msg.party,
+            round: Round::Nonce,
+            check: Check::NonceXNonZero,
+            evidence: Box::new(Evidence::Point(msg.point_r.clone())),
+        });
+    }
+    Ok(())
+}
+
+// checks the opening against the earlier commitment, then the revealed nonce itself
+pub fn open_nonce(
+    params: &CurveParams,
+    commitment: &NonceCommitmentMsg,
+    reveal: &NonceRevealMsg,
+) -> Result<NonceMsg, Abort> {
+    let opened = NonceMsg::from_bytes(params, &reveal.decommitment.message)
+        .filter(|msg| msg.party == commitment.party);
+    match opened {
+        Some(msg) if commit_reveal::verify(&commitment.commitment, &reveal.decommitment) => {
+            verify_nonce(params, &msg)?;
+            Ok(msg)
+        }
+        _ => Err(Abort {
+            culprit: commitment.party,
+            round: Round::Nonce,
+            check: Check::Decommitment,
+            evidence: Box::new(Evidence::Decommitment {
+                commitment: commitment.commitment.clone(),
+                decommitment: reveal.decommitment.clone(),
+            }),
+        }),
+    }
+}
+
+pub fn verify_partial_signature(msg: &PartialSignatureMsg, public_key: &(BigInt, BigInt)) -> Result<(), Abort> {
+    check_ciphertext(msg.party, Round::PartialSignature, public_key, &msg.c3)
+}
+
+// H(M) as a uniform scalar modulo the order n of the curve
+pub fn hash_message(params: &CurveParams, message: &str) -> BigInt {
+    let mut transcript = Transcript::new(b"ecdsa-mpc/message");
+    transcript.append_message(b"M", message.as_bytes());
+    transcript.challenge_scalar(b"H(M)", &params.n)
+}
+
+pub fn verify_ecdsa(params: &CurveParams, point_q: &Point, hash: &BigInt, r: &BigInt, s: &BigInt) -> bool {
+    let (new_ec, n) = (&params.curve, &params.n);
+    if r.is_zero() || s.is_zero() {
+        return false;
+    }
+    let inv_s = match inv_mod(s, n) {
+        Some(inv_s) => inv_s,
+        None => return false,
+    };
+    let u1 = (hash * &inv_s) % n;
+    let u2 = (r * &inv_s) % n;
+    match new_ec.multi_scalar_mul(&[(params.point_g.clone(), u1), (point_q.clone(), u2)]) {
+        Point::Affine(x, _) => r == &(x % n),
+        Point::Infinity => false,
+    }
+}
+
+// an honest party redraws k_i until R_i = k_i * G passes its own NonceXNonZero check
+fn random_nonce(params: &CurveParams) -> (BigInt, Point) {
+    loop {
+        let k = random_scalar(&params.n);
+        let point_r = params.mul_g(&k);
+        if point_r.x().is_some_and(|x| !(x % &params.n).is_zero()) {
+            return (k, point_r);
+        }
+    }
+}
+
+// Paillier primes for Party 1. s' = k2^-1 * H(M) + v * Dec(c_key) is computed modulo N, so
+// it must still be right modulo n: on the teaching curves N = n * q, so n divides N and the
+// numbers stay printable; otherwise N > n * response_bound(n), the most the proof on c_key
+// lets Dec(c_key) grow to, so s' never wraps (see encrypted_share_holds).
+// gen_key_paillier uses g = 5, so 5 must not divide N
+pub fn paillier_primes(n: &BigInt) -> (BigInt, BigInt) {
+    let five = BigInt::from(5);
+    if n.bits() <= 16 && is_probable_prime(n) && n != &five {
+        // Paillier needs gcd(N, (p - 1)(q - 1)) = 1
+        let mut q = BigInt::from(3);
+        while &q == n
+            || q == five
+            || !is_probable_prime(&q)
+            || gcd(&(n * &q), &((n - 1) * (&q - 1))) != BigInt::one()
+        {
+            q += 1;
+        }
+        (n.clone(), q)
+    } else {
+        // n < 2^b and response_bound(n) < 2^(2b + SLACK_BITS + 1), and N >= 2^(2 bits - 2)
+        let bits = (3 * n.bits().max(3) + paillier_dlog::SLACK_BITS + 4).div_ceil(2);
+        let p = random_prime(bits);
+        let mut q = random_prime(bits);
+        while q == p {
+            q = random_prime(bits);
+        }
+        (p, q)
+    }
+}
+
+pub fn ecdsa_mpc(params: &CurveParams) -> Result<(BigInt, BigInt), Abort> {
     // TSS setup with ECDSA: For two parties
-    println!("\n ----+------ TSS setup with ECDSA: For two parties: ----+------ \n");
-    let generators = new_ec.get_base_points(&group_add);
-    let point_g = generators[0].clone();
-    let key_pair_1 = new_ec.gen_key_pair(&point_g);
+    println!("\n ----+------ TSS setup with ECDSA: For two parties ({}): ----+------ \n", params.name);
+    let new_ec = &params.curve;
+    let n = params.n.clone();
+    let point_g = params.point_g.clone();
+    let key_pair_1 = params.gen_key_pair();
     println!(
         " Generator {:?} -> {:?} Part 1",
         point_g,
-        &key_pair_1.as_ref().unwrap()
+        &key_pair_1
     );
-    let key_pair_2 = new_ec.gen_key_pair(&point_g);
+    let key_pair_2 = params.gen_key_pair();
     println!(
         " Generator {:?} -> {:?} Part 2",
         point_g,
-        &key_pair_2.as_ref().unwrap()
+        &key_pair_2
     );
+    let share_1 = KeyShareMsg {
+        party: 1,
+        point_q: key_pair_1.pk.clone(),
+        proof: schnorr::prove(
+            new_ec,
+            &point_g,
+            &n,
+            &key_pair_1.sk,
+            &mut proof_transcript(params, Round::KeyShare, 1),
+        ),
+    };
+    let share_2 = KeyShareMsg {
+        party: 2,
+        point_q: key_pair_2.pk.clone(),
+        proof: schnorr::prove(
+            new_ec,
+            &point_g,
+            &n,
+            &key_pair_2.sk,
+            &mut proof_transcript(params, Round::KeyShare, 2),
+        ),
+    };
+    // each party checks the share it receives and its proof of knowledge of d_i
+    verify_key_share(params, &share_2)?;
+    verify_key_share(params, &share_1)?;
+    println!(" ZK proofs of knowledge of d1 and d2 verified");
 
     // Diffie-Hellman
     println!("\n ----+------ Diffie-Hellman (DH): ----+------ \n");
     println!(" Part 1 computa Q = Q_2 * d1:");
-    let part_1_dh = new_ec.scalar_mul(
-        &key_pair_2.as_ref().unwrap().pk,
-        &key_pair_1.as_ref().unwrap().sk,
-    );
+    let group = EcGroup::from(params);
+    let part_1_dh = key_share_dh(&group, &key_pair_1.sk, &share_2)?;
     println!(" Part 1 gets Q as -> {:?}", &part_1_dh);
     println!(" Part 2 computa Q = Q_1 * d2:");
-    let part_2_dh = new_ec.scalar_mul(
-        &key_pair_1.as_ref().unwrap().pk,
-        &key_pair_2.as_ref().unwrap().sk,
-    );
+    let part_2_dh = key_share_dh(&group, &key_pair_2.sk, &share_1)?;
     println!(" Part 1 gets Q as -> {:?}", &part_2_dh);
     println!(
         " The Diffie-Hellman protocol is followed -> {:?}",
-        &part_1_dh == &part_2_dh
+        part_1_dh == part_2_dh
     );
-    let mut point_g = Point::new(BigInt::zero(), BigInt::zero());
-    if part_1_dh == part_2_dh {
-        point_g = part_1_dh;
-    }
+    // d1 * Q2 = d2 * Q1 = d1 d2 G once both shares have passed verify_key_share
+    let point_q = part_1_dh;
 
     // Paillier key generation
     println!("\n ----+------ Paillier key generation: ----+------ \n");
-    let paillier_key_p1 = gen_key_paillier(
-        &BigInt::from_i64(11).unwrap(),
-        &BigInt::from_i64(3).unwrap(),
-    );
+    let (paillier_p, paillier_q) = paillier_primes(&n);
+    let paillier_key_p1 = gen_key_paillier(&paillier_p, &paillier_q);
     println!(" Part 1 -> {:?}", &paillier_key_p1);
 
     // Chiper secret with Paillier - Part 1
     let chiper_p1 = cipher_paillier(
         &paillier_key_p1.public_key,
-        &key_pair_1.as_ref().unwrap().sk,
+        &key_pair_1.sk,
     );
     let dechiper_p1 = decipher_paillier(
         &paillier_key_p1.private_key,
@@ -65,7 +579,7 @@ pub fn ecdsa_mpc(new_ec: &EcWei, group_add: &Vec<crate::curve::ecc::Point>, poin
     );
     println!(
         " Encrypting the secret ({:?}) of Part 1 -> {:?}",
-        &key_pair_1.as_ref().unwrap().sk,
+        &key_pair_1.sk,
         &chiper_p1.as_ref().unwrap()
     );
     println!(
@@ -73,15 +587,45 @@ pub fn ecdsa_mpc(new_ec: &EcWei, group_add: &Vec<crate::curve::ecc::Point>, poin
         &chiper_p1.as_ref().unwrap(),
         &dechiper_p1
     );
+    let c_key = chiper_p1.as_ref().unwrap().clone();
+    let key_share = PaillierDlogStatement {
+        public_key: paillier_key_p1.public_key.clone(),
+        c_key: c_key.clone(),
+        point_x: share_1.point_q.clone(),
+    };
+    let c_key_randomness = recover_randomness_paillier(
+        &paillier_key_p1.private_key,
+        &c_key,
+        &key_pair_1.sk,
+        &paillier_key_p1.public_key,
+    )
+    .expect("Part 1 opens its own ciphertexts");
+    let paillier_msg = PaillierKeyMsg {
+        party: 1,
+        public_key: paillier_key_p1.public_key.clone(),
+        c_key,
+        proof: paillier_dlog::prove(
+            new_ec,
+            &point_g,
+            &n,
+            &key_share,
+            &key_pair_1.sk,
+            &c_key_randomness,
+            &mut proof_transcript(params, Round::PaillierKey, 1),
+        ),
+    };
+    // Part 2 checks the Paillier key message of Part 1 against Q1
+    verify_paillier_key(params, &paillier_msg, &share_1.point_q)?;
+    println!(" ZK proof that c_key encrypts the d1 behind Q1 verified");
 
     // Chiper secret with Paillier - Part 2
     let chiper_p2 = cipher_paillier(
         &paillier_key_p1.public_key,
-        &key_pair_2.as_ref().unwrap().sk,
+        &key_pair_2.sk,
     );
     println!(
         " Encrypting the secret ({:?}) of Part 2 -> {:?}",
-        &key_pair_2.as_ref().unwrap().sk,
+        &key_pair_2.sk,
         &chiper_p2.as_ref().unwrap()
     );
 
@@ -90,28 +634,28 @@ pub fn ecdsa_mpc(new_ec: &EcWei, group_add: &Vec<crate::curve::ecc::Point>, poin
     println!("       Dec(Enc(m_1) * Enc(m_2)) = m_1 + m_2");
     println!(
         "       Dec(Enc({0:?}) * Enc({1:?})) = {0:?} + {1:?}",
-        &key_pair_1.as_ref().unwrap().sk,
-        &key_pair_2.as_ref().unwrap().sk
+        &key_pair_1.sk,
+        &key_pair_2.sk
     );
     println!(
         "       Dec({0:?} * {1:?}) = {2:?} + {3:?}",
         &chiper_p1.as_ref().unwrap(),
         &chiper_p2.as_ref().unwrap(),
-        &key_pair_1.as_ref().unwrap().sk,
-        &key_pair_2.as_ref().unwrap().sk
+        &key_pair_1.sk,
+        &key_pair_2.sk
     );
     println!(
         "       Dec({0:?}) = {1:?} + {2:?}",
         chiper_p1.as_ref().unwrap() * chiper_p2.as_ref().unwrap(),
-        &key_pair_1.as_ref().unwrap().sk,
-        &key_pair_2.as_ref().unwrap().sk
+        &key_pair_1.sk,
+        &key_pair_2.sk
     );
     println!(
         "       Dec({0:?}) = {1:?} + {2:?}",
         (chiper_p1.as_ref().unwrap() * chiper_p2.as_ref().unwrap())
             % &paillier_key_p1.public_key.1.clone().pow(2),
-        &key_pair_1.as_ref().unwrap().sk,
-        &key_pair_2.as_ref().unwrap().sk
+        &key_pair_1.sk,
+        &key_pair_2.sk
     );
     println!(
         "Part 1 Dec:  {0:?} = {1:?} + {2:?}",
@@ -121,8 +665,8 @@ pub fn ecdsa_mpc(new_ec: &EcWei, group_add: &Vec<crate::curve::ecc::Point>, poin
                 % &paillier_key_p1.public_key.1.clone().pow(2),
             &paillier_key_p1.public_key
         ),
-        &key_pair_1.as_ref().unwrap().sk,
-        &key_pair_2.as_ref().unwrap().sk
+        &key_pair_1.sk,
+        &key_pair_2.sk
     );
 
     // MPC Wallet
@@ -130,115 +674,158 @@ pub fn ecdsa_mpc(new_ec: &EcWei, group_add: &Vec<crate::curve::ecc::Point>, poin
     println!(" MPC wallet will sign the message M");
     let message = "Hello Victor, this is a message from Peggy";
     println!(" (M = {})", message);
-
-    println!("\n ----+------ MPC Wallet Configuration ----+------");
-    println!("\n   + --- Part 1 generates a random secret k1, point R1 and a ZK proof --- +  \n");
-    let ckey = chiper_p1;
+    let ckey = paillier_msg.c_key;
     let mut rng = rand::thread_rng();
-    let k1 = BigInt::from(rng.gen_range(1..(group_add.len() + 1)));
-    println!("     k1 = {}", &k1);
-    let point_r1 = new_ec.scalar_mul(&points_g[0], &k1);
-    println!("     R1 = {:?}", &point_r1);
-    println!("     => Post R1 and ZK proof that it correctly generated k1");
-    println!("\n   + --- Part 2 generates a random secret k2, point R2 and a ZK proof --- +  \n");
-    let k2 = BigInt::from(rng.gen_range(2..(group_add.len() + 1)));
-    println!("     k2 = {}", &k2);
-    let point_r2 = new_ec.scalar_mul(&points_g[0], &k2);
-    println!("     R2 = {:?}", &point_r2);
-    println!("     => Post R2 and ZK proof that it correctly generated k2");
-    println!("\n   + --- Through DH they secretly share an R point --- + \n");
-    println!(
-        "     Shared secret Parte 1 (R = {:?})",
-        new_ec.scalar_mul(&point_r2, &k1)
-    );
-    println!(
-        "     Shared secret Parte 2 (R = {:?})",
-        new_ec.scalar_mul(&point_r1, &k2)
-    );
 
-    println!("\n ----+------ MPC Signature ----+------");
-    println!("\n   + --- Part 2 operates homomorphically --- + \n");
-    let mut hash_message_p2 = Sha256::new();
-    hash_message_p2.update(message.as_bytes());
-    let hash_result_p2 = hash_message_p2.finalize();
-    let hash_message_p2_to_ec = BigInt::from_bytes_be(
-                        num_bigint::Sign::Plus, 
-                &hash_result_p2) %
-                BigInt::from(2_u32.pow((&group_add.len() + 1) as u32)
-    );
-    println!("    [H(M) = {:?}]", &hash_message_p2_to_ec);
-    let mut rho = paillier_key_p1.public_key.1.clone();
-    while gcd(&rho, &paillier_key_p1.public_key.1) != BigInt::one() {
-        rho = BigInt::from(rng.gen_range(0..(group_add.len() + 1).pow(2)));
-    }
-    let inv_k2 = inv_mod(&k2, &BigInt::from(group_add.len() + 1));
-    let c1 = cipher_paillier(
-        &paillier_key_p1.public_key,
-        &((&rho * &BigInt::from(group_add.len() + 1)
-            + inv_k2.as_ref().unwrap() * &hash_message_p2_to_ec)
-            % BigInt::from(group_add.len() + 1)),
-    );
-    println!(
-        "    c1 = Enc(N + k2^-1 * H(M)) = {:?}",
-        c1.as_ref().unwrap()
-    );
-    let v = (inv_k2.as_ref().unwrap()
-        * &new_ec.scalar_mul(&point_r1, &k2).x
-        * &key_pair_2.as_ref().unwrap().sk)
-        % BigInt::from(group_add.len() + 1);
-    let c2 = ckey
-        .as_ref()
-        .unwrap()
-        .modpow(&v, &paillier_key_p1.public_key.1.pow(2));
-    println!("    c2 = k2^-1 * r * d2 * cKey = {:?}", &c2);
-    let c3 =
-        (c1.as_ref().unwrap() * &c2).modpow(&BigInt::one(), &paillier_key_p1.public_key.1.pow(2));
-    println!("    c3 = c1 + c2 = {:?}", &c3);
-    println!("    => Part 2 send (c3 = {:?}) to Part 1", &c3);
-
-    println!("\n   + --- Part 1 compute firma r y s --- + \n");
-    let mut s = decipher_paillier(
-        &paillier_key_p1.private_key,
-        c3,
-        &paillier_key_p1.public_key,
-    );
-    let r = new_ec.scalar_mul(&point_r2, &k1).x % BigInt::from(group_add.len() + 1);
-    s = (s * inv_mod(&k1, &BigInt::from(group_add.len() + 1)).unwrap())
-        % BigInt::from(group_add.len() + 1);
-    println!(
-        "    => Part 1 post the signature like (r, s): ({:?}, {:?})",
-        &r, &s
-    );
+    loop {
+        println!("\n ----+------ MPC Wallet Configuration ----+------");
+        println!("\n   + --- Part 1 generates a random secret k1, point R1 and a ZK proof --- +  \n");
+        let (k1, point_r1) = random_nonce(params);
+        println!("     k1 = {}", &k1);
+        println!("     R1 = {:?}", &point_r1);
+        println!("     => Post R1 and ZK proof that it correctly generated k1");
+        println!("\n   + --- Part 2 generates a random secret k2, point R2 and a ZK proof --- +  \n");
+        let (k2, point_r2) = random_nonce(params);
+        println!("     k2 = {}", &k2);
+        println!("     R2 = {:?}", &point_r2);
+        println!("     => Post R2 and ZK proof that it correctly generated k2");
+        let nonce_1 = NonceMsg {
+            party: 1,
+            point_r: point_r1.clone(),
+            proof: schnorr::prove(
+                new_ec,
+                &point_g,
+                &n,
+                &k1,
+                &mut proof_transcript(params, Round::Nonce, 1),
+            ),
+        };
+        let nonce_2 = NonceMsg {
+            party: 2,
+            point_r: point_r2.clone(),
+            proof: schnorr::prove(
+                new_ec,
+                &point_g,
+                &n,
+                &k2,
+                &mut proof_transcript(params, Round::Nonce, 2),
+            ),
+        };
+        println!("     Proof of k1 = {:?}", &nonce_1.proof);
+        println!("     Proof of k2 = {:?}", &nonce_2.proof);
+        println!("\n   + --- Both parties commit to (R_i, proof) before revealing anything --- + \n");
+        let (commit_1, reveal_1) = commit_nonce(params, &nonce_1);
+        let (commit_2, reveal_2) = commit_nonce(params, &nonce_2);
+        println!("     Part 1 commitment = {:?}", &commit_1.commitment);
+        println!("     Part 2 commitment = {:?}", &commit_2.commitment);
+        // once both commitments are posted, the openings are exchanged and checked
+        let nonce_2 = open_nonce(params, &commit_2, &reveal_2)?;
+        let nonce_1 = open_nonce(params, &commit_1, &reveal_1)?;
+        println!("     => Both openings and ZK proofs verified");
+        println!("\n   + --- Through DH they secretly share an R point --- + \n");
+        let shared_r = new_ec.scalar_mul_ct(&nonce_2.point_r, &k1);
+        println!("     Shared secret Parte 1 (R = {:?})", &shared_r);
+        println!(
+            "     Shared secret Parte 2 (R = {:?})",
+            new_ec.scalar_mul_ct(&nonce_1.point_r, &k2)
+        );
+        let r = shared_r.x().map_or(BigInt::zero(), |x| x % &n);
+        if r.is_zero() {
+            println!("     r = 0, back to point one");
+            continue;
+        }
 
-    println!(
-        "\n   + --- The MPC wallet verifies the signature ({:?}, {:?}) for the message --- + \n",
-        &r, &s
-    );
-    let mut hash_message_verifier = Sha256::new();
-    hash_message_verifier.update(message.as_bytes());
-    let hash_result_verifier = hash_message_verifier.finalize();
-    let hash_message_verifier_to_ec =
-        BigInt::from_bytes_be(num_bigint::Sign::Plus, &hash_result_verifier)
-            % BigInt::from(2_u32.pow((&group_add.len() + 1) as u32));
-    println!(
-        "    [H({:?}) = {:?}]",
-        &message, &hash_message_verifier_to_ec
-    );
+        println!("\n ----+------ MPC Signature ----+------");
+        println!("\n   + --- Part 2 operates homomorphically --- + \n");
+        let hash_message_p2_to_ec = hash_message(params, message);
+        println!("    [H(M) = {:?}]", &hash_message_p2_to_ec);
+        let mut rho = paillier_key_p1.public_key.1.clone();
+        while gcd(&rho, &paillier_key_p1.public_key.1) != BigInt::one() {
+            rho = rng.gen_bigint_range(&BigInt::zero(), &(&n * &n));
+        }
+        let inv_k2 = inv_mod(&k2, &n);
+        let c1 = match cipher_paillier(
+            &paillier_key_p1.public_key,
+            &((&rho * &n + inv_k2.as_ref().unwrap() * &hash_message_p2_to_ec) % &n),
+        ) {
+            Ok(c1) => c1,
+            Err(_) => {
+                println!("    k2^-1 * H(M) = 0, back to point one");
+                continue;
+            }
+        };
+        println!("    c1 = Enc(N + k2^-1 * H(M)) = {:?}", &c1);
+        let v = (inv_k2.as_ref().unwrap()
+            * new_ec.scalar_mul_ct(&nonce_1.point_r, &k2).x().unwrap()
+            * &key_pair_2.sk)
+            % &n;
+        let c2 = ckey.modpow(&v, &paillier_key_p1.public_key.1.pow(2));
+        println!("    c2 = k2^-1 * r * d2 * cKey = {:?}", &c2);
+        let c3 = (&c1 * &c2).modpow(&BigInt::one(), &paillier_key_p1.public_key.1.pow(2));
+        println!("    c3 = c1 + c2 = {:?}", &c3);
+        println!("    => Part 2 send (c3 = {:?}) to Part 1", &c3);
+        let partial_msg = PartialSignatureMsg { party: 2, c3 };
 
-    let u1 = (hash_message_verifier_to_ec
-        * inv_mod(&s, &BigInt::from(group_add.len() + 1)).unwrap())
-        % BigInt::from(group_add.len() + 1);
-    let u2 = (&r * inv_mod(&s, &BigInt::from(group_add.len() + 1)).unwrap())
-        % BigInt::from(group_add.len() + 1);
-    let x = new_ec
-        .point_add(
-            &new_ec.scalar_mul(&points_g[0], &u1),
-            &new_ec.scalar_mul(&point_g, &u2),
-        )
-        .x;
-    if &r == &(x % BigInt::from(group_add.len() + 1)) {
-        println!("    The signature ({:?}, {:?}) is correct...", &r, &s)
-    } else {
-        println!("    The signature ({:?}, {:?}) is incorrect...", &r, &s)
+        println!("\n   + --- Part 1 compute firma r y s --- + \n");
+        verify_partial_signature(&partial_msg, &paillier_key_p1.public_key)?;
+        let s_prime = decipher_paillier(
+            &paillier_key_p1.private_key,
+            partial_msg.c3.clone(),
+            &paillier_key_p1.public_key,
+        );
+        let s = (&s_prime * inv_mod(&k1, &n).unwrap()) % &n;
+        if s.is_zero() {
+            println!("    s = 0, back to point one");
+            continue;
+        }
+        // Part 1 only publishes a signature it has checked itself; otherwise it opens c3.
+        // c_key was proven to encrypt d1, so an honest c3 always verifies: the failure is
+        // Part 2's (see PartialSignatureOpening)
+        if !verify_ecdsa(params, &point_q, &hash_message_p2_to_ec, &r, &s) {
+            // c3 passed verify_partial_signature, and Part 1 opens every unit mod N^2
+            // under its own key
+            let randomness = recover_randomness_paillier(
+                &paillier_key_p1.private_key,
+                &partial_msg.c3,
+                &s_prime,
+                &paillier_key_p1.public_key,
+            )
+            .expect("Part 1 opens its own ciphertexts");
+            return Err(Abort {
+                culprit: partial_msg.party,
+                round: Round::PartialSignature,
+                check: Check::PartialSignatureOpening,
+                evidence: Box::new(Evidence::PartialSignature(Box::new(PartialSignatureOpening {
+                    c3: partial_msg.c3,
+                    key_share,
+                    key_share_proof: paillier_msg.proof,
+                    s_prime,
+                    randomness,
+                    k1,
+                    point_r1,
+                    point_r2,
+                    point_q,
+                    hash: hash_message_p2_to_ec,
+                }))),
+            });
+        }
+        println!(
+            "    => Part 1 post the signature like (r, s): ({:?}, {:?})",
+            &r, &s
+        );
+
+        println!(
+            "\n   + --- The MPC wallet verifies the signature ({:?}, {:?}) for the message --- + \n",
+            &r, &s
+        );
+        let hash_message_verifier_to_ec = hash_message(params, message);
+        println!(
+            "    [H({:?}) = {:?}]",
+            &message, &hash_message_verifier_to_ec
+        );
+        // the same check Part 1 made before publishing, so it cannot fail here
+        debug_assert!(verify_ecdsa(params, &point_q, &hash_message_verifier_to_ec, &r, &s));
+        println!("    The signature ({:?}, {:?}) is correct...", &r, &s);
+        return Ok((r, s));
     }
 }
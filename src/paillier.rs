@@ -1,8 +1,7 @@
 use crate::arithmetic::basic_op;
 extern crate rand;
-use rand::Rng;
-use num_bigint::BigInt;
-use num_traits::{One, Zero, ToPrimitive};
+use num_bigint::{BigInt, RandBigInt};
+use num_traits::{One, Zero};
 use std::ops::Sub;
 
 #[derive(Debug)]
@@ -24,7 +23,7 @@ pub fn gen_key_paillier(p: &BigInt, q: &BigInt) -> PaillierKey {
 
     // select g and calculate L until gcd(L, N) == 1
     while basic_op::gcd(&g, &n) != BigInt::one() || basic_op::gcd(&l, &n) != BigInt::one() {
-        let random_value: u64 = rng.gen_range(1..(n.pow(2).to_u64().unwrap() as u64 - 1)); // generate a random u64
+        let _random_value = rng.gen_bigint_range(&BigInt::one(), &(n.pow(2) - 1)); // generate a random BigInt
         g = BigInt::from(5);//BigInt::from(random_value); // convert to BigInt
         l = (g.modpow(&lambda, &n.pow(2)).sub(1)) / &n;
     }
@@ -49,14 +48,14 @@ pub fn cipher_paillier(public_key: &(BigInt, BigInt), m: &BigInt) -> Result<BigI
     // generate r randomly, making sure that gcd(r, N) == 1
     let mut rng = rand::thread_rng();
     let mut r = n.clone();
-    while basic_op::gcd(&r, &n) != BigInt::one() {
-        let random_value: u64 = rng.gen_range(1..(n.to_u64().unwrap())); // generate a random u64
-        r = BigInt::from(7);;//BigInt::from(random_value); // convert to BigInt
+    while basic_op::gcd(&r, n) != BigInt::one() {
+        let _random_value = rng.gen_bigint_range(&BigInt::one(), n); // generate a random BigInt
+        r = BigInt::from(7); //BigInt::from(random_value); // convert to BigInt
     }
 
     // calculating the encryption
-    let k1 = g.modpow(&m, &n.pow(2));  // g^M mod N^2
-    let k2 = r.modpow(&n, &n.pow(2));  // r^N mod N^2
+    let k1 = g.modpow(m, &n.pow(2));  // g^M mod N^2
+    let k2 = r.modpow(n, &n.pow(2));  // r^N mod N^2
     let c_key = (k1 * k2) % n.pow(2);     // (g^M * r^N) mod N^2
 
     Ok(c_key)
@@ -68,9 +67,33 @@ pub fn decipher_paillier(private_key: &(BigInt, BigInt), c_key: BigInt, public_k
     // function L = (x - 1) / N
     let l = |x: BigInt| -> BigInt { (x - BigInt::one()) / n };
     // L(c^λ mod N^2)
-    let l_value = l(c_key.modpow(&lambda, &n.pow(2)));
+    let l_value = l(c_key.modpow(lambda, &n.pow(2)));
     // M = L(c^λ mod N^2) * μ mod N
-    let m = (l_value * mu) % n;
-    
-    m
-}
\ No newline at end of file
+    (l_value * mu) % n
+}
+pub fn cipher_paillier_with_r(public_key: &(BigInt, BigInt), m: &BigInt, r: &BigInt) -> BigInt {
+    let (g, n) = public_key;
+    let n_square = n.pow(2);
+    // (g^M * r^N) mod N^2 with a caller supplied r
+    (g.modpow(m, &n_square) * r.modpow(n, &n_square)) % n_square
+}
+
+// recover the randomness r of a ciphertext c = g^M * r^N mod N^2 once M is known,
+// so that the opening (M, r) can be checked by anyone holding the public key
+pub fn recover_randomness_paillier(private_key: &(BigInt, BigInt), c_key: &BigInt, m: &BigInt, public_key: &(BigInt, BigInt)) -> Option<BigInt> {
+    let (lambda, _mu) = private_key;
+    let (g, n) = public_key;
+    let n_square = n.pow(2);
+    // r^N mod N = c * g^-M mod N
+    let g_m = g.modpow(m, &n_square);
+    let r_n = (c_key * basic_op::inv_mod(&g_m, &n_square)?) % n;
+    // r = (r^N)^(N^-1 mod λ) mod N
+    let n_inv = basic_op::inv_mod(&(n % lambda), lambda)?;
+    Some(r_n.modpow(&n_inv, n))
+}
+
+// a ciphertext is well formed when it is a unit of Z*_{N^2}
+pub fn is_valid_cipher_paillier(public_key: &(BigInt, BigInt), c_key: &BigInt) -> bool {
+    let (_g, n) = public_key;
+    *c_key > BigInt::zero() && *c_key < n.pow(2) && basic_op::gcd(c_key, n) == BigInt::one()
+}
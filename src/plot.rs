@@ -0,0 +1,277 @@
+// SVG figures of small curves over F_p, written without external tools: the points of the
+// curve on the p x p grid, the chord-and-tangent construction of a point addition and the
+// orbit of a point. A line of F_p is drawn as the real line through the same points, cut
+// each time it leaves the square and continued from the opposite side
+use crate::arithmetic::basic_op;
+use crate::curve::ecc::{EcWei, Point};
+use num_bigint::{BigInt, Sign};
+use num_traits::{ToPrimitive, Zero};
+use std::fmt::Write;
+
+// the largest p that is plotted: beyond it the points are too dense to tell apart
+pub const PLOT_MAX_P: u64 = 1024;
+
+// side of the square that holds the grid, and the margin around it, in pixels
+const PLOT_SIZE: f64 = 640.0;
+const MARGIN: f64 = 40.0;
+// every grid line is drawn up to this p, only the labelled ones above it
+const FULL_GRID_MAX_P: f64 = 64.0;
+
+const STYLE: &str = "text { font-family: sans-serif; font-size: 11px; fill: #333; }
+.grid { stroke: #bbb; stroke-width: 0.5; stroke-dasharray: 2 2; }
+.frame { fill: none; stroke: #333; }
+.point { fill: crimson; }
+.marked { fill: darkorange; }
+.line { stroke: steelblue; stroke-width: 1.5; }
+.reflection { stroke: #666; stroke-width: 1.5; stroke-dasharray: 5 3; }
+.step { stroke: seagreen; stroke-width: 1; marker-end: url(#arrow); }
+.label { font-weight: bold; }";
+
+// the segments, in grid coordinates, of the real line of slope `slope` through `through`
+// once it is folded into [0, p] x [0, p]. Together they pass through every (x, λx + c mod p)
+// for integer x; the slope is taken in (-p/2, p/2] so that the line wraps as little as possible
+pub fn wrapped_line(p: &BigInt, slope: &BigInt, through: (&BigInt, &BigInt)) -> Vec<((f64, f64), (f64, f64))> {
+    let reduce = |x: BigInt| basic_op::reduce(&x, p);
+    let mut slope = reduce(slope.clone());
+    if &slope * 2 > *p {
+        slope -= p;
+    }
+    let intercept = reduce(through.1 - &slope * through.0);
+    let (p, slope) = (p.to_f64().unwrap(), slope.to_f64().unwrap());
+    let (mut x, mut y) = (0.0, intercept.to_f64().unwrap());
+    let mut segments = Vec::new();
+    while x < p {
+        // where the line leaves the square: through the top, the bottom or the right side
+        let (x_end, y_end, y_next) = if slope > 0.0 && y + slope * (p - x) > p {
+            (x + (p - y) / slope, p, 0.0)
+        } else if slope < 0.0 && y + slope * (p - x) < 0.0 {
+            (x + y / -slope, 0.0, p)
+        } else {
+            (p, y + slope * (p - x), 0.0)
+        };
+        if x_end > x {
+            segments.push(((x, y), (x_end, y_end)));
+        }
+        (x, y) = (x_end, y_next);
+    }
+    segments
+}
+
+// the affine points of the curve by increasing x
+fn affine_points(new_ec: &EcWei) -> Vec<Point> {
+    let p = new_ec.p();
+    let mut points = Vec::new();
+    let mut x = BigInt::zero();
+    while &x < p {
+        let rhs = (&x * &x * &x + new_ec.a() * &x + new_ec.b()) % p;
+        if let Some(y) = basic_op::sqrt_mod(&rhs, p) {
+            points.push(Point::new(x.clone(), y.clone()));
+            if !y.is_zero() {
+                points.push(Point::new(x.clone(), p - y));
+            }
+        }
+        x += 1;
+    }
+    points
+}
+
+fn equation(new_ec: &EcWei) -> String {
+    let term = |c: &BigInt, power: &str| match c.sign() {
+        Sign::NoSign => String::new(),
+        Sign::Minus => format!(" - {}{}", -c, power),
+        Sign::Plus => format!(" + {}{}", c, power),
+    };
+    format!("y² = x³{}{} mod {}", term(new_ec.a(), "x"), term(new_ec.b(), ""), new_ec.p())
+}
+
+fn plotted_p(new_ec: &EcWei) -> Result<u64, String> {
+    match new_ec.p().to_u64() {
+        Some(p) if p <= PLOT_MAX_P => Ok(p),
+        _ => Err(format!("only curves with p <= {} are plotted", PLOT_MAX_P)),
+    }
+}
+
+// an SVG document under construction, with the grid of F_p x F_p already drawn
+struct Canvas {
+    p: f64,
+    cell: f64,
+    body: String,
+}
+
+impl Canvas {
+    fn new(new_ec: &EcWei, caption: &str) -> Result<Self, String> {
+        let p = plotted_p(new_ec)? as f64;
+        let mut canvas = Canvas {
+            p,
+            cell: PLOT_SIZE / p,
+            body: String::new(),
+        };
+        canvas.grid();
+        canvas.text(MARGIN, MARGIN / 2.0, "start", caption);
+        canvas.text(MARGIN + PLOT_SIZE, MARGIN / 2.0, "end", &equation(new_ec));
+        Ok(canvas)
+    }
+
+    // pixel coordinates of a grid position, y growing upwards
+    fn at(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (MARGIN + x * self.cell, MARGIN + (self.p - y) * self.cell)
+    }
+
+    fn coordinates(point: &Point) -> Option<(f64, f64)> {
+        Some((point.x()?.to_f64()?, point.y()?.to_f64()?))
+    }
+
+    fn grid(&mut self) {
+        let step = (self.p / 16.0).ceil();
+        let mut value = 0.0;
+        while value < self.p {
+            let labelled = value % step == 0.0;
+            if labelled || self.p <= FULL_GRID_MAX_P {
+                let (x, y) = self.at((value, value));
+                let (left, bottom) = self.at((0.0, 0.0));
+                let (right, top) = self.at((self.p, self.p));
+                writeln!(self.body, r#"<line class="grid" x1="{x:.2}" y1="{top:.2}" x2="{x:.2}" y2="{bottom:.2}"/>"#).unwrap();
+                writeln!(self.body, r#"<line class="grid" x1="{left:.2}" y1="{y:.2}" x2="{right:.2}" y2="{y:.2}"/>"#).unwrap();
+                if labelled {
+                    self.text(x, bottom + 14.0, "middle", &value.to_string());
+                    self.text(left - 6.0, y + 4.0, "end", &value.to_string());
+                }
+            }
+            value += 1.0;
+        }
+        writeln!(
+            self.body,
+            r#"<rect class="frame" x="{MARGIN}" y="{MARGIN}" width="{PLOT_SIZE}" height="{PLOT_SIZE}"/>"#
+        )
+        .unwrap();
+    }
+
+    fn text(&mut self, x: f64, y: f64, anchor: &str, text: &str) {
+        writeln!(self.body, r#"<text x="{x:.2}" y="{y:.2}" text-anchor="{anchor}">{text}</text>"#).unwrap();
+    }
+
+    fn radius(&self) -> f64 {
+        (self.cell * 0.3).clamp(1.5, 6.0)
+    }
+
+    fn dot(&mut self, point: &Point, class: &str) {
+        if let Some(position) = Self::coordinates(point) {
+            let ((x, y), r) = (self.at(position), self.radius());
+            writeln!(self.body, r#"<circle class="{class}" cx="{x:.2}" cy="{y:.2}" r="{r:.2}"/>"#).unwrap();
+        }
+    }
+
+    fn label(&mut self, point: &Point, text: &str) {
+        if let Some(position) = Self::coordinates(point) {
+            let ((x, y), r) = (self.at(position), self.radius());
+            writeln!(self.body, r#"<text class="label" x="{:.2}" y="{:.2}">{text}</text>"#, x + r + 2.0, y - r - 2.0).unwrap();
+        }
+    }
+
+    fn segment(&mut self, from: (f64, f64), to: (f64, f64), class: &str) {
+        let ((x1, y1), (x2, y2)) = (self.at(from), self.at(to));
+        writeln!(self.body, r#"<line class="{class}" x1="{x1:.2}" y1="{y1:.2}" x2="{x2:.2}" y2="{y2:.2}"/>"#).unwrap();
+    }
+
+    fn finish(self) -> String {
+        let side = PLOT_SIZE + 2.0 * MARGIN;
+        format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{side}" height="{side}" viewBox="0 0 {side} {side}">"#,
+                "\n<style>\n{style}\n</style>\n",
+                r#"<defs><marker id="arrow" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="6" markerHeight="6" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z" fill="seagreen"/></marker></defs>"#,
+                "\n<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n{body}</svg>\n"
+            ),
+            side = side,
+            style = STYLE,
+            body = self.body
+        )
+    }
+}
+
+// every affine point of the curve; ∞ has no place on the grid, so the caption counts it
+pub fn plot_curve(new_ec: &EcWei) -> Result<String, String> {
+    plotted_p(new_ec)?;
+    let points = affine_points(new_ec);
+    let mut canvas = Canvas::new(new_ec, &format!("{} points (∞ not shown)", points.len() + 1))?;
+    for point in &points {
+        canvas.dot(point, "point");
+    }
+    Ok(canvas.finish())
+}
+
+// A + B by chord and tangent: the line through A and B (the tangent when A = B) meets the
+// curve again in -(A + B), and the vertical through it gives A + B. When B = -A the line is
+// vertical and the sum is ∞
+pub fn plot_addition(new_ec: &EcWei, point_a: &Point, point_b: &Point) -> Result<String, String> {
+    let (x1, y1, x2, y2) = match (point_a, point_b) {
+        (Point::Affine(x1, y1), Point::Affine(x2, y2)) if new_ec.is_point(point_a) && new_ec.is_point(point_b) => (x1, y1, x2, y2),
+        _ => return Err("both points must be affine points of the curve".to_string()),
+    };
+    let p = new_ec.p();
+    let sum = new_ec.point_add(point_a, point_b);
+    let mut canvas = Canvas::new(new_ec, &format!("{} + {} = {}", point_a, point_b, sum))?;
+    for point in affine_points(new_ec) {
+        canvas.dot(&point, "point");
+    }
+
+    if sum.is_infinity() {
+        let x = x1.to_f64().unwrap();
+        canvas.segment((x, 0.0), (x, canvas.p), "line");
+    } else {
+        let slope = if x1 != x2 {
+            (y2 - y1) * basic_op::inv_mod(&(x2 - x1), p).unwrap()
+        } else {
+            (BigInt::from(3) * x1 * x1 + new_ec.a()) * basic_op::inv_mod(&(BigInt::from(2) * y1), p).unwrap()
+        };
+        for (from, to) in wrapped_line(p, &slope, (x1, y1)) {
+            canvas.segment(from, to, "line");
+        }
+        let third = new_ec.negate(&sum);
+        let (from, to) = (Canvas::coordinates(&third).unwrap(), Canvas::coordinates(&sum).unwrap());
+        canvas.segment(from, to, "reflection");
+        canvas.dot(&third, "marked");
+        canvas.label(&third, "-(A + B)");
+        canvas.dot(&sum, "marked");
+        canvas.label(&sum, "A + B");
+    }
+    canvas.dot(point_a, "marked");
+    canvas.dot(point_b, "marked");
+    if point_a == point_b {
+        canvas.label(point_a, "A = B");
+    } else {
+        canvas.label(point_a, "A");
+        canvas.label(point_b, "B");
+    }
+    Ok(canvas.finish())
+}
+
+// P, 2P, 3P, ... joined by arrows in order until kP = ∞, each labelled with its multiple
+pub fn plot_orbit(new_ec: &EcWei, point: &Point) -> Result<String, String> {
+    if point.is_infinity() || !new_ec.is_point(point) {
+        return Err("the point must be an affine point of the curve".to_string());
+    }
+    plotted_p(new_ec)?;
+    let mut orbit = vec![point.clone()];
+    loop {
+        let next = new_ec.point_add(orbit.last().unwrap(), point);
+        if next.is_infinity() {
+            break;
+        }
+        orbit.push(next);
+    }
+    let caption = format!("orbit of {}: {}P = ∞", point, orbit.len() + 1);
+    let mut canvas = Canvas::new(new_ec, &caption)?;
+    for other in affine_points(new_ec) {
+        canvas.dot(&other, "point");
+    }
+    for pair in orbit.windows(2) {
+        let (from, to) = (Canvas::coordinates(&pair[0]).unwrap(), Canvas::coordinates(&pair[1]).unwrap());
+        canvas.segment(from, to, "step");
+    }
+    for (k, multiple) in orbit.iter().enumerate() {
+        canvas.dot(multiple, "marked");
+        canvas.label(multiple, &(k + 1).to_string());
+    }
+    Ok(canvas.finish())
+}
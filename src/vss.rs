@@ -0,0 +1,88 @@
+// Feldman's verifiable secret sharing over any group of prime order q: the dealer shares
+// s with a random polynomial f of degree t - 1 over Z_q with f(0) = s, sends f(i) to party
+// i and publishes C_j = g^(a_j) for the coefficients a_j. Party i checks its share with
+// g^f(i) = prod C_j^(i^j), and any t shares give s back by Lagrange interpolation at 0.
+// C_0 = g^s is public, so s is hidden only as well as the discrete log of the group hides it
+use crate::arithmetic::basic_op;
+use crate::group::Group;
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Share {
+    // the party, i >= 1
+    pub index: BigInt,
+    // f(i) mod q
+    pub value: BigInt,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dealing<E> {
+    pub shares: Vec<Share>,
+    // C_j = g^(a_j), C_0 = g^s
+    pub commitments: Vec<E>,
+}
+
+// t-of-parties shares of `secret`
+pub fn deal<G: Group>(group: &G, secret: &BigInt, threshold: usize, parties: usize) -> Result<Dealing<G::Element>, String> {
+    let q = group.order();
+    if threshold == 0 || threshold > parties {
+        return Err(format!("the threshold {} must be in [1, {}]", threshold, parties));
+    }
+    if BigInt::from(parties) >= q {
+        return Err(format!("{} parties need distinct nonzero indices modulo q = {}", parties, q));
+    }
+    let coefficients: Vec<BigInt> = std::iter::once(basic_op::reduce(secret, &q))
+        .chain((1..threshold).map(|_| basic_op::random_scalar(&q)))
+        .collect();
+    let shares = (1..=parties)
+        .map(|i| {
+            let index = BigInt::from(i);
+            // Horner
+            let value = coefficients.iter().rev().fold(BigInt::zero(), |acc, a| (acc * &index + a) % &q);
+            Share { index, value }
+        })
+        .collect();
+    Ok(Dealing {
+        shares,
+        commitments: coefficients.iter().map(|a| group.exp_g(a)).collect(),
+    })
+}
+
+// g^f(i) == prod C_j^(i^j)
+pub fn verify_share<G: Group>(group: &G, commitments: &[G::Element], share: &Share) -> bool {
+    if commitments.is_empty() || commitments.iter().any(|c| !group.is_element(c)) {
+        return false;
+    }
+    let q = group.order();
+    let mut power = BigInt::from(1);
+    let mut expected = group.identity();
+    for commitment in commitments {
+        expected = group.op(&expected, &group.exp(commitment, &power));
+        power = power * &share.index % &q;
+    }
+    group.exp_g(&share.value) == expected
+}
+
+// f(0) = sum f(i) prod j / (j - i) mod q over the given shares, which must have distinct
+// indices; fewer than t shares give an unrelated value
+pub fn reconstruct(q: &BigInt, shares: &[Share]) -> Result<BigInt, String> {
+    if shares.is_empty() {
+        return Err("no shares".to_string());
+    }
+    let mut secret = BigInt::zero();
+    for (k, share) in shares.iter().enumerate() {
+        let mut lagrange = BigInt::from(1);
+        for (l, other) in shares.iter().enumerate() {
+            if k == l {
+                continue;
+            }
+            let denominator = basic_op::inv_mod(&(&other.index - &share.index), q)
+                .filter(|inverse| !inverse.is_zero())
+                .ok_or_else(|| format!("share {} appears twice", share.index))?;
+            lagrange = lagrange * &other.index % q * denominator % q;
+        }
+        secret = (secret + &share.value * lagrange) % q;
+    }
+    Ok((secret + q) % q)
+}
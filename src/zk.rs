@@ -0,0 +1,50 @@
+pub mod bulletproofs;
+pub mod dleq;
+pub mod paillier_dlog;
+pub mod schnorr;
+pub mod sigma;
+pub mod transcript;
+
+use crate::curve::ecc::Point;
+use num_bigint::{BigInt, Sign};
+
+// big-endian encoding of a non-negative integer left padded to len bytes
+pub(crate) fn to_fixed_bytes(value: &BigInt, len: usize) -> Vec<u8> {
+    let (_, bytes) = value.to_bytes_be();
+    let mut out = vec![0u8; len.saturating_sub(bytes.len())];
+    out.extend_from_slice(&bytes);
+    out
+}
+
+pub(crate) fn byte_len(value: &BigInt) -> usize {
+    value.bits().div_ceil(8) as usize
+}
+
+// tag || x || y with coordinates of field_len bytes: tag 4 for an affine point, tag 0 and
+// zero coordinates for the point at infinity
+pub(crate) fn point_to_fixed_bytes(point: &Point, field_len: usize) -> Vec<u8> {
+    match point {
+        Point::Infinity => vec![0u8; 1 + 2 * field_len],
+        Point::Affine(x, y) => {
+            let mut out = vec![4u8];
+            out.extend(to_fixed_bytes(x, field_len));
+            out.extend(to_fixed_bytes(y, field_len));
+            out
+        }
+    }
+}
+
+pub(crate) fn point_from_fixed_bytes(bytes: &[u8], field_len: usize) -> Option<Point> {
+    if bytes.len() != 1 + 2 * field_len {
+        return None;
+    }
+    let (x, y) = bytes[1..].split_at(field_len);
+    match bytes[0] {
+        0 if bytes[1..].iter().all(|byte| *byte == 0) => Some(Point::Infinity),
+        4 => Some(Point::new(
+            BigInt::from_bytes_be(Sign::Plus, x),
+            BigInt::from_bytes_be(Sign::Plus, y),
+        )),
+        _ => None,
+    }
+}
@@ -0,0 +1,516 @@
+// Bulletproofs (Bunz et al., 2018) on an EcWei curve: the logarithmic inner-product
+// argument and range proofs showing that Pedersen commitments V = v * G + gamma * H
+// open to values in [0, 2^bits), for one value or for several values at once.
+//
+// The group must have prime order n with 2^bits < n. On the toy curves this only allows
+// one or two bits, which is enough to follow the algebra; on secp256k1 64-bit ranges are
+// the usual choice.
+use crate::arithmetic::basic_op::{self, reduce};
+use crate::commitment::pedersen::{derive_generator, Pedersen};
+use crate::curve::ecc::{EcWei, Point};
+use crate::zk::transcript::Transcript;
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+
+const DOMAIN_IPA: &[u8] = b"bulletproofs-inner-product";
+const DOMAIN_RANGE: &[u8] = b"bulletproofs-range";
+
+// generators shared by prover and verifier: the Pedersen pair (G, H) for the values and
+// bits * parties vector generators G_i, H_i, all with unknown discrete logs to each other
+pub struct BulletproofGens<'a> {
+    pub pedersen: Pedersen<'a>,
+    pub g_vec: Vec<Point>,
+    pub h_vec: Vec<Point>,
+    pub bits: usize,
+    pub parties: usize,
+}
+
+// log2(len) pairs (L_k, R_k) and the two scalars left after the last folding round
+#[derive(Debug, Clone, PartialEq)]
+pub struct InnerProductProof {
+    pub l_vec: Vec<Point>,
+    pub r_vec: Vec<Point>,
+    pub a: BigInt,
+    pub b: BigInt,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeProof {
+    pub point_a: Point,
+    pub point_s: Point,
+    pub point_t1: Point,
+    pub point_t2: Point,
+    pub tau_x: BigInt,
+    pub mu: BigInt,
+    pub t_hat: BigInt,
+    pub ipp: InnerProductProof,
+}
+
+impl<'a> BulletproofGens<'a> {
+    // `bits` and `parties` must be powers of two; `parties` is the largest number of
+    // values an aggregated proof may cover
+    pub fn new(new_ec: &'a EcWei, point_g: &Point, n: &BigInt, bits: usize, parties: usize) -> Result<Self, String> {
+        if !bits.is_power_of_two() || !parties.is_power_of_two() {
+            return Err("bits and parties must be powers of two.".to_string());
+        }
+        if BigInt::one() << bits >= *n {
+            return Err(format!("2^{} does not fit below the group order {}.", bits, n));
+        }
+        let len = bits * parties;
+        let vector_generator = |prefix: &[u8], i: usize| {
+            let mut label = prefix.to_vec();
+            label.extend_from_slice(&(i as u64).to_be_bytes());
+            derive_generator(new_ec, point_g, n, &label)
+        };
+        Ok(Self {
+            pedersen: Pedersen::new(new_ec, point_g, n),
+            g_vec: (0..len).map(|i| vector_generator(b"bulletproofs/G", i)).collect(),
+            h_vec: (0..len).map(|i| vector_generator(b"bulletproofs/H", i)).collect(),
+            bits,
+            parties,
+        })
+    }
+}
+
+// ----- scalar and point helpers -----
+
+fn inner_product(a: &[BigInt], b: &[BigInt], n: &BigInt) -> BigInt {
+    a.iter().zip(b).fold(BigInt::zero(), |acc, (a_i, b_i)| (acc + a_i * b_i) % n)
+}
+
+// 1, x, x^2, ..., x^(len - 1)
+fn powers(x: &BigInt, len: usize, n: &BigInt) -> Vec<BigInt> {
+    let mut out = Vec::with_capacity(len);
+    let mut current = BigInt::one();
+    for _ in 0..len {
+        out.push(current.clone());
+        current = (current * x) % n;
+    }
+    out
+}
+
+// challenges get inverted, so zero is skipped (it only ever shows up on toy groups)
+fn nonzero_challenge(transcript: &mut Transcript, label: &[u8], n: &BigInt) -> BigInt {
+    loop {
+        let c = transcript.challenge_scalar(label, n);
+        if !c.is_zero() {
+            return c;
+        }
+    }
+}
+
+// sum(k_i * P_i) with the scalars reduced modulo n
+fn multi_exp(new_ec: &EcWei, n: &BigInt, terms: &[(BigInt, &Point)]) -> Point {
+    let terms: Vec<(Point, BigInt)> = terms.iter().map(|(k, point)| ((*point).clone(), reduce(k, n))).collect();
+    new_ec.multi_scalar_mul(&terms)
+}
+
+// <a, G> + <b, H>
+fn vector_commit(new_ec: &EcWei, n: &BigInt, a: &[BigInt], g_vec: &[Point], b: &[BigInt], h_vec: &[Point]) -> Point {
+    let terms: Vec<(BigInt, &Point)> = a
+        .iter()
+        .cloned()
+        .zip(g_vec)
+        .chain(b.iter().cloned().zip(h_vec))
+        .collect();
+    multi_exp(new_ec, n, &terms)
+}
+
+// ----- inner-product argument -----
+
+// proves knowledge of a, b with P = <a, G> + <b, H> + <a, b> * Q, where the vectors have a
+// power-of-two length; each round halves them and sends the cross terms L and R
+pub fn prove_inner_product(
+    new_ec: &EcWei,
+    n: &BigInt,
+    point_q: &Point,
+    generators: (Vec<Point>, Vec<Point>),
+    witness: (Vec<BigInt>, Vec<BigInt>),
+    transcript: &mut Transcript,
+) -> InnerProductProof {
+    let (mut g_vec, mut h_vec) = generators;
+    let (mut a, mut b) = witness;
+    transcript.append_message(b"dom-sep", DOMAIN_IPA);
+    transcript.append_u64(b"len", a.len() as u64);
+
+    let mut l_vec = Vec::new();
+    let mut r_vec = Vec::new();
+    while a.len() > 1 {
+        let half = a.len() / 2;
+        let (a_lo, a_hi) = a.split_at(half);
+        let (b_lo, b_hi) = b.split_at(half);
+        let (g_lo, g_hi) = g_vec.split_at(half);
+        let (h_lo, h_hi) = h_vec.split_at(half);
+
+        // L = <a_lo, G_hi> + <b_hi, H_lo> + <a_lo, b_hi> * Q, R symmetrically
+        let c_l = inner_product(a_lo, b_hi, n);
+        let c_r = inner_product(a_hi, b_lo, n);
+        let point_l = new_ec.point_add(
+            &vector_commit(new_ec, n, a_lo, g_hi, b_hi, h_lo),
+            &new_ec.scalar_mul(point_q, &c_l),
+        );
+        let point_r = new_ec.point_add(
+            &vector_commit(new_ec, n, a_hi, g_lo, b_lo, h_hi),
+            &new_ec.scalar_mul(point_q, &c_r),
+        );
+        transcript.append_points(b"L, R", &[&point_l, &point_r]);
+        let u = nonzero_challenge(transcript, b"u", n);
+        let u_inv = basic_op::inv_mod(&u, n).unwrap();
+
+        // a' = u * a_lo + u^-1 * a_hi, b' = u^-1 * b_lo + u * b_hi
+        // G' = u^-1 * G_lo + u * G_hi, H' = u * H_lo + u^-1 * H_hi
+        let fold_scalars = |lo: &[BigInt], hi: &[BigInt], x: &BigInt, y: &BigInt| -> Vec<BigInt> {
+            lo.iter().zip(hi).map(|(l, h)| (l * x + h * y) % n).collect()
+        };
+        let fold_points = |lo: &[Point], hi: &[Point], x: &BigInt, y: &BigInt| -> Vec<Point> {
+            lo.iter()
+                .zip(hi)
+                .map(|(l, h)| multi_exp(new_ec, n, &[(x.clone(), l), (y.clone(), h)]))
+                .collect()
+        };
+        let next_a = fold_scalars(a_lo, a_hi, &u, &u_inv);
+        let next_b = fold_scalars(b_lo, b_hi, &u_inv, &u);
+        let next_g = fold_points(g_lo, g_hi, &u_inv, &u);
+        let next_h = fold_points(h_lo, h_hi, &u, &u_inv);
+        a = next_a;
+        b = next_b;
+        g_vec = next_g;
+        h_vec = next_h;
+
+        l_vec.push(point_l);
+        r_vec.push(point_r);
+    }
+
+    InnerProductProof {
+        l_vec,
+        r_vec,
+        a: a[0].clone(),
+        b: b[0].clone(),
+    }
+}
+
+// the round challenges u_k and the coefficients s_i such that the folded generators are
+// sum(s_i * G_i) and sum(s_i^-1 * H_i); replays the transcript of `prove_inner_product`
+struct IpaScalars {
+    u_sq: Vec<BigInt>,
+    u_inv_sq: Vec<BigInt>,
+    s: Vec<BigInt>,
+    s_inv: Vec<BigInt>,
+}
+
+fn inner_product_scalars(
+    new_ec: &EcWei,
+    n: &BigInt,
+    len: usize,
+    proof: &InnerProductProof,
+    transcript: &mut Transcript,
+) -> Option<IpaScalars> {
+    let rounds = proof.l_vec.len();
+    if !len.is_power_of_two() || len != 1 << rounds || proof.r_vec.len() != rounds {
+        return None;
+    }
+    let points_ok = proof
+        .l_vec
+        .iter()
+        .chain(&proof.r_vec)
+        .all(|point| new_ec.is_point(point));
+    if !points_ok {
+        return None;
+    }
+
+    transcript.append_message(b"dom-sep", DOMAIN_IPA);
+    transcript.append_u64(b"len", len as u64);
+    let mut u = Vec::with_capacity(rounds);
+    let mut u_inv = Vec::with_capacity(rounds);
+    for (point_l, point_r) in proof.l_vec.iter().zip(&proof.r_vec) {
+        transcript.append_points(b"L, R", &[point_l, point_r]);
+        let u_k = nonzero_challenge(transcript, b"u", n);
+        u_inv.push(basic_op::inv_mod(&u_k, n).unwrap());
+        u.push(u_k);
+    }
+
+    // round k folds on bit (rounds - 1 - k) of the index: u_k if it is set, u_k^-1 if not
+    let mut s = Vec::with_capacity(len);
+    for i in 0..len {
+        let mut s_i = BigInt::one();
+        for k in 0..rounds {
+            let bit = (i >> (rounds - 1 - k)) & 1 == 1;
+            s_i = (s_i * if bit { &u[k] } else { &u_inv[k] }) % n;
+        }
+        s.push(s_i);
+    }
+    let s_inv = s.iter().rev().cloned().collect();
+
+    Some(IpaScalars {
+        u_sq: u.iter().map(|u_k| (u_k * u_k) % n).collect(),
+        u_inv_sq: u_inv.iter().map(|u_k| (u_k * u_k) % n).collect(),
+        s,
+        s_inv,
+    })
+}
+
+// checks P + sum(u_k^2 * L_k + u_k^-2 * R_k) = a * sum(s_i * G_i) + b * sum(s_i^-1 * H_i) + a * b * Q
+// as a single multi-exponentiation
+pub fn verify_inner_product(
+    new_ec: &EcWei,
+    n: &BigInt,
+    point_q: &Point,
+    generators: (&[Point], &[Point]),
+    point_p: &Point,
+    proof: &InnerProductProof,
+    transcript: &mut Transcript,
+) -> bool {
+    let (g_vec, h_vec) = generators;
+    if g_vec.len() != h_vec.len() || !new_ec.is_point(point_p) {
+        return false;
+    }
+    let scalars = match inner_product_scalars(new_ec, n, g_vec.len(), proof, transcript) {
+        Some(scalars) => scalars,
+        None => return false,
+    };
+
+    let mut terms: Vec<(BigInt, &Point)> = vec![(BigInt::one(), point_p), (-(&proof.a * &proof.b), point_q)];
+    for (k, (point_l, point_r)) in proof.l_vec.iter().zip(&proof.r_vec).enumerate() {
+        terms.push((scalars.u_sq[k].clone(), point_l));
+        terms.push((scalars.u_inv_sq[k].clone(), point_r));
+    }
+    for (i, (g_i, h_i)) in g_vec.iter().zip(h_vec).enumerate() {
+        terms.push((-(&proof.a * &scalars.s[i]), g_i));
+        terms.push((-(&proof.b * &scalars.s_inv[i]), h_i));
+    }
+    multi_exp(new_ec, n, &terms).is_infinity()
+}
+
+// ----- range proofs -----
+
+fn append_range_statement(transcript: &mut Transcript, bits: usize, commitments: &[Point]) {
+    transcript.append_message(b"dom-sep", DOMAIN_RANGE);
+    transcript.append_u64(b"bits", bits as u64);
+    transcript.append_points(b"V", &commitments.iter().collect::<Vec<_>>());
+}
+
+// z^(2 + j) * 2^(i mod bits) for the block j that position i belongs to
+fn zeta(z: &BigInt, bits: usize, m: usize, n: &BigInt) -> Vec<BigInt> {
+    let two_powers = powers(&BigInt::from(2), bits, n);
+    let z_powers = powers(z, m + 2, n);
+    (0..bits * m)
+        .map(|i| (&z_powers[2 + i / bits] * &two_powers[i % bits]) % n)
+        .collect()
+}
+
+// delta(y, z) = (z - z^2) * <1, y^(bits * m)> - sum_j z^(3 + j) * <1, 2^bits>
+fn delta(y: &BigInt, z: &BigInt, bits: usize, m: usize, n: &BigInt) -> BigInt {
+    let sum_y = powers(y, bits * m, n).iter().fold(BigInt::zero(), |acc, y_i| (acc + y_i) % n);
+    let sum_two = (BigInt::one() << bits) - 1;
+    let z_powers = powers(z, m + 3, n);
+    let mut delta = (z - &z_powers[2]) * sum_y;
+    for z_j in &z_powers[3..] {
+        delta -= z_j * &sum_two;
+    }
+    reduce(&delta, n)
+}
+
+// single value: V = v * G + blinding * H with v in [0, 2^bits)
+pub fn prove_range(
+    gens: &BulletproofGens,
+    value: &BigInt,
+    blinding: &BigInt,
+    transcript: &mut Transcript,
+) -> Result<(RangeProof, Point), String> {
+    let (proof, mut commitments) =
+        prove_range_aggregated(gens, std::slice::from_ref(value), std::slice::from_ref(blinding), transcript)?;
+    Ok((proof, commitments.remove(0)))
+}
+
+pub fn verify_range(gens: &BulletproofGens, proof: &RangeProof, commitment: &Point, transcript: &mut Transcript) -> bool {
+    verify_range_aggregated(gens, proof, std::slice::from_ref(commitment), transcript)
+}
+
+// m values (a power of two, at most gens.parties) in one proof of the size of a single
+// one plus 2 * log2(m) points
+pub fn prove_range_aggregated(
+    gens: &BulletproofGens,
+    values: &[BigInt],
+    blindings: &[BigInt],
+    transcript: &mut Transcript,
+) -> Result<(RangeProof, Vec<Point>), String> {
+    let new_ec = gens.pedersen.new_ec;
+    let n = &gens.pedersen.n;
+    let (bits, m) = (gens.bits, values.len());
+    if !m.is_power_of_two() || m > gens.parties {
+        return Err(format!("the number of values must be a power of two up to {}.", gens.parties));
+    }
+    if blindings.len() != m {
+        return Err("one blinding factor is needed per value.".to_string());
+    }
+    let bound = BigInt::one() << bits;
+    if values.iter().any(|v| v < &BigInt::zero() || v >= &bound) {
+        return Err(format!("the values must lie in [0, 2^{}).", bits));
+    }
+    let len = bits * m;
+    let (g_vec, h_vec) = (&gens.g_vec[..len], &gens.h_vec[..len]);
+
+    let commitments: Vec<Point> = values
+        .iter()
+        .zip(blindings)
+        .map(|(v, gamma)| gens.pedersen.commit(v, gamma))
+        .collect();
+    append_range_statement(transcript, bits, &commitments);
+
+    // a_L holds the bits of every value, a_R = a_L - 1
+    let a_l: Vec<BigInt> = values
+        .iter()
+        .flat_map(|v| (0..bits as u64).map(move |i| BigInt::from(v.bit(i) as u8)))
+        .collect();
+    let a_r: Vec<BigInt> = a_l.iter().map(|bit| reduce(&(bit - 1), n)).collect();
+
+    let alpha = basic_op::random_scalar(n);
+    let point_a = new_ec.point_add(
+        &vector_commit(new_ec, n, &a_l, g_vec, &a_r, h_vec),
+        &new_ec.scalar_mul_ct(&gens.pedersen.point_h, &alpha),
+    );
+    let s_l: Vec<BigInt> = (0..len).map(|_| basic_op::random_scalar(n)).collect();
+    let s_r: Vec<BigInt> = (0..len).map(|_| basic_op::random_scalar(n)).collect();
+    let rho = basic_op::random_scalar(n);
+    let point_s = new_ec.point_add(
+        &vector_commit(new_ec, n, &s_l, g_vec, &s_r, h_vec),
+        &new_ec.scalar_mul_ct(&gens.pedersen.point_h, &rho),
+    );
+    transcript.append_points(b"A, S", &[&point_a, &point_s]);
+    let y = nonzero_challenge(transcript, b"y", n);
+    let z = nonzero_challenge(transcript, b"z", n);
+
+    // l(X) = (a_L - z) + s_L * X
+    // r(X) = y^i o (a_R + z + s_R * X) + zeta
+    let y_powers = powers(&y, len, n);
+    let zeta = zeta(&z, bits, m, n);
+    let l0: Vec<BigInt> = a_l.iter().map(|a_i| reduce(&(a_i - &z), n)).collect();
+    let l1 = s_l;
+    let r0: Vec<BigInt> = (0..len)
+        .map(|i| (&y_powers[i] * (&a_r[i] + &z) + &zeta[i]) % n)
+        .collect();
+    let r1: Vec<BigInt> = (0..len).map(|i| (&y_powers[i] * &s_r[i]) % n).collect();
+
+    // t(X) = <l(X), r(X)> = t0 + t1 * X + t2 * X^2
+    let t1 = (inner_product(&l0, &r1, n) + inner_product(&l1, &r0, n)) % n;
+    let t2 = inner_product(&l1, &r1, n);
+    let tau_1 = basic_op::random_scalar(n);
+    let tau_2 = basic_op::random_scalar(n);
+    let point_t1 = gens.pedersen.commit(&t1, &tau_1);
+    let point_t2 = gens.pedersen.commit(&t2, &tau_2);
+    transcript.append_points(b"T1, T2", &[&point_t1, &point_t2]);
+    let x = nonzero_challenge(transcript, b"x", n);
+
+    let l: Vec<BigInt> = l0.iter().zip(&l1).map(|(a, b)| (a + b * &x) % n).collect();
+    let r: Vec<BigInt> = r0.iter().zip(&r1).map(|(a, b)| (a + b * &x) % n).collect();
+    let t_hat = inner_product(&l, &r, n);
+    let z_powers = powers(&z, m + 2, n);
+    let blinded = blindings
+        .iter()
+        .zip(&z_powers[2..])
+        .fold(BigInt::zero(), |acc, (gamma, z_j)| acc + z_j * gamma);
+    let tau_x = reduce(&(&tau_2 * &x * &x + &tau_1 * &x + blinded), n);
+    let mu = (&alpha + &rho * &x) % n;
+    transcript.append_scalar(b"tau_x", &tau_x);
+    transcript.append_scalar(b"mu", &mu);
+    transcript.append_scalar(b"t_hat", &t_hat);
+
+    // the inner-product argument runs on H'_i = y^-i * H_i with Q = w * G
+    let w = nonzero_challenge(transcript, b"w", n);
+    let point_q = new_ec.scalar_mul(&gens.pedersen.point_g, &w);
+    let y_inv = basic_op::inv_mod(&y, n).unwrap();
+    let h_prime: Vec<Point> = h_vec
+        .iter()
+        .zip(powers(&y_inv, len, n))
+        .map(|(h_i, y_inv_i)| new_ec.scalar_mul(h_i, &y_inv_i))
+        .collect();
+    let ipp = prove_inner_product(new_ec, n, &point_q, (g_vec.to_vec(), h_prime), (l, r), transcript);
+
+    Ok((
+        RangeProof {
+            point_a,
+            point_s,
+            point_t1,
+            point_t2,
+            tau_x,
+            mu,
+            t_hat,
+            ipp,
+        },
+        commitments,
+    ))
+}
+
+// both verification equations,
+//   t_hat * G + tau_x * H = sum(z^(2 + j) * V_j) + delta(y, z) * G + x * T1 + x^2 * T2
+//   A + x * S - z * <1, G> + <z + zeta o y^-i, H> - mu * H + t_hat * Q = <l, G> + <r, H'>
+// with the second one checked through the inner-product argument, are merged with a
+// random weight c into a single multi-exponentiation over every point involved
+pub fn verify_range_aggregated(
+    gens: &BulletproofGens,
+    proof: &RangeProof,
+    commitments: &[Point],
+    transcript: &mut Transcript,
+) -> bool {
+    let new_ec = gens.pedersen.new_ec;
+    let n = &gens.pedersen.n;
+    let (bits, m) = (gens.bits, commitments.len());
+    if !m.is_power_of_two() || m > gens.parties {
+        return false;
+    }
+    let points_ok = commitments
+        .iter()
+        .chain([&proof.point_a, &proof.point_s, &proof.point_t1, &proof.point_t2])
+        .all(|point| new_ec.is_point(point));
+    if !points_ok {
+        return false;
+    }
+    let len = bits * m;
+    let (g_vec, h_vec) = (&gens.g_vec[..len], &gens.h_vec[..len]);
+
+    append_range_statement(transcript, bits, commitments);
+    transcript.append_points(b"A, S", &[&proof.point_a, &proof.point_s]);
+    let y = nonzero_challenge(transcript, b"y", n);
+    let z = nonzero_challenge(transcript, b"z", n);
+    transcript.append_points(b"T1, T2", &[&proof.point_t1, &proof.point_t2]);
+    let x = nonzero_challenge(transcript, b"x", n);
+    transcript.append_scalar(b"tau_x", &proof.tau_x);
+    transcript.append_scalar(b"mu", &proof.mu);
+    transcript.append_scalar(b"t_hat", &proof.t_hat);
+    let w = nonzero_challenge(transcript, b"w", n);
+    let ipa = match inner_product_scalars(new_ec, n, len, &proof.ipp, transcript) {
+        Some(ipa) => ipa,
+        None => return false,
+    };
+    // the weight is local to the verifier, so it does not need to come from the transcript
+    let c = basic_op::random_scalar(n);
+
+    let y_inv = basic_op::inv_mod(&y, n).unwrap();
+    let y_inv_powers = powers(&y_inv, len, n);
+    let zeta = zeta(&z, bits, m, n);
+    let z_powers = powers(&z, m + 2, n);
+    let (a, b) = (&proof.ipp.a, &proof.ipp.b);
+
+    let coeff_g = &w * (&proof.t_hat - a * b) + &c * (&proof.t_hat - delta(&y, &z, bits, m, n));
+    let coeff_h = &c * &proof.tau_x - &proof.mu;
+    let mut terms: Vec<(BigInt, &Point)> = vec![
+        (coeff_g, &gens.pedersen.point_g),
+        (coeff_h, &gens.pedersen.point_h),
+        (BigInt::one(), &proof.point_a),
+        (x.clone(), &proof.point_s),
+        (-(&c * &x), &proof.point_t1),
+        (-(&c * &x * &x), &proof.point_t2),
+    ];
+    for (commitment, z_j) in commitments.iter().zip(&z_powers[2..]) {
+        terms.push((-(&c * z_j), commitment));
+    }
+    for (k, (point_l, point_r)) in proof.ipp.l_vec.iter().zip(&proof.ipp.r_vec).enumerate() {
+        terms.push((ipa.u_sq[k].clone(), point_l));
+        terms.push((ipa.u_inv_sq[k].clone(), point_r));
+    }
+    for i in 0..len {
+        terms.push((-(&z + a * &ipa.s[i]), &g_vec[i]));
+        terms.push((&z + (&zeta[i] - b * &ipa.s_inv[i]) * &y_inv_powers[i], &h_vec[i]));
+    }
+    multi_exp(new_ec, n, &terms).is_infinity()
+}
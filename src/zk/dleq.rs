@@ -0,0 +1,320 @@
+// Chaum-Pedersen proof that log_G(X) = log_H(Y) on an EcWei curve,
+// where G and H are points of the same prime order n.
+use crate::arithmetic::basic_op;
+use crate::curve::ecc::{EcWei, Point};
+use crate::zk::sigma::SigmaProtocol;
+use crate::zk::transcript::Transcript;
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+const DOMAIN: &[u8] = b"chaum-pedersen-dleq";
+const DOMAIN_BATCH: &[u8] = b"chaum-pedersen-dleq-batch";
+const DOMAIN_OR: &[u8] = b"chaum-pedersen-dleq-or";
+
+// X = x * G and Y = x * H
+#[derive(Debug, Clone, PartialEq)]
+pub struct DleqStatement {
+    pub point_g: Point,
+    pub point_h: Point,
+    pub point_x: Point,
+    pub point_y: Point,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DleqProof {
+    pub challenge: BigInt,
+    pub response: BigInt,
+}
+
+// one branch per statement of the OR proof; the challenges add up to the hash
+#[derive(Debug, Clone, PartialEq)]
+pub struct DleqOrProof {
+    pub challenges: Vec<BigInt>,
+    pub responses: Vec<BigInt>,
+}
+
+impl DleqStatement {
+    pub fn new(new_ec: &EcWei, point_g: &Point, point_h: &Point, secret: &BigInt) -> Self {
+        Self {
+            point_g: point_g.clone(),
+            point_h: point_h.clone(),
+            point_x: new_ec.scalar_mul_ct(point_g, secret),
+            point_y: new_ec.scalar_mul_ct(point_h, secret),
+        }
+    }
+
+    fn is_valid(&self, new_ec: &EcWei) -> bool {
+        [&self.point_g, &self.point_h, &self.point_x, &self.point_y]
+            .iter()
+            .all(|point| new_ec.is_point(point))
+    }
+
+    // A1 = z * G - c * X and A2 = z * H - c * Y, the commitments a valid proof must have used
+    fn commitments(&self, new_ec: &EcWei, n: &BigInt, c: &BigInt, z: &BigInt) -> (Point, Point) {
+        let minus_c = (n - c % n) % n;
+        let a1 = new_ec.point_add(
+            &new_ec.scalar_mul(&self.point_g, z),
+            &new_ec.scalar_mul(&self.point_x, &minus_c),
+        );
+        let a2 = new_ec.point_add(
+            &new_ec.scalar_mul(&self.point_h, z),
+            &new_ec.scalar_mul(&self.point_y, &minus_c),
+        );
+        (a1, a2)
+    }
+
+    fn points(&self) -> [&Point; 4] {
+        [&self.point_g, &self.point_h, &self.point_x, &self.point_y]
+    }
+
+    fn append_to(&self, transcript: &mut Transcript) {
+        transcript.append_points(b"G, H, X, Y", &self.points());
+    }
+}
+
+fn fiat_shamir(
+    transcript: &mut Transcript,
+    domain: &[u8],
+    statements: &[&DleqStatement],
+    commitments: &[(Point, Point)],
+    n: &BigInt,
+) -> BigInt {
+    transcript.append_message(b"dom-sep", domain);
+    for statement in statements {
+        statement.append_to(transcript);
+    }
+    for (a1, a2) in commitments {
+        transcript.append_points(b"A1, A2", &[a1, a2]);
+    }
+    transcript.challenge_scalar(b"c", n)
+}
+
+pub fn prove(
+    new_ec: &EcWei,
+    n: &BigInt,
+    statement: &DleqStatement,
+    secret: &BigInt,
+    transcript: &mut Transcript,
+) -> DleqProof {
+    let nonce = basic_op::random_scalar(n);
+    let a1 = new_ec.scalar_mul_ct(&statement.point_g, &nonce);
+    let a2 = new_ec.scalar_mul_ct(&statement.point_h, &nonce);
+    let c = fiat_shamir(transcript, DOMAIN, &[statement], &[(a1, a2)], n);
+    DleqProof {
+        response: (nonce + &c * secret) % n,
+        challenge: c,
+    }
+}
+
+pub fn verify(
+    new_ec: &EcWei,
+    n: &BigInt,
+    statement: &DleqStatement,
+    proof: &DleqProof,
+    transcript: &mut Transcript,
+) -> bool {
+    if !statement.is_valid(new_ec) {
+        return false;
+    }
+    let commitment = statement.commitments(new_ec, n, &proof.challenge, &proof.response);
+    fiat_shamir(transcript, DOMAIN, &[statement], &[commitment], n) == proof.challenge
+}
+
+// ----- batched variant: one secret x for many pairs (H_i, Y_i = x * H_i) -----
+
+// folds the pairs into H = sum(d_i * H_i), Y = sum(d_i * Y_i) with weights d_i drawn from
+// a fork of the transcript that has absorbed every pair
+fn fold_batch(
+    new_ec: &EcWei,
+    n: &BigInt,
+    point_g: &Point,
+    point_x: &Point,
+    pairs: &[(Point, Point)],
+    transcript: &Transcript,
+) -> DleqStatement {
+    let mut weights = transcript.fork(DOMAIN_BATCH);
+    weights.append_points(b"G, X", &[point_g, point_x]);
+    for (point_h, point_y) in pairs {
+        weights.append_points(b"H_i, Y_i", &[point_h, point_y]);
+    }
+    let mut terms_h = Vec::with_capacity(pairs.len());
+    let mut terms_y = Vec::with_capacity(pairs.len());
+    for (h_i, y_i) in pairs {
+        let d_i = weights.challenge_scalar(b"d_i", n);
+        terms_h.push((h_i.clone(), d_i.clone()));
+        terms_y.push((y_i.clone(), d_i));
+    }
+    DleqStatement {
+        point_g: point_g.clone(),
+        point_h: new_ec.multi_scalar_mul(&terms_h),
+        point_x: point_x.clone(),
+        point_y: new_ec.multi_scalar_mul(&terms_y),
+    }
+}
+
+// proves log_G(X) = log_{H_i}(Y_i) for every pair with a single proof
+pub fn prove_batch(
+    new_ec: &EcWei,
+    n: &BigInt,
+    point_g: &Point,
+    pairs: &[(Point, Point)],
+    secret: &BigInt,
+    transcript: &mut Transcript,
+) -> DleqProof {
+    let point_x = new_ec.scalar_mul_ct(point_g, secret);
+    let statement = fold_batch(new_ec, n, point_g, &point_x, pairs, transcript);
+    let nonce = basic_op::random_scalar(n);
+    let a1 = new_ec.scalar_mul_ct(&statement.point_g, &nonce);
+    let a2 = new_ec.scalar_mul_ct(&statement.point_h, &nonce);
+    let c = fiat_shamir(transcript, DOMAIN_BATCH, &[&statement], &[(a1, a2)], n);
+    DleqProof {
+        response: (nonce + &c * secret) % n,
+        challenge: c,
+    }
+}
+
+pub fn verify_batch(
+    new_ec: &EcWei,
+    n: &BigInt,
+    point_g: &Point,
+    point_x: &Point,
+    pairs: &[(Point, Point)],
+    proof: &DleqProof,
+    transcript: &mut Transcript,
+) -> bool {
+    let all_on_curve = pairs
+        .iter()
+        .all(|(h_i, y_i)| new_ec.is_point(h_i) && new_ec.is_point(y_i));
+    if pairs.is_empty() || !all_on_curve || !new_ec.is_point(point_g) || !new_ec.is_point(point_x) {
+        return false;
+    }
+    let statement = fold_batch(new_ec, n, point_g, point_x, pairs, transcript);
+    let commitment = statement.commitments(new_ec, n, &proof.challenge, &proof.response);
+    fiat_shamir(transcript, DOMAIN_BATCH, &[&statement], &[commitment], n) == proof.challenge
+}
+
+// ----- OR composition (Cramer-Damgard-Schoenmakers) -----
+
+// proves that one of the statements holds, `index` being the one whose secret is known;
+// the other branches are simulated with challenges chosen in advance
+pub fn prove_or(
+    new_ec: &EcWei,
+    n: &BigInt,
+    statements: &[DleqStatement],
+    index: usize,
+    secret: &BigInt,
+    transcript: &mut Transcript,
+) -> DleqOrProof {
+    let mut challenges = vec![BigInt::zero(); statements.len()];
+    let mut responses = vec![BigInt::zero(); statements.len()];
+    let mut commitments = Vec::with_capacity(statements.len());
+    let nonce = basic_op::random_scalar(n);
+    for (i, statement) in statements.iter().enumerate() {
+        if i == index {
+            commitments.push((
+                new_ec.scalar_mul_ct(&statement.point_g, &nonce),
+                new_ec.scalar_mul_ct(&statement.point_h, &nonce),
+            ));
+        } else {
+            challenges[i] = basic_op::random_mod(n);
+            responses[i] = basic_op::random_mod(n);
+            commitments.push(statement.commitments(new_ec, n, &challenges[i], &responses[i]));
+        }
+    }
+    let refs: Vec<&DleqStatement> = statements.iter().collect();
+    let c = fiat_shamir(transcript, DOMAIN_OR, &refs, &commitments, n);
+    let simulated = challenges.iter().fold(BigInt::zero(), |acc, c_i| acc + c_i);
+    challenges[index] = basic_op::reduce(&(c - simulated), n);
+    responses[index] = (nonce + &challenges[index] * secret) % n;
+    DleqOrProof {
+        challenges,
+        responses,
+    }
+}
+
+pub fn verify_or(
+    new_ec: &EcWei,
+    n: &BigInt,
+    statements: &[DleqStatement],
+    proof: &DleqOrProof,
+    transcript: &mut Transcript,
+) -> bool {
+    if statements.is_empty()
+        || proof.challenges.len() != statements.len()
+        || proof.responses.len() != statements.len()
+        || !statements.iter().all(|st| st.is_valid(new_ec))
+    {
+        return false;
+    }
+    let commitments: Vec<(Point, Point)> = statements
+        .iter()
+        .zip(proof.challenges.iter().zip(proof.responses.iter()))
+        .map(|(st, (c_i, z_i))| st.commitments(new_ec, n, c_i, z_i))
+        .collect();
+    let refs: Vec<&DleqStatement> = statements.iter().collect();
+    let c = fiat_shamir(transcript, DOMAIN_OR, &refs, &commitments, n);
+    let sum = proof.challenges.iter().fold(BigInt::zero(), |acc, c_i| acc + c_i) % n;
+    sum == c
+}
+
+// the same protocol behind the generic sigma-protocol interface
+pub struct DleqSigma<'a> {
+    pub new_ec: &'a EcWei,
+    pub n: BigInt,
+}
+
+impl<'a> DleqSigma<'a> {
+    pub fn new(new_ec: &'a EcWei, n: &BigInt) -> Self {
+        Self {
+            new_ec,
+            n: n.clone(),
+        }
+    }
+}
+
+impl SigmaProtocol for DleqSigma<'_> {
+    type Statement = DleqStatement;
+    type Witness = BigInt;
+    type Commitment = (Point, Point);
+    type Challenge = BigInt;
+    type Response = BigInt;
+    type ProverState = BigInt;
+
+    fn commit(&self, statement: &DleqStatement, _witness: &BigInt) -> (BigInt, (Point, Point)) {
+        let nonce = basic_op::random_scalar(&self.n);
+        let a1 = self.new_ec.scalar_mul_ct(&statement.point_g, &nonce);
+        let a2 = self.new_ec.scalar_mul_ct(&statement.point_h, &nonce);
+        (nonce, (a1, a2))
+    }
+
+    fn random_challenge(&self) -> BigInt {
+        basic_op::random_mod(&self.n)
+    }
+
+    fn respond(&self, _statement: &DleqStatement, witness: &BigInt, state: BigInt, challenge: &BigInt) -> BigInt {
+        (state + challenge * witness) % &self.n
+    }
+
+    fn verify(&self, statement: &DleqStatement, commitment: &(Point, Point), challenge: &BigInt, response: &BigInt) -> bool {
+        statement.is_valid(self.new_ec)
+            && &statement.commitments(self.new_ec, &self.n, challenge, response) == commitment
+    }
+
+    fn simulate(&self, statement: &DleqStatement, challenge: &BigInt) -> ((Point, Point), BigInt) {
+        let response = basic_op::random_mod(&self.n);
+        (statement.commitments(self.new_ec, &self.n, challenge, &response), response)
+    }
+
+    fn append_statement(&self, transcript: &mut Transcript, statement: &DleqStatement) {
+        transcript.append_message(b"dom-sep", DOMAIN);
+        statement.append_to(transcript);
+    }
+
+    fn append_commitment(&self, transcript: &mut Transcript, commitment: &(Point, Point)) {
+        transcript.append_points(b"A1, A2", &[&commitment.0, &commitment.1]);
+    }
+
+    fn challenge(&self, transcript: &mut Transcript) -> BigInt {
+        transcript.challenge_scalar(b"c", &self.n)
+    }
+}
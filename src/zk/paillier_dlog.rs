@@ -0,0 +1,119 @@
+// proof that a Paillier ciphertext c = g^x r^N mod N^2 encrypts the discrete logarithm x
+// of X = x * G. The response z1 = alpha + e x is sent over the integers, not mod n, so the
+// same z1 opens both the ciphertext and the point; checking z1 < 2 n^2 2^SLACK_BITS also
+// bounds the plaintext (with slack), which the wallet needs so that c_key^v never wraps
+// modulo N
+use crate::arithmetic::basic_op;
+use crate::curve::ecc::{EcWei, Point};
+use crate::paillier::is_valid_cipher_paillier;
+use crate::zk::transcript::Transcript;
+use num_bigint::{BigInt, RandBigInt};
+use num_traits::{One, Zero};
+
+const DOMAIN: &[u8] = b"paillier-dlog";
+
+// statistical hiding of x in z1 = alpha + e x
+pub const SLACK_BITS: u64 = 80;
+
+// c_key = Enc(x) under public_key = (g, N) and X = x * G
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaillierDlogStatement {
+    pub public_key: (BigInt, BigInt),
+    pub c_key: BigInt,
+    pub point_x: Point,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaillierDlogProof {
+    // A = g^alpha beta^N mod N^2
+    pub ciphertext_commitment: BigInt,
+    // B = alpha * G
+    pub point_commitment: Point,
+    // z1 = alpha + e x over the integers
+    pub response: BigInt,
+    // z2 = beta r^e mod N
+    pub randomness_response: BigInt,
+}
+
+// alpha is drawn below n^2 2^SLACK_BITS, and e x < n^2, so an honest z1 stays below twice that
+pub fn response_bound(n: &BigInt) -> BigInt {
+    (n * n) << (SLACK_BITS + 1)
+}
+
+fn fiat_shamir(
+    transcript: &mut Transcript,
+    point_g: &Point,
+    n: &BigInt,
+    statement: &PaillierDlogStatement,
+    commitments: (&BigInt, &Point),
+) -> BigInt {
+    transcript.append_message(b"dom-sep", DOMAIN);
+    transcript.append_points(b"G, X", &[point_g, &statement.point_x]);
+    transcript.append_ciphertext(b"c", &statement.public_key, &statement.c_key);
+    transcript.append_ciphertext(b"A", &statement.public_key, commitments.0);
+    transcript.append_point(b"B", commitments.1);
+    transcript.challenge_scalar(b"e", n)
+}
+
+// `secret` in [0, n) and `randomness` are the plaintext and the randomness of c_key
+pub fn prove(
+    new_ec: &EcWei,
+    point_g: &Point,
+    n: &BigInt,
+    statement: &PaillierDlogStatement,
+    secret: &BigInt,
+    randomness: &BigInt,
+    transcript: &mut Transcript,
+) -> PaillierDlogProof {
+    let (g, modulus) = &statement.public_key;
+    let n_square = modulus * modulus;
+    let mut rng = rand::thread_rng();
+    let alpha = rng.gen_bigint_range(&BigInt::zero(), &((n * n) << SLACK_BITS));
+    let beta = loop {
+        let beta = rng.gen_bigint_range(&BigInt::one(), modulus);
+        if basic_op::gcd(&beta, modulus).is_one() {
+            break beta;
+        }
+    };
+    let ciphertext_commitment = (g.modpow(&alpha, &n_square) * beta.modpow(modulus, &n_square)) % &n_square;
+    let point_commitment = new_ec.scalar_mul_ct(point_g, &(&alpha % n));
+    let e = fiat_shamir(transcript, point_g, n, statement, (&ciphertext_commitment, &point_commitment));
+    PaillierDlogProof {
+        ciphertext_commitment,
+        point_commitment,
+        response: alpha + &e * secret,
+        randomness_response: (beta * randomness.modpow(&e, modulus)) % modulus,
+    }
+}
+
+// g^z1 z2^N = A c^e mod N^2 and z1 * G = B + e * X, with z1 in [0, response_bound(n))
+pub fn verify(
+    new_ec: &EcWei,
+    point_g: &Point,
+    n: &BigInt,
+    statement: &PaillierDlogStatement,
+    proof: &PaillierDlogProof,
+    transcript: &mut Transcript,
+) -> bool {
+    let PaillierDlogStatement { public_key, c_key, point_x } = statement;
+    let (g, modulus) = public_key;
+    let n_square = modulus * modulus;
+    let (z1, z2) = (&proof.response, &proof.randomness_response);
+    if !new_ec.is_point(point_x)
+        || !new_ec.is_point(&proof.point_commitment)
+        || !is_valid_cipher_paillier(public_key, c_key)
+        || !is_valid_cipher_paillier(public_key, &proof.ciphertext_commitment)
+        || z1 < &BigInt::zero()
+        || z1 >= &response_bound(n)
+        || z2 <= &BigInt::zero()
+        || z2 >= modulus
+    {
+        return false;
+    }
+    let e = fiat_shamir(transcript, point_g, n, statement, (&proof.ciphertext_commitment, &proof.point_commitment));
+    let lhs = (g.modpow(z1, &n_square) * z2.modpow(modulus, &n_square)) % &n_square;
+    let rhs = (&proof.ciphertext_commitment * c_key.modpow(&e, &n_square)) % &n_square;
+    let point_lhs = new_ec.scalar_mul(point_g, &(z1 % n));
+    let point_rhs = new_ec.point_add(&proof.point_commitment, &new_ec.scalar_mul(point_x, &e));
+    lhs == rhs && point_lhs == point_rhs
+}
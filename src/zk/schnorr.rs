@@ -0,0 +1,228 @@
+// Schnorr proof of knowledge of x such that X = x * G on any EcWei curve,
+// where G is a point of prime order n. The `_in` functions prove X = g^x in any group.
+use crate::arithmetic::basic_op;
+use crate::curve::ecc::{EcWei, Point};
+use crate::group::{EcGroup, Group};
+use crate::zk::sigma::SigmaProtocol;
+use crate::zk::transcript::Transcript;
+use crate::zk::{byte_len, point_from_fixed_bytes, point_to_fixed_bytes, to_fixed_bytes};
+use num_bigint::{BigInt, Sign};
+use num_traits::Zero;
+
+const DOMAIN: &[u8] = b"schnorr-dlog";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchnorrProof<E = Point> {
+    // A = r * G
+    pub commitment: E,
+    // z = r + c * x mod n
+    pub response: BigInt,
+}
+
+// ----- interactive form: commit, challenge, response -----
+
+// prover first move: a secret nonce r and the commitment A = r * G
+pub fn commit(new_ec: &EcWei, point_g: &Point, n: &BigInt) -> (BigInt, Point) {
+    let nonce = basic_op::random_scalar(n);
+    let commitment = new_ec.scalar_mul_ct(point_g, &nonce);
+    (nonce, commitment)
+}
+
+// verifier move: a random challenge c in [0, n)
+pub fn random_challenge(n: &BigInt) -> BigInt {
+    basic_op::random_mod(n)
+}
+
+// prover last move: z = r + c * x mod n
+pub fn respond(n: &BigInt, secret: &BigInt, nonce: &BigInt, challenge: &BigInt) -> BigInt {
+    (nonce + challenge * secret) % n
+}
+
+// verifier check: z * G == A + c * X
+pub fn verify_transcript(
+    new_ec: &EcWei,
+    point_g: &Point,
+    point_x: &Point,
+    commitment: &Point,
+    challenge: &BigInt,
+    response: &BigInt,
+) -> bool {
+    let lhs = new_ec.scalar_mul(point_g, response);
+    let rhs = new_ec.point_add(commitment, &new_ec.scalar_mul(point_x, challenge));
+    lhs == rhs
+}
+
+// ----- non-interactive form (Fiat-Shamir) -----
+
+// c = H(transcript || G || X || A) mod n
+fn fiat_shamir<G: Group>(transcript: &mut Transcript, group: &G, point_x: &G::Element, commitment: &G::Element) -> BigInt {
+    transcript.append_message(b"dom-sep", DOMAIN);
+    group.append_element(transcript, b"G", &group.generator());
+    group.append_element(transcript, b"X", point_x);
+    group.append_element(transcript, b"A", commitment);
+    transcript.challenge_scalar(b"c", &group.order())
+}
+
+// prove knowledge of `secret` for X = secret * G, binding the proof to everything
+// already appended to `transcript`
+pub fn prove(new_ec: &EcWei, point_g: &Point, n: &BigInt, secret: &BigInt, transcript: &mut Transcript) -> SchnorrProof {
+    prove_in(&EcGroup::new(new_ec, point_g, n), secret, transcript)
+}
+
+pub fn verify(
+    new_ec: &EcWei,
+    point_g: &Point,
+    n: &BigInt,
+    point_x: &Point,
+    proof: &SchnorrProof,
+    transcript: &mut Transcript,
+) -> bool {
+    verify_in(&EcGroup::new(new_ec, point_g, n), point_x, proof, transcript)
+}
+
+// checks sum(w_i * A_i + w_i * c_i * X_i) - sum(w_i * z_i) * G == ∞ with random weights w_i,
+// a single multi-scalar multiplication instead of one equation per proof; every proof was
+// made on a copy of `transcript`
+pub fn batch_verify(
+    new_ec: &EcWei,
+    point_g: &Point,
+    n: &BigInt,
+    statements: &[(Point, SchnorrProof)],
+    transcript: &Transcript,
+) -> bool {
+    let group = EcGroup::new(new_ec, point_g, n);
+    let mut sum_z = BigInt::zero();
+    let mut terms = Vec::with_capacity(2 * statements.len() + 1);
+    for (point_x, proof) in statements {
+        if !new_ec.is_point(point_x) || !new_ec.is_point(&proof.commitment) {
+            return false;
+        }
+        let c = fiat_shamir(&mut transcript.clone(), &group, point_x, &proof.commitment);
+        let weight = basic_op::random_scalar(n);
+        sum_z = (sum_z + &weight * &proof.response) % n;
+        terms.push((point_x.clone(), (&weight * c) % n));
+        terms.push((proof.commitment.clone(), weight));
+    }
+    terms.push((point_g.clone(), -sum_z));
+    new_ec.multi_scalar_mul(&terms).is_infinity()
+}
+
+// ----- any group -----
+
+// X = g^secret and a proof of knowledge of secret, as `prove`
+pub fn prove_in<G: Group>(group: &G, secret: &BigInt, transcript: &mut Transcript) -> SchnorrProof<G::Element> {
+    let n = group.order();
+    let point_x = group.exp_g(secret);
+    let nonce = basic_op::random_scalar(&n);
+    let commitment = group.exp_g(&nonce);
+    let c = fiat_shamir(transcript, group, &point_x, &commitment);
+    SchnorrProof {
+        commitment,
+        response: respond(&n, secret, &nonce, &c),
+    }
+}
+
+// g^z == A X^c, for X and A in the group
+pub fn verify_in<G: Group>(group: &G, point_x: &G::Element, proof: &SchnorrProof<G::Element>, transcript: &mut Transcript) -> bool {
+    if !group.is_element(point_x) || !group.is_element(&proof.commitment) {
+        return false;
+    }
+    let c = fiat_shamir(transcript, group, point_x, &proof.commitment);
+    group.exp_g(&proof.response) == group.op(&proof.commitment, &group.exp(point_x, &c))
+}
+
+impl SchnorrProof {
+    // fixed width encoding A || z, sized from the field and the group order
+    pub fn to_bytes(&self, new_ec: &EcWei, n: &BigInt) -> Vec<u8> {
+        let field_len = byte_len(new_ec.p());
+        let mut out = point_to_fixed_bytes(&self.commitment, field_len);
+        out.extend(to_fixed_bytes(&self.response, byte_len(n)));
+        out
+    }
+
+    pub fn from_bytes(new_ec: &EcWei, n: &BigInt, bytes: &[u8]) -> Option<SchnorrProof> {
+        let field_len = byte_len(new_ec.p());
+        let point_len = 1 + 2 * field_len;
+        if bytes.len() != point_len + byte_len(n) {
+            return None;
+        }
+        let commitment = point_from_fixed_bytes(&bytes[..point_len], field_len)?;
+        let response = BigInt::from_bytes_be(Sign::Plus, &bytes[point_len..]);
+        if !new_ec.is_point(&commitment) || &response >= n {
+            return None;
+        }
+        Some(SchnorrProof {
+            commitment,
+            response,
+        })
+    }
+}
+
+// the same protocol behind the generic sigma-protocol interface
+pub struct SchnorrSigma<'a> {
+    pub new_ec: &'a EcWei,
+    pub point_g: Point,
+    pub n: BigInt,
+}
+
+impl<'a> SchnorrSigma<'a> {
+    pub fn new(new_ec: &'a EcWei, point_g: &Point, n: &BigInt) -> Self {
+        Self {
+            new_ec,
+            point_g: point_g.clone(),
+            n: n.clone(),
+        }
+    }
+}
+
+impl SigmaProtocol for SchnorrSigma<'_> {
+    type Statement = Point;
+    type Witness = BigInt;
+    type Commitment = Point;
+    type Challenge = BigInt;
+    type Response = BigInt;
+    type ProverState = BigInt;
+
+    fn commit(&self, _statement: &Point, _witness: &BigInt) -> (BigInt, Point) {
+        commit(self.new_ec, &self.point_g, &self.n)
+    }
+
+    fn random_challenge(&self) -> BigInt {
+        random_challenge(&self.n)
+    }
+
+    fn respond(&self, _statement: &Point, witness: &BigInt, state: BigInt, challenge: &BigInt) -> BigInt {
+        respond(&self.n, witness, &state, challenge)
+    }
+
+    fn verify(&self, statement: &Point, commitment: &Point, challenge: &BigInt, response: &BigInt) -> bool {
+        self.new_ec.is_point(statement)
+            && self.new_ec.is_point(commitment)
+            && verify_transcript(self.new_ec, &self.point_g, statement, commitment, challenge, response)
+    }
+
+    // A = z * G - c * X for a random z
+    fn simulate(&self, statement: &Point, challenge: &BigInt) -> (Point, BigInt) {
+        let response = basic_op::random_mod(&self.n);
+        let minus_c = (&self.n - challenge % &self.n) % &self.n;
+        let commitment = self.new_ec.point_add(
+            &self.new_ec.scalar_mul(&self.point_g, &response),
+            &self.new_ec.scalar_mul(statement, &minus_c),
+        );
+        (commitment, response)
+    }
+
+    fn append_statement(&self, transcript: &mut Transcript, statement: &Point) {
+        transcript.append_message(b"dom-sep", DOMAIN);
+        transcript.append_point(b"G", &self.point_g);
+        transcript.append_point(b"X", statement);
+    }
+
+    fn append_commitment(&self, transcript: &mut Transcript, commitment: &Point) {
+        transcript.append_point(b"A", commitment);
+    }
+
+    fn challenge(&self, transcript: &mut Transcript) -> BigInt {
+        transcript.challenge_scalar(b"c", &self.n)
+    }
+}
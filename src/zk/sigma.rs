@@ -0,0 +1,334 @@
+// Generic three-move (commit, challenge, response) protocols, their Fiat-Shamir
+// compilation and the AND / OR combinators.
+use crate::arithmetic::basic_op;
+use crate::zk::transcript::Transcript;
+use num_bigint::BigInt;
+
+const DOMAIN_AND: &[u8] = b"sigma-and";
+const DOMAIN_OR: &[u8] = b"sigma-or";
+
+pub trait SigmaProtocol {
+    type Statement;
+    type Witness;
+    type Commitment: Clone;
+    type Challenge: Clone + PartialEq;
+    type Response: Clone;
+    // secret randomness kept by the prover between commit and respond
+    type ProverState;
+
+    // prover first move
+    fn commit(&self, statement: &Self::Statement, witness: &Self::Witness) -> (Self::ProverState, Self::Commitment);
+
+    // verifier move
+    fn random_challenge(&self) -> Self::Challenge;
+
+    // prover last move
+    fn respond(
+        &self,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: Self::ProverState,
+        challenge: &Self::Challenge,
+    ) -> Self::Response;
+
+    fn verify(
+        &self,
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> bool;
+
+    // honest-verifier zero-knowledge simulator: an accepting transcript for a given
+    // challenge, produced without the witness
+    fn simulate(&self, statement: &Self::Statement, challenge: &Self::Challenge) -> (Self::Commitment, Self::Response);
+
+    // what the Fiat-Shamir transform binds, and how it draws the challenge
+    fn append_statement(&self, transcript: &mut Transcript, statement: &Self::Statement);
+    fn append_commitment(&self, transcript: &mut Transcript, commitment: &Self::Commitment);
+    fn challenge(&self, transcript: &mut Transcript) -> Self::Challenge;
+}
+
+// non-interactive proof obtained through Fiat-Shamir
+#[derive(Debug, Clone, PartialEq)]
+pub struct NizkProof<C, Z> {
+    pub commitment: C,
+    pub response: Z,
+}
+
+fn fiat_shamir<P: SigmaProtocol>(
+    protocol: &P,
+    statement: &P::Statement,
+    commitment: &P::Commitment,
+    transcript: &mut Transcript,
+) -> P::Challenge {
+    protocol.append_statement(transcript, statement);
+    protocol.append_commitment(transcript, commitment);
+    protocol.challenge(transcript)
+}
+
+// Fiat-Shamir compilation: the challenge is drawn from the transcript once the statement
+// and the commitment have been appended to it
+pub fn prove<P: SigmaProtocol>(
+    protocol: &P,
+    statement: &P::Statement,
+    witness: &P::Witness,
+    transcript: &mut Transcript,
+) -> NizkProof<P::Commitment, P::Response> {
+    let (state, commitment) = protocol.commit(statement, witness);
+    let challenge = fiat_shamir(protocol, statement, &commitment, transcript);
+    let response = protocol.respond(statement, witness, state, &challenge);
+    NizkProof {
+        commitment,
+        response,
+    }
+}
+
+pub fn verify<P: SigmaProtocol>(
+    protocol: &P,
+    statement: &P::Statement,
+    proof: &NizkProof<P::Commitment, P::Response>,
+    transcript: &mut Transcript,
+) -> bool {
+    let challenge = fiat_shamir(protocol, statement, &proof.commitment, transcript);
+    protocol.verify(statement, &proof.commitment, &challenge, &proof.response)
+}
+
+// ----- AND: both statements hold, proven with a single shared challenge -----
+
+pub struct And<P, Q> {
+    pub left: P,
+    pub right: Q,
+}
+
+impl<P, Q> And<P, Q> {
+    pub fn new(left: P, right: Q) -> Self {
+        Self { left, right }
+    }
+}
+
+impl<P, Q> SigmaProtocol for And<P, Q>
+where
+    P: SigmaProtocol,
+    Q: SigmaProtocol<Challenge = P::Challenge>,
+{
+    type Statement = (P::Statement, Q::Statement);
+    type Witness = (P::Witness, Q::Witness);
+    type Commitment = (P::Commitment, Q::Commitment);
+    type Challenge = P::Challenge;
+    type Response = (P::Response, Q::Response);
+    type ProverState = (P::ProverState, Q::ProverState);
+
+    fn commit(&self, statement: &Self::Statement, witness: &Self::Witness) -> (Self::ProverState, Self::Commitment) {
+        let (state_l, commitment_l) = self.left.commit(&statement.0, &witness.0);
+        let (state_r, commitment_r) = self.right.commit(&statement.1, &witness.1);
+        ((state_l, state_r), (commitment_l, commitment_r))
+    }
+
+    fn random_challenge(&self) -> Self::Challenge {
+        self.left.random_challenge()
+    }
+
+    fn respond(
+        &self,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: Self::ProverState,
+        challenge: &Self::Challenge,
+    ) -> Self::Response {
+        (
+            self.left.respond(&statement.0, &witness.0, state.0, challenge),
+            self.right.respond(&statement.1, &witness.1, state.1, challenge),
+        )
+    }
+
+    fn verify(
+        &self,
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> bool {
+        self.left.verify(&statement.0, &commitment.0, challenge, &response.0)
+            && self.right.verify(&statement.1, &commitment.1, challenge, &response.1)
+    }
+
+    fn simulate(&self, statement: &Self::Statement, challenge: &Self::Challenge) -> (Self::Commitment, Self::Response) {
+        let (commitment_l, response_l) = self.left.simulate(&statement.0, challenge);
+        let (commitment_r, response_r) = self.right.simulate(&statement.1, challenge);
+        ((commitment_l, commitment_r), (response_l, response_r))
+    }
+
+    fn append_statement(&self, transcript: &mut Transcript, statement: &Self::Statement) {
+        transcript.append_message(b"dom-sep", DOMAIN_AND);
+        self.left.append_statement(transcript, &statement.0);
+        self.right.append_statement(transcript, &statement.1);
+    }
+
+    fn append_commitment(&self, transcript: &mut Transcript, commitment: &Self::Commitment) {
+        self.left.append_commitment(transcript, &commitment.0);
+        self.right.append_commitment(transcript, &commitment.1);
+    }
+
+    fn challenge(&self, transcript: &mut Transcript) -> Self::Challenge {
+        self.left.challenge(transcript)
+    }
+}
+
+// ----- OR (Cramer-Damgard-Schoenmakers): one of the statements holds -----
+
+// both branches must draw their challenges from Z_n for the same n
+pub struct Or<P, Q> {
+    pub left: P,
+    pub right: Q,
+    pub n: BigInt,
+}
+
+impl<P, Q> Or<P, Q> {
+    pub fn new(left: P, right: Q, n: &BigInt) -> Self {
+        Self {
+            left,
+            right,
+            n: n.clone(),
+        }
+    }
+}
+
+// the prover knows the witness of one branch only
+pub enum OrWitness<WL, WR> {
+    Left(WL),
+    Right(WR),
+}
+
+// state of the real branch, plus the challenge and response of the simulated one
+pub enum OrState<SL, SR, ZL, ZR> {
+    Left(SL, BigInt, ZR),
+    Right(SR, BigInt, ZL),
+}
+
+// the split of the challenge c = c_left + c_right mod n and both responses
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrResponse<ZL, ZR> {
+    pub challenge_left: BigInt,
+    pub response_left: ZL,
+    pub response_right: ZR,
+}
+
+impl<P, Q> Or<P, Q> {
+    fn other_half(&self, challenge: &BigInt, half: &BigInt) -> BigInt {
+        basic_op::reduce(&(challenge - half), &self.n)
+    }
+}
+
+impl<P, Q> SigmaProtocol for Or<P, Q>
+where
+    P: SigmaProtocol<Challenge = BigInt>,
+    Q: SigmaProtocol<Challenge = BigInt>,
+{
+    type Statement = (P::Statement, Q::Statement);
+    type Witness = OrWitness<P::Witness, Q::Witness>;
+    type Commitment = (P::Commitment, Q::Commitment);
+    type Challenge = BigInt;
+    type Response = OrResponse<P::Response, Q::Response>;
+    type ProverState = OrState<P::ProverState, Q::ProverState, P::Response, Q::Response>;
+
+    fn commit(&self, statement: &Self::Statement, witness: &Self::Witness) -> (Self::ProverState, Self::Commitment) {
+        match witness {
+            OrWitness::Left(w) => {
+                let (state, commitment_l) = self.left.commit(&statement.0, w);
+                let challenge_r = self.right.random_challenge();
+                let (commitment_r, response_r) = self.right.simulate(&statement.1, &challenge_r);
+                (
+                    OrState::Left(state, challenge_r, response_r),
+                    (commitment_l, commitment_r),
+                )
+            }
+            OrWitness::Right(w) => {
+                let (state, commitment_r) = self.right.commit(&statement.1, w);
+                let challenge_l = self.left.random_challenge();
+                let (commitment_l, response_l) = self.left.simulate(&statement.0, &challenge_l);
+                (
+                    OrState::Right(state, challenge_l, response_l),
+                    (commitment_l, commitment_r),
+                )
+            }
+        }
+    }
+
+    fn random_challenge(&self) -> BigInt {
+        basic_op::random_mod(&self.n)
+    }
+
+    fn respond(
+        &self,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: Self::ProverState,
+        challenge: &BigInt,
+    ) -> Self::Response {
+        match (witness, state) {
+            (OrWitness::Left(w), OrState::Left(state, challenge_r, response_r)) => {
+                let challenge_l = self.other_half(challenge, &challenge_r);
+                OrResponse {
+                    response_left: self.left.respond(&statement.0, w, state, &challenge_l),
+                    challenge_left: challenge_l,
+                    response_right: response_r,
+                }
+            }
+            (OrWitness::Right(w), OrState::Right(state, challenge_l, response_l)) => {
+                let challenge_r = self.other_half(challenge, &challenge_l);
+                OrResponse {
+                    challenge_left: challenge_l,
+                    response_left: response_l,
+                    response_right: self.right.respond(&statement.1, w, state, &challenge_r),
+                }
+            }
+            _ => panic!("the prover state does not match the witness branch"),
+        }
+    }
+
+    fn verify(
+        &self,
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &BigInt,
+        response: &Self::Response,
+    ) -> bool {
+        let challenge_r = self.other_half(challenge, &response.challenge_left);
+        self.left
+            .verify(&statement.0, &commitment.0, &response.challenge_left, &response.response_left)
+            && self
+                .right
+                .verify(&statement.1, &commitment.1, &challenge_r, &response.response_right)
+    }
+
+    fn simulate(&self, statement: &Self::Statement, challenge: &BigInt) -> (Self::Commitment, Self::Response) {
+        let challenge_l = self.left.random_challenge();
+        let challenge_r = self.other_half(challenge, &challenge_l);
+        let (commitment_l, response_l) = self.left.simulate(&statement.0, &challenge_l);
+        let (commitment_r, response_r) = self.right.simulate(&statement.1, &challenge_r);
+        (
+            (commitment_l, commitment_r),
+            OrResponse {
+                challenge_left: challenge_l,
+                response_left: response_l,
+                response_right: response_r,
+            },
+        )
+    }
+
+    fn append_statement(&self, transcript: &mut Transcript, statement: &Self::Statement) {
+        transcript.append_message(b"dom-sep", DOMAIN_OR);
+        self.left.append_statement(transcript, &statement.0);
+        self.right.append_statement(transcript, &statement.1);
+    }
+
+    fn append_commitment(&self, transcript: &mut Transcript, commitment: &Self::Commitment) {
+        self.left.append_commitment(transcript, &commitment.0);
+        self.right.append_commitment(transcript, &commitment.1);
+    }
+
+    fn challenge(&self, transcript: &mut Transcript) -> BigInt {
+        transcript.challenge_scalar(b"c", &self.n)
+    }
+}
@@ -0,0 +1,119 @@
+// Merlin-style Fiat-Shamir transcript built on SHA-256.
+//
+// Every message is absorbed together with a label and its length, so two
+// transcripts only agree when the same labeled messages were appended in the
+// same order. Challenges are squeezed from the running state and then fed back
+// into it, so later challenges depend on earlier ones.
+use crate::curve::ecc::Point;
+use crate::zk::{byte_len, to_fixed_bytes};
+use num_bigint::{BigInt, Sign};
+use sha2::{Digest, Sha256};
+
+const PROTOCOL_LABEL: &[u8] = b"mpc-zk-paillier/transcript/v1";
+
+#[derive(Clone)]
+pub struct Transcript {
+    state: Sha256,
+}
+
+impl Transcript {
+    // a fresh transcript for the protocol named by `label`
+    pub fn new(label: &[u8]) -> Self {
+        let mut transcript = Self {
+            state: Sha256::new(),
+        };
+        transcript.append_message(b"protocol", PROTOCOL_LABEL);
+        transcript.append_message(b"dom-sep", label);
+        transcript
+    }
+
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        self.state.update((label.len() as u64).to_be_bytes());
+        self.state.update(label);
+        self.state.update((message.len() as u64).to_be_bytes());
+        self.state.update(message);
+    }
+
+    pub fn append_u64(&mut self, label: &[u8], value: u64) {
+        self.append_message(label, &value.to_be_bytes());
+    }
+
+    // the sign is absorbed too, so x and -x never collide
+    pub fn append_scalar(&mut self, label: &[u8], scalar: &BigInt) {
+        let (sign, bytes) = scalar.to_bytes_be();
+        let mut message = vec![(sign == Sign::Minus) as u8];
+        message.extend_from_slice(&bytes);
+        self.append_message(label, &message);
+    }
+
+    // a one-byte tag keeps the point at infinity apart from every affine point
+    pub fn append_point(&mut self, label: &[u8], point: &Point) {
+        let mut message = Vec::new();
+        match point {
+            Point::Infinity => message.push(0u8),
+            Point::Affine(x, y) => {
+                message.push(4u8);
+                for coordinate in [x, y] {
+                    let bytes = coordinate.to_bytes_be().1;
+                    message.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+                    message.extend_from_slice(&bytes);
+                }
+            }
+        }
+        self.append_message(label, &message);
+    }
+
+    pub fn append_points(&mut self, label: &[u8], points: &[&Point]) {
+        self.append_u64(label, points.len() as u64);
+        for point in points {
+            self.append_point(label, point);
+        }
+    }
+
+    // a Paillier ciphertext together with the modulus it lives under, encoded with the
+    // fixed width of N^2
+    pub fn append_ciphertext(&mut self, label: &[u8], public_key: &(BigInt, BigInt), c_key: &BigInt) {
+        let (g, n) = public_key;
+        let width = byte_len(&n.pow(2));
+        let mut message = to_fixed_bytes(g, width);
+        message.extend(to_fixed_bytes(n, width));
+        message.extend(to_fixed_bytes(c_key, width));
+        self.append_message(label, &message);
+    }
+
+    // fills `dest` with challenge bytes and ratchets the state with them
+    pub fn challenge_bytes(&mut self, label: &[u8], dest: &mut [u8]) {
+        self.append_u64(label, dest.len() as u64);
+        for (counter, chunk) in dest.chunks_mut(32).enumerate() {
+            let mut block = self.state.clone();
+            block.update((counter as u64).to_be_bytes());
+            chunk.copy_from_slice(&block.finalize()[..chunk.len()]);
+        }
+        self.append_message(b"challenge", dest);
+    }
+
+    // uniform scalar in [0, n): candidates of bits(n) bits are drawn until one is below n,
+    // so no residue is more likely than another
+    pub fn challenge_scalar(&mut self, label: &[u8], n: &BigInt) -> BigInt {
+        let bits = n.bits();
+        let mut bytes = vec![0u8; bits.div_ceil(8) as usize];
+        let excess = (bytes.len() as u64) * 8 - bits;
+        loop {
+            self.challenge_bytes(label, &mut bytes);
+            if excess > 0 {
+                bytes[0] &= 0xff >> excess;
+            }
+            let candidate = BigInt::from_bytes_be(Sign::Plus, &bytes);
+            if &candidate < n {
+                return candidate;
+            }
+        }
+    }
+
+    // independent copy for a sub-proof; it inherits everything appended so far
+    pub fn fork(&self, label: &[u8]) -> Transcript {
+        let mut forked = self.clone();
+        forked.append_message(b"fork", label);
+        forked
+    }
+}
@@ -0,0 +1,128 @@
+use mpc_zk_paillier::arithmetic::basic_op;
+use mpc_zk_paillier::curve::ecc::CurveParams;
+use mpc_zk_paillier::zk::bulletproofs::{self, BulletproofGens, RangeProof};
+use mpc_zk_paillier::zk::transcript::Transcript;
+use num_bigint::BigInt;
+
+const BITS: usize = 8;
+
+fn transcript() -> Transcript {
+    Transcript::new(b"bulletproofs-test")
+}
+
+// every way of changing one field of a valid proof
+fn tampered(gens: &BulletproofGens, proof: &RangeProof) -> Vec<RangeProof> {
+    let new_ec = gens.pedersen.new_ec;
+    let point_g = &gens.pedersen.point_g;
+    let mut out = Vec::new();
+    let mut add = |change: &dyn Fn(&mut RangeProof)| {
+        let mut forged = proof.clone();
+        change(&mut forged);
+        out.push(forged);
+    };
+    add(&|p| p.point_a = new_ec.point_add(&p.point_a, point_g));
+    add(&|p| p.point_s = new_ec.point_add(&p.point_s, point_g));
+    add(&|p| p.point_t1 = new_ec.point_add(&p.point_t1, point_g));
+    add(&|p| p.point_t2 = new_ec.point_add(&p.point_t2, point_g));
+    add(&|p| p.tau_x += 1);
+    add(&|p| p.mu += 1);
+    add(&|p| p.t_hat += 1);
+    add(&|p| p.ipp.a += 1);
+    add(&|p| p.ipp.b += 1);
+    add(&|p| p.ipp.l_vec[0] = new_ec.point_add(&p.ipp.l_vec[0], point_g));
+    add(&|p| p.ipp.r_vec.swap(0, 1));
+    add(&|p| {
+        p.ipp.l_vec.pop();
+    });
+    out
+}
+
+#[test]
+fn single_range_proofs_verify() {
+    let params = CurveParams::secp256k1();
+    let gens = BulletproofGens::new(&params.curve, &params.point_g, &params.n, BITS, 1).unwrap();
+    for value in [0, 1, 77, 255] {
+        let blinding = basic_op::random_scalar(&params.n);
+        let (proof, commitment) = bulletproofs::prove_range(&gens, &BigInt::from(value), &blinding, &mut transcript()).unwrap();
+        assert_eq!(commitment, gens.pedersen.commit(&BigInt::from(value), &blinding));
+        assert!(bulletproofs::verify_range(&gens, &proof, &commitment, &mut transcript()));
+        assert!(!bulletproofs::verify_range(&gens, &proof, &commitment, &mut Transcript::new(b"other")));
+    }
+}
+
+#[test]
+fn aggregated_range_proofs_verify() {
+    let params = CurveParams::secp256k1();
+    let gens = BulletproofGens::new(&params.curve, &params.point_g, &params.n, BITS, 4).unwrap();
+    for values in [vec![3], vec![0, 255], vec![1, 2, 254, 100]] {
+        let values: Vec<BigInt> = values.into_iter().map(BigInt::from).collect();
+        let blindings: Vec<BigInt> = values.iter().map(|_| basic_op::random_scalar(&params.n)).collect();
+        let (proof, commitments) = bulletproofs::prove_range_aggregated(&gens, &values, &blindings, &mut transcript()).unwrap();
+        assert!(bulletproofs::verify_range_aggregated(&gens, &proof, &commitments, &mut transcript()));
+        if commitments.len() > 1 {
+            let mut swapped = commitments.clone();
+            swapped.swap(0, 1);
+            assert!(!bulletproofs::verify_range_aggregated(&gens, &proof, &swapped, &mut transcript()));
+            assert!(!bulletproofs::verify_range_aggregated(&gens, &proof, &commitments[..1], &mut transcript()));
+        }
+    }
+    // three values are not a power of two, and five are more than the generators cover
+    let three: Vec<BigInt> = (0..3).map(BigInt::from).collect();
+    assert!(bulletproofs::prove_range_aggregated(&gens, &three, &three, &mut transcript()).is_err());
+    let five: Vec<BigInt> = (0..5).map(BigInt::from).collect();
+    assert!(bulletproofs::prove_range_aggregated(&gens, &five, &five, &mut transcript()).is_err());
+}
+
+#[test]
+fn tampered_proofs_and_commitments_are_rejected() {
+    let params = CurveParams::secp256k1();
+    let gens = BulletproofGens::new(&params.curve, &params.point_g, &params.n, BITS, 2).unwrap();
+    let blinding = basic_op::random_scalar(&params.n);
+    let (proof, commitment) = bulletproofs::prove_range(&gens, &BigInt::from(200), &blinding, &mut transcript()).unwrap();
+    for forged in tampered(&gens, &proof) {
+        assert!(!bulletproofs::verify_range(&gens, &forged, &commitment, &mut transcript()));
+    }
+    let other = gens.pedersen.commit(&BigInt::from(201), &blinding);
+    assert!(!bulletproofs::verify_range(&gens, &proof, &other, &mut transcript()));
+    let shifted = params.curve.point_add(&commitment, &gens.pedersen.point_h);
+    assert!(!bulletproofs::verify_range(&gens, &proof, &shifted, &mut transcript()));
+
+    let values = [BigInt::from(5), BigInt::from(6)];
+    let blindings = [BigInt::from(7), BigInt::from(8)];
+    let (proof, commitments) = bulletproofs::prove_range_aggregated(&gens, &values, &blindings, &mut transcript()).unwrap();
+    for forged in tampered(&gens, &proof) {
+        assert!(!bulletproofs::verify_range_aggregated(&gens, &forged, &commitments, &mut transcript()));
+    }
+    let mut moved = commitments.clone();
+    moved[1] = params.curve.point_add(&moved[1], &params.point_g);
+    assert!(!bulletproofs::verify_range_aggregated(&gens, &proof, &moved, &mut transcript()));
+}
+
+#[test]
+fn values_outside_the_range_cannot_be_proven() {
+    let params = CurveParams::secp256k1();
+    let gens = BulletproofGens::new(&params.curve, &params.point_g, &params.n, BITS, 2).unwrap();
+    let blinding = basic_op::random_scalar(&params.n);
+    for value in [BigInt::from(-1), BigInt::from(256), &params.n - 1] {
+        assert!(bulletproofs::prove_range(&gens, &value, &blinding, &mut transcript()).is_err());
+    }
+    let values = [BigInt::from(1), BigInt::from(1 << BITS)];
+    assert!(bulletproofs::prove_range_aggregated(&gens, &values, &[BigInt::from(2), BigInt::from(3)], &mut transcript()).is_err());
+
+    // a proof for 255 does not carry over to a commitment to 256 = 255 + 1 with the same blinding
+    let (proof, commitment) = bulletproofs::prove_range(&gens, &BigInt::from(255), &blinding, &mut transcript()).unwrap();
+    let out_of_range = params.curve.point_add(&commitment, &params.point_g);
+    assert_eq!(out_of_range, gens.pedersen.commit(&BigInt::from(256), &blinding));
+    assert!(!bulletproofs::verify_range(&gens, &proof, &out_of_range, &mut transcript()));
+}
+
+#[test]
+fn generators_need_powers_of_two_below_the_order() {
+    let params = CurveParams::e17();
+    let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+    assert!(BulletproofGens::new(new_ec, point_g, n, 2, 1).is_ok());
+    // 2^4 = 16 > 11
+    assert!(BulletproofGens::new(new_ec, point_g, n, 4, 1).is_err());
+    assert!(BulletproofGens::new(new_ec, point_g, n, 3, 1).is_err());
+    assert!(BulletproofGens::new(new_ec, point_g, n, 2, 3).is_err());
+}
@@ -0,0 +1,104 @@
+use mpc_zk_paillier::curve::cayley::CayleyTable;
+use mpc_zk_paillier::curve::ecc::{CurveModel, CurveParams, EcMont, Point};
+use num_bigint::BigInt;
+
+fn point(x: i64, y: i64) -> Point {
+    Point::new(BigInt::from(x), BigInt::from(y))
+}
+
+fn e17_table() -> CayleyTable {
+    let new_ec = CurveParams::e17().curve;
+    new_ec.cayley(&new_ec.group_points())
+}
+
+#[test]
+fn e17_table_is_a_group() {
+    let table = e17_table();
+    assert_eq!(table.len(), 11);
+    assert_eq!(table.identity(), &Point::Infinity);
+    assert_eq!(table.check_axioms(), Ok(()));
+    assert!(table.is_abelian());
+    assert_eq!(table.get(&point(7, 9), &point(7, 9)), Some(&point(16, 12)));
+    assert_eq!(table.get(&point(7, 9), &point(1, 1)), None);
+    // the same table whether or not ∞ is listed
+    let new_ec = CurveParams::e17().curve;
+    let mut with_identity = new_ec.group_points();
+    with_identity.insert(3, Point::Infinity);
+    assert_eq!(new_ec.cayley(&with_identity), table);
+}
+
+#[test]
+fn edwards_identity_comes_first() {
+    let mont = EcMont::new(BigInt::from(4), BigInt::from(1), BigInt::from(29)).unwrap();
+    let ed = mont.to_edwards().unwrap();
+    let table = ed.cayley(&ed.group_points());
+    assert_eq!(table.identity(), &point(0, 1));
+    assert_eq!(table.check_axioms(), Ok(()));
+}
+
+#[test]
+fn broken_tables_are_rejected() {
+    let table = e17_table();
+
+    let mut outside = table.clone();
+    outside.entries[2][3] = point(1, 1);
+    assert!(outside.check_axioms().unwrap_err().starts_with("closure fails"));
+
+    let mut no_identity = table.clone();
+    no_identity.entries[0].swap(1, 2);
+    assert!(no_identity.check_axioms().unwrap_err().contains("is not an identity"));
+
+    // a Latin square with an identity that is not associative: rows 1 and 2 exchange two
+    // entries each, so every row still holds every element once
+    let mut scrambled = table.clone();
+    let (a, b) = (3, 4);
+    for row in [1, 2] {
+        let (x, y) = (scrambled.entries[row][a].clone(), scrambled.entries[row][b].clone());
+        scrambled.entries[row][a] = y;
+        scrambled.entries[row][b] = x;
+    }
+    let error = scrambled.check_axioms().unwrap_err();
+    assert!(error.contains("associativity") || error.contains("inverse"), "{}", error);
+
+    let mut repeated = table.clone();
+    repeated.elements[1] = repeated.elements[2].clone();
+    assert!(repeated.check_axioms().is_err());
+}
+
+#[test]
+fn exports() {
+    let table = e17_table();
+
+    let csv = table.to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 12);
+    assert!(lines[0].starts_with("+,∞,\"("));
+    assert!(lines[1].starts_with("∞,∞,\"("));
+    assert!(csv.ends_with("\r\n"));
+
+    let json = table.to_json();
+    assert!(json.starts_with("{\n  \"elements\": [null, {\"x\": \""));
+    assert_eq!(json.matches("null").count(), 1 + 11);
+    assert_eq!(json.matches('[').count(), json.matches(']').count());
+
+    let markdown = table.to_markdown();
+    assert_eq!(markdown.lines().count(), 13);
+    assert!(markdown.starts_with("| + | ∞ | ("));
+    assert_eq!(markdown.lines().nth(1).unwrap(), format!("|{}", ":---:|".repeat(12)));
+
+    let latex = table.to_latex();
+    assert!(latex.starts_with("\\begin{tabular}{c|c|cccccccccc}\n+ & $\\infty$ & $("));
+    assert!(latex.ends_with("\\end{tabular}\n"));
+    assert_eq!(latex.matches("\\\\\n").count(), 12);
+
+    let html = table.to_html();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(!html.contains("http"));
+    // every point but ∞ has exactly one inverse
+    assert_eq!(html.matches("<td class=\"inverse\">").count(), 10);
+    assert_eq!(html.matches("class=\"identity\"").count(), 2 + 11 + 10);
+
+    let grid = table.to_string();
+    assert_eq!(grid.lines().count(), 2 * 12 + 1);
+    assert!(grid.lines().all(|line| line.chars().count() == grid.lines().next().unwrap().chars().count()));
+}
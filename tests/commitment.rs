@@ -0,0 +1,78 @@
+use mpc_zk_paillier::arithmetic::basic_op;
+use mpc_zk_paillier::commitment::commit_reveal;
+use mpc_zk_paillier::commitment::pedersen::{derive_generator, Pedersen};
+use mpc_zk_paillier::curve::ecc::CurveParams;
+use num_bigint::BigInt;
+
+#[test]
+fn pedersen_openings_verify_and_wrong_ones_do_not() {
+    let params = CurveParams::secp256k1();
+    let pedersen = Pedersen::new(&params.curve, &params.point_g, &params.n);
+    let m = BigInt::from(42);
+    let (commitment, r) = pedersen.commit_random(&m);
+    assert!(pedersen.verify_opening(&commitment, &m, &r));
+    assert!(!pedersen.verify_opening(&commitment, &(&m + 1), &r));
+    assert!(!pedersen.verify_opening(&commitment, &m, &(&r + 1)));
+    assert!(!pedersen.verify_opening(&commitment, &(&m + 1), &(&r - 1)));
+    // the same message under fresh randomness gives another commitment
+    let (again, _) = pedersen.commit_random(&m);
+    assert_ne!(again, commitment);
+    // openings are taken modulo n
+    assert!(pedersen.verify_opening(&commitment, &(&m + &params.n), &(&r - &params.n)));
+}
+
+#[test]
+fn pedersen_commitments_are_additively_homomorphic() {
+    let params = CurveParams::secp256k1();
+    let pedersen = Pedersen::new(&params.curve, &params.point_g, &params.n);
+    let (m1, m2) = (basic_op::random_scalar(&params.n), BigInt::from(-7));
+    let (c1, r1) = pedersen.commit_random(&m1);
+    let (c2, r2) = pedersen.commit_random(&m2);
+    let (m, r) = pedersen.add_openings((&m1, &r1), (&m2, &r2));
+    assert!(pedersen.verify_opening(&pedersen.add(&c1, &c2), &m, &r));
+    let k = BigInt::from(3);
+    assert!(pedersen.verify_opening(&pedersen.scale(&c1, &k), &(&k * &m1), &(&k * &r1)));
+    assert!(!pedersen.verify_opening(&pedersen.scale(&c1, &k), &m1, &r1));
+}
+
+#[test]
+fn derived_generators_are_deterministic_and_independent_of_g() {
+    for params in [CurveParams::secp256k1(), CurveParams::e17_order_15()] {
+        let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+        let point_h = derive_generator(new_ec, point_g, n, b"default");
+        assert!(new_ec.is_point(&point_h));
+        assert!(new_ec.scalar_mul(&point_h, n).is_infinity());
+        assert_ne!(&point_h, point_g);
+        assert!(!point_h.is_infinity());
+        assert_eq!(derive_generator(new_ec, point_g, n, b"default"), point_h);
+        assert_eq!(Pedersen::new(new_ec, point_g, n).point_h, point_h);
+    }
+    let params = CurveParams::secp256k1();
+    let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+    assert_ne!(derive_generator(new_ec, point_g, n, b"one"), derive_generator(new_ec, point_g, n, b"two"));
+}
+
+#[test]
+fn commit_reveal_binds_the_message_and_the_blinding() {
+    let (commitment, decommitment) = commit_reveal::commit(b"nonce");
+    assert!(commit_reveal::verify(&commitment, &decommitment));
+
+    let mut other = decommitment.clone();
+    other.message = b"Nonce".to_vec();
+    assert!(!commit_reveal::verify(&commitment, &other));
+    let mut other = decommitment.clone();
+    other.message.push(0);
+    assert!(!commit_reveal::verify(&commitment, &other));
+    let mut other = decommitment.clone();
+    other.blinding[0] ^= 1;
+    assert!(!commit_reveal::verify(&commitment, &other));
+    let mut forged = commitment.clone();
+    forged.0[31] ^= 1;
+    assert!(!commit_reveal::verify(&forged, &decommitment));
+
+    // a fresh blinding hides repeated messages
+    let (again, _) = commit_reveal::commit(b"nonce");
+    assert_ne!(again, commitment);
+    let (empty, opening) = commit_reveal::commit(b"");
+    assert!(commit_reveal::verify(&empty, &opening));
+}
@@ -0,0 +1,107 @@
+// Montgomery and twisted Edwards models: RFC 7748 vectors for X25519, Ed25519 against
+// Curve25519, and the birational maps on a small curve
+use mpc_zk_paillier::curve::ecc::{x25519, CurveModel, EcEdwards, EcMont, Point};
+use num_bigint::BigInt;
+
+fn hex_bytes(s: &str) -> [u8; 32] {
+    let bytes: Vec<u8> = (0..64).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect();
+    bytes.try_into().unwrap()
+}
+
+#[test]
+fn x25519_rfc7748_vectors() {
+    let scalar = hex_bytes("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4");
+    let u = hex_bytes("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c");
+    let expected = hex_bytes("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552");
+    assert_eq!(x25519(&scalar, &u), expected);
+
+    // the Diffie-Hellman example of section 6.1
+    let mut base = [0u8; 32];
+    base[0] = 9;
+    let alice = hex_bytes("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+    let bob = hex_bytes("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+    let alice_public = x25519(&alice, &base);
+    let bob_public = x25519(&bob, &base);
+    assert_eq!(alice_public, hex_bytes("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"));
+    assert_eq!(bob_public, hex_bytes("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f"));
+    let shared = hex_bytes("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    assert_eq!(x25519(&alice, &bob_public), shared);
+    assert_eq!(x25519(&bob, &alice_public), shared);
+}
+
+#[test]
+fn ed25519_is_birationally_equivalent_to_curve25519() {
+    let ed = EcEdwards::ed25519();
+    let base = EcEdwards::ed25519_base_point();
+    assert!(ed.is_complete());
+    assert!(ed.is_point(&base));
+    let l = (BigInt::from(1) << 252) + BigInt::parse_bytes(b"27742317777372353535851937790883648493", 10).unwrap();
+    assert_eq!(ed.scalar_mul(&base, &l), ed.identity());
+
+    // same A as Curve25519, and the base point goes to u = 9
+    let mont = ed.to_montgomery();
+    assert_eq!(mont.a(), EcMont::curve25519().a());
+    assert_eq!(ed.point_to_montgomery(&base).x(), Some(&BigInt::from(9)));
+    let k = BigInt::parse_bytes(b"1234567890abcdef1234567890abcdef", 16).unwrap();
+    let u = EcMont::curve25519().ladder_x(&BigInt::from(9), &k);
+    assert_eq!(u.as_ref(), ed.point_to_montgomery(&ed.scalar_mul(&base, &k)).x());
+}
+
+#[test]
+fn maps_between_the_three_models_are_group_isomorphisms() {
+    // y^2 = x^3 + 4 x^2 + x over F_29: A + 2 is a square and A - 2 is not, so Edwards is complete
+    let mont = EcMont::new(BigInt::from(4), BigInt::from(1), BigInt::from(29)).unwrap();
+    let wei = mont.to_weierstrass();
+    let ed = mont.to_edwards().unwrap();
+    assert!(ed.is_complete());
+    let mut points = mont.group_points();
+    points.push(Point::Infinity);
+    assert_eq!(wei.group_points().len() + 1, points.len());
+    assert_eq!(ed.group_points().len() + 1, points.len());
+
+    let back = EcMont::from_weierstrass(&wei).unwrap();
+    for point_a in &points {
+        let on_wei = mont.point_to_weierstrass(point_a);
+        let on_ed = mont.point_to_edwards(point_a).unwrap();
+        assert!(wei.is_point(&on_wei) && ed.is_point(&on_ed));
+        assert_eq!(&mont.point_from_weierstrass(&on_wei), point_a);
+        assert_eq!(&ed.point_to_montgomery(&on_ed), point_a);
+        assert!(back.is_point(&back.point_from_weierstrass(&on_wei)));
+        for point_b in &points {
+            let sum = mont.point_add(point_a, point_b);
+            let wei_sum = wei.point_add(&on_wei, &mont.point_to_weierstrass(point_b));
+            let ed_sum = ed.point_add(&on_ed, &mont.point_to_edwards(point_b).unwrap());
+            assert_eq!(mont.point_to_weierstrass(&sum), wei_sum);
+            assert_eq!(mont.point_to_edwards(&sum).unwrap(), ed_sum);
+        }
+        for k in 0..40 {
+            let k = BigInt::from(k);
+            if let Some(u) = point_a.x() {
+                assert_eq!(mont.ladder_x(u, &k).as_ref(), mont.scalar_mul(point_a, &k).x());
+            }
+        }
+    }
+}
+
+#[test]
+fn incomplete_edwards_curves_are_refused() {
+    // over F_13 these have points at infinity: on (1, 3), (4, 6) + (4, 6) is one of them
+    let p = BigInt::from(13);
+    for (a, d) in [(1, 3), (2, 3), (1, 4)] {
+        let err = EcEdwards::new(BigInt::from(a), BigInt::from(d), p.clone()).unwrap_err();
+        assert!(err.contains("not complete"), "({}, {}): {}", a, d, err);
+    }
+    // y^2 = x^3 + 4 x^2 + x over F_13: a = 6 is not a square, so there is no Edwards form,
+    // but the Montgomery form still lists a group
+    let mont = EcMont::new(BigInt::from(4), BigInt::from(1), p.clone()).unwrap();
+    assert!(mont.to_edwards().is_err());
+    assert_eq!(mont.cayley(&mont.group_points()).check_axioms(), Ok(()));
+    // Point::Infinity stands for the identity (0, 1) on a complete curve
+    let ed = EcEdwards::new(BigInt::from(1), BigInt::from(2), BigInt::from(5)).unwrap();
+    for point in ed.group_points() {
+        assert_eq!(ed.point_add(&Point::Infinity, &point), point);
+        assert_eq!(ed.point_add(&point, &Point::Infinity), point);
+        assert_eq!(ed.scalar_mul(&Point::Infinity, &BigInt::from(3)), ed.identity());
+    }
+    assert_eq!(ed.cayley(&ed.group_points()).check_axioms(), Ok(()));
+}
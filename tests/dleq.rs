@@ -0,0 +1,63 @@
+use mpc_zk_paillier::arithmetic::basic_op;
+use mpc_zk_paillier::curve::ecc::CurveParams;
+use mpc_zk_paillier::zk::dleq::{self, DleqStatement};
+use mpc_zk_paillier::zk::transcript::Transcript;
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+fn transcript() -> Transcript {
+    Transcript::new(b"dleq-test")
+}
+
+// a true statement for secret and a false one, over G and H = 3 G
+fn statements(params: &CurveParams, secret: &BigInt) -> (DleqStatement, DleqStatement) {
+    let new_ec = &params.curve;
+    let point_h = params.mul_g(&BigInt::from(3));
+    let honest = DleqStatement::new(new_ec, &params.point_g, &point_h, secret);
+    let mut false_statement = DleqStatement::new(new_ec, &params.point_g, &point_h, &basic_op::random_scalar(&params.n));
+    false_statement.point_y = new_ec.point_add(&false_statement.point_y, &point_h);
+    (honest, false_statement)
+}
+
+#[test]
+fn proofs_verify_for_equal_logs_only() {
+    let params = CurveParams::secp256k1();
+    let (new_ec, n) = (&params.curve, &params.n);
+    let secret = basic_op::random_scalar(n);
+    let (honest, false_statement) = statements(&params, &secret);
+    let proof = dleq::prove(new_ec, n, &honest, &secret, &mut transcript());
+    assert!(dleq::verify(new_ec, n, &honest, &proof, &mut transcript()));
+    assert!(!dleq::verify(new_ec, n, &false_statement, &proof, &mut transcript()));
+    assert!(!dleq::verify(new_ec, n, &honest, &proof, &mut Transcript::new(b"other")));
+}
+
+#[test]
+fn or_proofs_hide_the_true_branch() {
+    let params = CurveParams::secp256k1();
+    let (new_ec, n) = (&params.curve, &params.n);
+    let secret = basic_op::random_scalar(n);
+    let (honest, false_statement) = statements(&params, &secret);
+    for (list, index) in [(vec![honest.clone(), false_statement.clone()], 0), (vec![false_statement, honest], 1)] {
+        let proof = dleq::prove_or(new_ec, n, &list, index, &secret, &mut transcript());
+        assert!(dleq::verify_or(new_ec, n, &list, &proof, &mut transcript()));
+        assert!(!dleq::verify_or(new_ec, n, &list[..1], &proof, &mut transcript()));
+    }
+}
+
+#[test]
+fn simulated_branches_take_zero_like_real_ones() {
+    // on e17 (n = 11) a real challenge or response is 0 about once in 11 proofs, so the
+    // simulated branch must produce 0 as often, or a 0 would point at the real branch
+    let params = CurveParams::e17();
+    let (new_ec, n) = (&params.curve, &params.n);
+    let secret = BigInt::from(4);
+    let (honest, false_statement) = statements(&params, &secret);
+    let list = [honest, false_statement];
+    let (mut zero_challenges, mut zero_responses) = (0, 0);
+    for _ in 0..400 {
+        let proof = dleq::prove_or(new_ec, n, &list, 0, &secret, &mut transcript());
+        zero_challenges += proof.challenges[1].is_zero() as usize;
+        zero_responses += proof.responses[1].is_zero() as usize;
+    }
+    assert!(zero_challenges > 0 && zero_responses > 0);
+}
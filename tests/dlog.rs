@@ -0,0 +1,104 @@
+use mpc_zk_paillier::arithmetic::basic_op;
+use mpc_zk_paillier::attacks::dlog;
+use mpc_zk_paillier::curve::ecc::{CurveModel, CurveParams, EcWei, Point};
+use mpc_zk_paillier::elgamal;
+use num_bigint::BigInt;
+
+fn curve(a: u64, b: u64, p: u64) -> EcWei {
+    EcWei::new(BigInt::from(a), BigInt::from(b), BigInt::from(p)).unwrap()
+}
+
+#[test]
+fn every_solver_breaks_e17() {
+    let params = CurveParams::e17();
+    let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+    for x in 0..11 {
+        let x = BigInt::from(x);
+        let target = new_ec.scalar_mul(point_g, &x);
+        assert_eq!(dlog::brute_force(new_ec, point_g, &target, n), Ok(x.clone()));
+        assert_eq!(dlog::baby_step_giant_step(new_ec, point_g, &target, n), Ok(x.clone()));
+        assert_eq!(dlog::pollard_rho(new_ec, point_g, &target, n), Ok(x.clone()));
+        assert_eq!(dlog::pohlig_hellman(new_ec, point_g, &target, n), Ok(x.clone()));
+        assert_eq!(dlog::kangaroo(new_ec, point_g, &target, &BigInt::from(0), &BigInt::from(10)), Ok(x.clone()));
+        assert_eq!(dlog::discrete_log(new_ec, point_g, &target), Ok(x));
+    }
+}
+
+#[test]
+fn points_outside_the_subgroup_are_reported() {
+    // #E = 15: G of order 5 does not reach the points of order 3
+    let new_ec = CurveParams::e17_order_15().curve;
+    let points = new_ec.group_points();
+    let order_of = |point: &Point| point.order(&new_ec);
+    let point_g = points.iter().find(|point| order_of(point) == BigInt::from(5)).unwrap();
+    let other = points.iter().find(|point| order_of(point) == BigInt::from(3)).unwrap();
+    let five = BigInt::from(5);
+    assert!(dlog::brute_force(&new_ec, point_g, other, &five).is_err());
+    assert!(dlog::baby_step_giant_step(&new_ec, point_g, other, &five).is_err());
+    assert!(dlog::pollard_rho(&new_ec, point_g, other, &five).is_err());
+    assert!(dlog::pohlig_hellman(&new_ec, point_g, other, &BigInt::from(15)).is_err());
+    assert!(dlog::discrete_log(&new_ec, point_g, other).is_err());
+
+    // Pohlig-Hellman accepts any multiple of the order, such as #E
+    let x = BigInt::from(3);
+    let target = new_ec.scalar_mul(point_g, &x);
+    assert_eq!(dlog::pohlig_hellman(&new_ec, point_g, &target, &BigInt::from(15)), Ok(x));
+}
+
+#[test]
+fn rho_on_a_28_bit_prime_order_curve() {
+    // #E = 268450381 is prime
+    let new_ec = curve(1, 19, 268435459);
+    let n = BigInt::from(268450381u64);
+    let point_g = new_ec.random_point();
+    let x = basic_op::random_scalar(&n);
+    let target = new_ec.scalar_mul(&point_g, &x);
+    assert_eq!(dlog::pollard_rho(&new_ec, &point_g, &target, &n), Ok(x));
+}
+
+#[test]
+fn pohlig_hellman_on_a_smooth_40_bit_curve() {
+    // #E = 2^3 * 59 * 79 * 4463 * 6607, far below the 2^20 steps of rho on 40 bits
+    let new_ec = curve(1, 6, 1099511627791);
+    let n = BigInt::from(1099512784808u64);
+    // a random point need not generate the group, so n can be a proper multiple of its order
+    for _ in 0..10 {
+        let point_g = new_ec.random_point();
+        let x = basic_op::random_scalar(&n);
+        let target = new_ec.scalar_mul(&point_g, &x);
+        let found = dlog::pohlig_hellman(&new_ec, &point_g, &target, &n).unwrap();
+        assert_eq!(new_ec.scalar_mul(&point_g, &found), target);
+    }
+    let point_g = new_ec.random_point();
+    let target = new_ec.scalar_mul(&point_g, &BigInt::from(7));
+    assert!(dlog::baby_step_giant_step(&new_ec, &point_g, &target, &(BigInt::from(1) << 60)).is_err());
+}
+
+#[test]
+fn kangaroo_on_secp256k1() {
+    let params = CurveParams::secp256k1();
+    let lower = BigInt::from(1) << 40;
+    let upper = &lower + (BigInt::from(1) << 12);
+    let x = &lower + 3210;
+    let target = params.mul_g(&x);
+    assert_eq!(dlog::kangaroo(&params.curve, &params.point_g, &target, &lower, &upper), Ok(x));
+    let outside = params.mul_g(&(&upper + 1));
+    assert!(dlog::kangaroo(&params.curve, &params.point_g, &outside, &lower, &upper).is_err());
+}
+
+#[test]
+fn elgamal_decrypts_small_sums() {
+    let params = CurveParams::secp256k1();
+    let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+    let keys = params.gen_key_pair();
+    let bound = BigInt::from(1 << 12);
+    let first = elgamal::encrypt(new_ec, point_g, n, &keys.pk, &BigInt::from(1234));
+    let second = elgamal::encrypt(new_ec, point_g, n, &keys.pk, &BigInt::from(2345));
+    assert_eq!(elgamal::decrypt(new_ec, point_g, n, &keys.sk, &first, &bound), Ok(BigInt::from(1234)));
+    let sum = elgamal::add(new_ec, point_g, n, &first, &second);
+    assert_eq!(elgamal::decrypt(new_ec, point_g, n, &keys.sk, &sum, &bound), Ok(BigInt::from(3579)));
+    assert_eq!(elgamal::decrypt_point(new_ec, point_g, n, &keys.sk, &sum), params.mul_g(&BigInt::from(3579)));
+
+    let large = elgamal::encrypt(new_ec, point_g, n, &keys.pk, &BigInt::from(1 << 14));
+    assert!(elgamal::decrypt(new_ec, point_g, n, &keys.sk, &large, &bound).is_err());
+}
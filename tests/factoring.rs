@@ -0,0 +1,145 @@
+use mpc_zk_paillier::arithmetic::basic_op;
+use mpc_zk_paillier::attacks::factoring::{self, FactoringMethod};
+use mpc_zk_paillier::curve::ecc::CurveParams;
+use mpc_zk_paillier::mpc_wallet::paillier_primes;
+use mpc_zk_paillier::paillier::{cipher_paillier, decipher_paillier, gen_key_paillier};
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+
+fn next_prime(n: &BigInt) -> BigInt {
+    let mut p = n + 1;
+    while !basic_op::is_probable_prime(&p) {
+        p += 1;
+    }
+    p
+}
+
+// the first prime k * m + sign, k = 1, 2, ...: p - sign has only the factors of m and k
+fn smooth_prime(m: &BigInt, sign: i32) -> BigInt {
+    let mut k = BigInt::one();
+    while !basic_op::is_probable_prime(&(&k * m + sign)) {
+        k += 1;
+    }
+    &k * m + sign
+}
+
+fn primorial(bound: u32) -> BigInt {
+    (2..=bound).filter(|p| basic_op::is_probable_prime(&BigInt::from(*p))).map(BigInt::from).product()
+}
+
+fn assert_factor(n: &BigInt, factor: Option<BigInt>) {
+    let factor = factor.unwrap_or_else(|| panic!("no factor of {}", n));
+    assert!(factor > BigInt::one() && &factor < n);
+    assert!((n % &factor).is_zero());
+}
+
+#[test]
+fn every_method_finds_its_kind_of_factor() {
+    let n = BigInt::from(33);
+    assert_eq!(factoring::trial_division(&n, 10), Some(BigInt::from(3)));
+    assert_eq!(factoring::trial_division(&BigInt::from(101 * 103), 100), None);
+
+    // two 30-bit primes
+    let n = next_prime(&BigInt::from(1u64 << 30)) * next_prime(&BigInt::from(3u64 << 29));
+    assert_factor(&n, factoring::pollard_rho(&n, 1 << 20));
+
+    // p - 1 and p + 1 are products of the primes below 60, the other prime is 64 bits
+    let q = next_prime(&BigInt::from(1u64 << 63));
+    let n = smooth_prime(&primorial(60), 1) * &q;
+    assert_factor(&n, factoring::pollard_p_minus_1(&n, 1000));
+    let n = smooth_prime(&primorial(60), -1) * &q;
+    assert_factor(&n, factoring::williams_p_plus_1(&n, 1000));
+
+    // two 100-bit primes a few thousand apart
+    let p = next_prime(&(BigInt::one() << 100));
+    let n = &p * next_prime(&(&p + 5000));
+    assert_eq!(factoring::fermat(&n, 10), Some(p));
+
+    // two 28-bit primes: the quadratic sieve
+    let n = next_prime(&BigInt::from(1u64 << 28)) * next_prime(&BigInt::from(3u64 << 27));
+    assert_factor(&n, factoring::quadratic_sieve(&n));
+}
+
+#[test]
+fn methods_give_up_on_primes_and_their_bounds() {
+    let p = next_prime(&BigInt::from(1u64 << 40));
+    assert_eq!(factoring::pollard_rho(&p, 1 << 10), None);
+    assert_eq!(factoring::quadratic_sieve(&p), None);
+    // p - 1 and p + 1 of random 128-bit primes are practically never 100-smooth
+    let n = basic_op::random_prime(128) * basic_op::random_prime(128);
+    assert_eq!(factoring::pollard_p_minus_1(&n, 100), None);
+    assert_eq!(factoring::williams_p_plus_1(&n, 100), None);
+    assert_eq!(factoring::fermat(&n, 100), None);
+}
+
+#[test]
+fn the_wallet_paillier_key_is_broken() {
+    // N = 33 on the e17 teaching curve
+    let (p, q) = paillier_primes(&CurveParams::e17().n);
+    let key = gen_key_paillier(&p, &q);
+    assert_eq!(key.public_key.1, BigInt::from(33));
+    let broken = factoring::break_paillier(&key.public_key).unwrap();
+    for m in 1..33 {
+        let c = cipher_paillier(&key.public_key, &BigInt::from(m)).unwrap();
+        assert_eq!(decipher_paillier(&broken.private_key, c, &broken.public_key), BigInt::from(m));
+    }
+
+    // 64 and 72 bits: mostly past the rho budget, so the quadratic sieve finishes them
+    for bits in [32, 36] {
+        let (p, q) = (basic_op::random_prime(bits), basic_op::random_prime(bits));
+        let key = gen_key_paillier(&p, &q);
+        let broken = factoring::break_paillier(&key.public_key).unwrap();
+        let m = BigInt::from(123456789);
+        let c = cipher_paillier(&key.public_key, &m).unwrap();
+        assert_eq!(decipher_paillier(&broken.private_key, c, &broken.public_key), m);
+    }
+}
+
+#[test]
+fn the_audit_flags_weak_moduli() {
+    let report = factoring::audit_modulus(&BigInt::from(33));
+    assert_eq!(report.factor(), Some((FactoringMethod::TrialDivision, &BigInt::from(3))));
+    assert!(report.is_weak());
+
+    // close 256-bit primes
+    let p = basic_op::random_prime(256);
+    let n = &p * next_prime(&(&p + (BigInt::one() << 100)));
+    let report = factoring::audit_paillier(&(BigInt::from(5), n));
+    assert_eq!(report.factor(), Some((FactoringMethod::Fermat, &p)));
+
+    // a smooth p - 1 beside a random prime
+    let p = smooth_prime(&primorial(200), 1);
+    let report = factoring::audit_modulus(&(&p * basic_op::random_prime(256)));
+    assert_eq!(report.factor().map(|(method, _)| method), Some(FactoringMethod::PollardPMinus1));
+
+    // nothing to find, but 512 bits are far too few
+    let n = basic_op::random_prime(256) * basic_op::random_prime(256);
+    let report = factoring::audit_modulus(&n);
+    assert_eq!(report.factor(), None);
+    assert!(report.is_too_small() && report.is_weak());
+    assert!(report.to_string().contains("too small"));
+}
+
+#[test]
+fn moduli_below_4_have_no_factor() {
+    for n in [-6, -1, 0, 1, 2, 3] {
+        let n = BigInt::from(n);
+        assert_eq!(factoring::trial_division(&n, 100), None);
+        assert_eq!(factoring::pollard_rho(&n, 100), None);
+        assert_eq!(factoring::pollard_p_minus_1(&n, 100), None);
+        assert_eq!(factoring::williams_p_plus_1(&n, 100), None);
+        assert_eq!(factoring::fermat(&n, 100), None);
+        assert_eq!(factoring::quadratic_sieve(&n), None);
+        assert_eq!(factoring::find_factor(&n), None);
+        assert_eq!(factoring::audit_modulus(&n).factor(), None);
+        assert!(factoring::break_paillier(&(BigInt::from(5), n)).is_err());
+    }
+}
+
+#[test]
+fn break_paillier_needs_two_primes() {
+    // 8 = 2 * 4 and 105 = 3 * 35 factor, but not into two primes
+    for n in [8, 105] {
+        assert!(factoring::break_paillier(&(BigInt::from(5), BigInt::from(n))).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+// the fixed-base table behind mul_g and the wNAF multiplication must give the same points
+// as scalar_mul, at the edges of [0, n) and beyond
+use mpc_zk_paillier::arithmetic::basic_op;
+use mpc_zk_paillier::curve::ecc::{CurveModel, CurveParams, EcWei, FixedBaseTable, Point};
+use num_bigint::BigInt;
+
+// 0, 1, 2, n - 1, n, n + 1, 2n - 1, 2n, 3n + 5 and a few random scalars
+fn scalars(n: &BigInt) -> Vec<BigInt> {
+    let mut out: Vec<BigInt> = [0, 1, 2].into_iter().map(BigInt::from).collect();
+    out.extend([n - 1, n.clone(), n + 1, 2 * n - 1, 2 * n, 3 * n + 5]);
+    out.extend((0..4).map(|_| basic_op::random_scalar(n)));
+    out
+}
+
+#[test]
+fn the_table_matches_scalar_mul() {
+    for params in [CurveParams::e17(), CurveParams::e17_order_15(), CurveParams::secp256k1()] {
+        let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+        let tables: Vec<FixedBaseTable> = [1, 4, 8]
+            .into_iter()
+            .map(|window| FixedBaseTable::new(new_ec, point_g, n, window).unwrap())
+            .collect();
+        for k in scalars(n) {
+            let expected = new_ec.scalar_mul(point_g, &k);
+            assert_eq!(params.mul_g(&k), expected, "{} on {}", k, params.name);
+            for table in &tables {
+                assert_eq!(table.mul(&k), expected, "{} on {}", k, params.name);
+            }
+            // negative scalars are reduced modulo n: (-k) G = -(k G)
+            if k > BigInt::from(0) {
+                assert_eq!(params.mul_g(&-&k), new_ec.negate(&expected), "-{} on {}", k, params.name);
+                assert_eq!(tables[1].mul(&-&k), new_ec.negate(&expected));
+            }
+        }
+        assert_eq!(tables[0].generator(), point_g);
+    }
+}
+
+#[test]
+fn every_multiple_of_the_toy_generators_matches() {
+    for params in [CurveParams::e17(), CurveParams::e17_order_15()] {
+        let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+        let table = FixedBaseTable::new(new_ec, point_g, n, 2).unwrap();
+        for k in -40..40 {
+            let k = BigInt::from(k);
+            let reduced = ((&k % n) + n) % n;
+            assert_eq!(table.mul(&k), new_ec.scalar_mul(point_g, &reduced), "{}", k);
+        }
+    }
+}
+
+#[test]
+fn tables_refuse_bad_windows_and_even_orders() {
+    let params = CurveParams::e17();
+    let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+    assert!(FixedBaseTable::new(new_ec, point_g, n, 0).is_err());
+    assert!(FixedBaseTable::new(new_ec, point_g, n, 9).is_err());
+    assert!(FixedBaseTable::new(new_ec, point_g, &BigInt::from(12), 4).is_err());
+    assert!(FixedBaseTable::new(new_ec, point_g, &BigInt::from(1), 4).is_err());
+}
+
+#[test]
+fn wnaf_matches_scalar_mul() {
+    for params in [CurveParams::e17(), CurveParams::secp256k1()] {
+        let (new_ec, n) = (&params.curve, &params.n);
+        let points = [params.point_g.clone(), params.gen_key_pair().pk, Point::Infinity];
+        for point in &points {
+            for k in scalars(n) {
+                assert_eq!(new_ec.scalar_mul_wnaf(point, &k), new_ec.scalar_mul(point, &k), "{} * {}", k, point);
+            }
+            // like scalar_mul, a scalar <= 0 gives ∞
+            assert_eq!(new_ec.scalar_mul_wnaf(point, &BigInt::from(-3)), Point::Infinity);
+            assert_eq!(new_ec.scalar_mul(point, &BigInt::from(-3)), Point::Infinity);
+        }
+    }
+    // every point and scalar on a curve with points of order 2
+    let new_ec = EcWei::new(BigInt::from(1), BigInt::from(0), BigInt::from(17)).unwrap();
+    for point in new_ec.group_points() {
+        for k in 0..100 {
+            let k = BigInt::from(k);
+            assert_eq!(new_ec.scalar_mul_wnaf(&point, &k), new_ec.scalar_mul(&point, &k), "{} * {}", k, point);
+        }
+    }
+}
+
+#[test]
+fn tables_accept_odd_composite_orders() {
+    // e17-15 is cyclic of order 15: a generator of the whole group has odd, non-prime order
+    let new_ec = CurveParams::e17_order_15().curve;
+    let n = BigInt::from(15);
+    let point_g = new_ec.group_points().into_iter().find(|point| point.order(&new_ec) == n).unwrap();
+    let table = FixedBaseTable::new(&new_ec, &point_g, &n, 3).unwrap();
+    for k in -20..50 {
+        let k = BigInt::from(k);
+        let reduced = ((&k % &n) + &n) % &n;
+        assert_eq!(table.mul(&k), new_ec.scalar_mul(&point_g, &reduced), "{}", k);
+    }
+}
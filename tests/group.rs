@@ -0,0 +1,141 @@
+use mpc_zk_paillier::curve::ecc::{CurveParams, Point};
+use mpc_zk_paillier::group::{bounded_log, EcGroup, Group, SchnorrGroup};
+use mpc_zk_paillier::zk::schnorr;
+use mpc_zk_paillier::zk::transcript::Transcript;
+use mpc_zk_paillier::{dh, elgamal, vss};
+use num_bigint::BigInt;
+use num_traits::One;
+
+// every protocol of the trait, run the same way on any backend
+fn check_protocols<G: Group>(group: &G) {
+    let q = group.order();
+    let g = group.generator();
+
+    // axioms on a few elements
+    let a = group.exp_g(&BigInt::from(3));
+    let b = group.exp_g(&BigInt::from(5));
+    assert_eq!(group.op(&a, &b), group.exp_g(&BigInt::from(8)));
+    assert_eq!(group.op(&a, &group.inverse(&a)), group.identity());
+    assert_eq!(group.op(&a, &group.identity()), a);
+    assert_eq!(group.exp(&g, &q), group.identity());
+    assert_eq!(group.exp(&g, &BigInt::from(-1)), group.inverse(&g));
+    assert!(group.is_element(&a));
+    assert_eq!(group.deserialize(&group.serialize(&a)), Some(a.clone()));
+    assert_eq!(group.serialize(&a).len(), group.serialize(&group.identity()).len());
+
+    // Diffie-Hellman
+    let (x1, y1) = group.key_pair();
+    let (x2, y2) = group.key_pair();
+    assert_eq!(dh::shared_secret(group, &x1, &y2), dh::shared_secret(group, &x2, &y1));
+    assert!(dh::shared_secret(group, &x1, &group.identity()).is_err());
+
+    // Schnorr
+    let proof = schnorr::prove_in(group, &x1, &mut Transcript::new(b"test"));
+    assert!(schnorr::verify_in(group, &y1, &proof, &mut Transcript::new(b"test")));
+    let mut forged = proof.clone();
+    forged.response = (forged.response + 1) % &q;
+    assert!(!schnorr::verify_in(group, &y1, &forged, &mut Transcript::new(b"test")));
+
+    // ElGamal
+    let first = elgamal::encrypt_in(group, &y1, &BigInt::from(3));
+    let second = elgamal::encrypt_in(group, &y1, &BigInt::from(4));
+    let sum = elgamal::add_in(group, &first, &second);
+    assert_eq!(elgamal::decrypt_element_in(group, &x1, &sum), group.exp_g(&BigInt::from(7)));
+    assert_eq!(elgamal::decrypt_in(group, &x1, &sum, &BigInt::from(9)), Ok(BigInt::from(7)));
+    assert!(elgamal::decrypt_in(group, &x1, &sum, &BigInt::from(6)).is_err());
+
+    // Feldman VSS, 3 of 5
+    let secret = BigInt::from(6);
+    let dealing = vss::deal(group, &secret, 3, 5).unwrap();
+    assert_eq!(dealing.commitments[0], group.exp_g(&secret));
+    assert!(dealing.shares.iter().all(|share| vss::verify_share(group, &dealing.commitments, share)));
+    let mut forged = dealing.shares[1].clone();
+    forged.value = (forged.value + 1) % &q;
+    assert!(!vss::verify_share(group, &dealing.commitments, &forged));
+    assert_eq!(vss::reconstruct(&q, &dealing.shares[2..]), Ok(secret.clone()));
+    assert_eq!(vss::reconstruct(&q, &[dealing.shares[4].clone(), dealing.shares[0].clone(), dealing.shares[2].clone()]), Ok(secret));
+    assert!(vss::reconstruct(&q, &[dealing.shares[0].clone(), dealing.shares[0].clone()]).is_err());
+    assert!(vss::deal(group, &BigInt::one(), 6, 5).is_err());
+}
+
+#[test]
+fn both_backends_run_the_same_protocols() {
+    let params = CurveParams::e17();
+    check_protocols(&EcGroup::from(&params));
+    check_protocols(&SchnorrGroup::toy());
+    let params = CurveParams::secp256k1();
+    check_protocols(&EcGroup::from(&params));
+    check_protocols(&SchnorrGroup::random(256, 64).unwrap());
+}
+
+#[test]
+fn e17_and_the_toy_schnorr_group_are_both_cyclic_of_order_11() {
+    let params = CurveParams::e17();
+    let (curve, field) = (EcGroup::from(&params), SchnorrGroup::toy());
+    assert_eq!(curve.order(), field.order());
+    let powers: Vec<BigInt> = (0..11).map(|k| field.exp_g(&BigInt::from(k))).collect();
+    assert_eq!(powers.iter().filter(|a| a.is_one()).count(), 1);
+    // g^k <-> k G is an isomorphism: discrete logs agree
+    for k in 0..11 {
+        let k = BigInt::from(k);
+        let bound = BigInt::from(10);
+        assert_eq!(bounded_log(&field, &field.exp_g(&k), &bound), Some(k.clone()));
+        assert_eq!(bounded_log(&curve, &curve.exp_g(&k), &bound), Some(k));
+    }
+}
+
+#[test]
+fn schnorr_groups_are_validated() {
+    let (p, q) = (BigInt::from(23), BigInt::from(11));
+    assert!(SchnorrGroup::new(&p, &q, &BigInt::from(5)).is_err()); // order 22
+    assert!(SchnorrGroup::new(&p, &BigInt::from(7), &BigInt::from(4)).is_err());
+    assert!(SchnorrGroup::new(&BigInt::from(21), &BigInt::from(5), &BigInt::from(4)).is_err());
+    assert!(SchnorrGroup::new(&p, &q, &BigInt::one()).is_err());
+    let group = SchnorrGroup::random(128, 32).unwrap();
+    assert_eq!(group.p.bits(), 128);
+    assert_eq!(group.q.bits(), 32);
+    assert!(SchnorrGroup::random(33, 32).is_err());
+
+    // 5 generates all of Z_23^*, so it is not in the subgroup of order 11
+    let field = SchnorrGroup::toy();
+    assert!(!field.is_element(&BigInt::from(5)));
+    assert_eq!(field.deserialize(&[5]), None);
+    assert!(dh::shared_secret(&field, &BigInt::from(3), &BigInt::from(5)).is_err());
+}
+
+#[test]
+fn curve_proofs_and_group_proofs_agree() {
+    // the curve-only functions are the generic ones on EcGroup, with the same transcript
+    let params = CurveParams::e17();
+    let group = EcGroup::from(&params);
+    let (secret, public) = group.key_pair();
+    let proof = schnorr::prove_in(&group, &secret, &mut Transcript::new(b"test"));
+    assert!(schnorr::verify(&params.curve, &params.point_g, &params.n, &public, &proof, &mut Transcript::new(b"test")));
+    let proof = schnorr::prove(&params.curve, &params.point_g, &params.n, &secret, &mut Transcript::new(b"test"));
+    assert!(schnorr::verify_in(&group, &public, &proof, &mut Transcript::new(b"test")));
+
+    let ct = elgamal::encrypt(&params.curve, &params.point_g, &params.n, &public, &BigInt::from(4));
+    assert_eq!(elgamal::decrypt_in(&group, &secret, &ct, &BigInt::from(10)), Ok(BigInt::from(4)));
+    assert_ne!(ct.c1, Point::Infinity);
+    let other = elgamal::encrypt_in(&group, &public, &BigInt::from(5));
+    let sum = elgamal::add(&params.curve, &params.point_g, &params.n, &ct, &other);
+    assert_eq!(sum, elgamal::add_in(&group, &ct, &other));
+    assert_eq!(elgamal::decrypt_point(&params.curve, &params.point_g, &params.n, &secret, &sum), elgamal::decrypt_element_in(&group, &secret, &sum));
+    assert_eq!(elgamal::decrypt(&params.curve, &params.point_g, &params.n, &secret, &sum, &BigInt::from(10)), Ok(BigInt::from(9)));
+}
+
+#[test]
+fn curve_proofs_reject_statements_outside_the_subgroup() {
+    // with cofactor 3 a point of order 3 is on the curve but not in <G>; the curve-only
+    // verify checks membership exactly as verify_in does
+    let params = CurveParams::e17_order_15();
+    let group = EcGroup::from(&params);
+    let outside = (0..17)
+        .flat_map(|x| (0..17).map(move |y| Point::new(BigInt::from(x), BigInt::from(y))))
+        .find(|p| params.curve.is_point(p) && !group.is_element(p))
+        .unwrap();
+    let (secret, _) = group.key_pair();
+    let proof = schnorr::prove_in(&group, &secret, &mut Transcript::new(b"test"));
+    assert!(!schnorr::verify(&params.curve, &params.point_g, &params.n, &outside, &proof, &mut Transcript::new(b"test")));
+    assert!(!schnorr::verify_in(&group, &outside, &proof, &mut Transcript::new(b"test")));
+}
@@ -0,0 +1,144 @@
+use mpc_zk_paillier::curve::ecc::{CurveModel, CurveParams, EcWei, Point};
+use num_bigint::BigInt;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+fn curve(a: i64, b: i64, p: i64) -> EcWei {
+    let reduce = |x: i64| BigInt::from(((x % p) + p) % p);
+    EcWei::new(reduce(a), reduce(b), BigInt::from(p)).unwrap()
+}
+
+// every point, ∞ first
+fn all_points(new_ec: &EcWei) -> Vec<Point> {
+    let mut points = vec![Point::Infinity];
+    points.extend(new_ec.group_points());
+    points
+}
+
+fn point_order(new_ec: &EcWei, point: &Point) -> BigInt {
+    let (mut k, mut multiple) = (1, point.clone());
+    while !multiple.is_infinity() {
+        multiple = new_ec.point_add(&multiple, point);
+        k += 1;
+    }
+    BigInt::from(k)
+}
+
+// the subgroup generated by some points, as indices into `points`
+fn closure(new_ec: &EcWei, points: &[Point], generators: &[Point]) -> BTreeSet<usize> {
+    let index = |point: &Point| points.iter().position(|q| q == point).unwrap();
+    let mut members = BTreeSet::from([0]);
+    let mut frontier = vec![Point::Infinity];
+    while let Some(point) = frontier.pop() {
+        for generator in generators {
+            let next = new_ec.point_add(&point, generator);
+            if members.insert(index(&next)) {
+                frontier.push(next);
+            }
+        }
+    }
+    members
+}
+
+// subgroups of E(F_p) need at most two generators
+fn brute_force_subgroups(new_ec: &EcWei, points: &[Point]) -> BTreeSet<BTreeSet<usize>> {
+    let mut subgroups = BTreeSet::new();
+    for p1 in points {
+        for p2 in points {
+            subgroups.insert(closure(new_ec, points, &[p1.clone(), p2.clone()]));
+        }
+    }
+    subgroups
+}
+
+#[test]
+fn toy_curves_decompose() {
+    let e17 = CurveParams::e17().curve.group_structure();
+    assert!(e17.is_cyclic());
+    assert_eq!((e17.n1.clone(), e17.n2.clone()), (BigInt::from(11), BigInt::from(1)));
+    assert_eq!(e17.to_string(), "Z_11");
+
+    let e17_order_15 = CurveParams::e17_order_15().curve.group_structure();
+    assert_eq!(e17_order_15.to_string(), "Z_15");
+
+    // y^2 = x^3 - x mod 5 has its three points of order 2 and eight points in all
+    let new_ec = curve(-1, 0, 5);
+    let structure = new_ec.group_structure();
+    assert!(!structure.is_cyclic());
+    assert_eq!(structure.to_string(), "Z_4 × Z_2");
+    assert_eq!(point_order(&new_ec, &structure.point_p1), BigInt::from(4));
+    assert_eq!(point_order(&new_ec, &structure.point_p2), BigInt::from(2));
+    assert!(new_ec.get_base_points(&new_ec.group_points()).is_empty());
+}
+
+#[test]
+fn analysis_matches_brute_force() {
+    let mut non_cyclic = 0;
+    for p in [5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        for (a, b) in [(-1, 0), (1, 0), (0, 1), (-3, 4), (2, 3), (4, 0)] {
+            let new_ec = match EcWei::new(BigInt::from(((a % p) + p) % p), BigInt::from(((b % p) + p) % p), BigInt::from(p)) {
+                Ok(new_ec) => new_ec,
+                Err(_) => continue,
+            };
+            let points = all_points(&new_ec);
+            let structure = new_ec.group_structure();
+            assert_eq!(structure.order(), BigInt::from(points.len()));
+            assert!((&structure.n1 % &structure.n2) == BigInt::from(0));
+            if !structure.is_cyclic() {
+                non_cyclic += 1;
+            }
+            // P1 and P2 generate everything
+            let generated = closure(&new_ec, &points, &[structure.point_p1.clone(), structure.point_p2.clone()]);
+            assert_eq!(generated.len(), points.len());
+
+            let mut distribution = BTreeMap::new();
+            for point in &points {
+                *distribution.entry(point_order(&new_ec, point)).or_insert(BigInt::from(0)) += 1;
+            }
+            let listed: BTreeMap<BigInt, BigInt> =
+                new_ec.order_distribution().into_iter().filter(|(_, count)| *count != BigInt::from(0)).collect();
+            assert_eq!(listed, distribution, "y^2 = x^3 + {}x + {} mod {}", a, b, p);
+
+            let subgroups = new_ec.subgroups();
+            let expected = brute_force_subgroups(&new_ec, &points);
+            let found: BTreeSet<BTreeSet<usize>> = subgroups
+                .iter()
+                .map(|subgroup| {
+                    let members = closure(&new_ec, &points, &subgroup.generators);
+                    assert_eq!(BigInt::from(members.len()), subgroup.order);
+                    assert_eq!(&subgroup.structure.0 * &subgroup.structure.1, subgroup.order);
+                    assert_eq!(subgroup.generators.len(), if subgroup.structure.1 == BigInt::from(1) { 1 } else { 2 });
+                    members
+                })
+                .collect();
+            assert_eq!(found.len(), subgroups.len(), "a subgroup is listed twice");
+            assert_eq!(found, expected, "y^2 = x^3 + {}x + {} mod {}", a, b, p);
+
+            for n in 1..=12 {
+                let n = BigInt::from(n);
+                let torsion: HashSet<Point> = new_ec.torsion(&n).into_iter().collect();
+                let expected: HashSet<Point> = points
+                    .iter()
+                    .filter(|point| new_ec.scalar_mul(point, &n).is_infinity())
+                    .cloned()
+                    .collect();
+                assert_eq!(torsion, expected);
+                let (g1, g2) = structure.torsion_structure(&n);
+                assert_eq!(BigInt::from(torsion.len()), g1 * g2);
+            }
+        }
+    }
+    assert!(non_cyclic > 0);
+}
+
+#[test]
+fn secp256k1_is_cyclic_of_prime_order() {
+    let params = CurveParams::secp256k1();
+    let structure = params.curve.group_structure();
+    assert!(structure.is_cyclic());
+    assert_eq!(structure.n1, params.n);
+    let subgroups = params.curve.subgroups();
+    assert_eq!(subgroups.len(), 2);
+    assert_eq!(subgroups[0].generators, vec![Point::Infinity]);
+    assert_eq!(subgroups[1].order, params.n);
+    assert_eq!(params.curve.torsion(&BigInt::from(2)), vec![Point::Infinity]);
+}
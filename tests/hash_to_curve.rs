@@ -0,0 +1,97 @@
+// known-answer tests from RFC 9380, appendices J.1.1 and K.1, and properties of the
+// try-and-increment fallback on the small curves
+use mpc_zk_paillier::curve::ecc::{CurveParams, Point};
+use mpc_zk_paillier::hash_to_curve::{expand_message_xmd, hash_to_curve, hash_to_field, Suite};
+use num_bigint::BigInt;
+
+fn hex_bytes(s: &str) -> Vec<u8> {
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+}
+
+fn hex(s: &str) -> BigInt {
+    BigInt::parse_bytes(s.as_bytes(), 16).unwrap()
+}
+
+fn messages() -> Vec<Vec<u8>> {
+    vec![
+        b"".to_vec(),
+        b"abc".to_vec(),
+        b"abcdef0123456789".to_vec(),
+        [b"q128_".to_vec(), vec![b'q'; 128]].concat(),
+        [b"a512_".to_vec(), vec![b'a'; 512]].concat(),
+    ]
+}
+
+#[test]
+fn expand_message_xmd_sha256_vectors() {
+    let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+    let expected = [
+        "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+        "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+        "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+        "b23a1d2b4d97b2ef7785562a7e8bac7eed54ed6e97e29aa51bfe3f12ddad1ff9",
+        "4623227bcc01293b8c130bf771da8c298dede7383243dc0993d2d94823958c4c",
+    ];
+    for (msg, uniform_bytes) in messages().iter().zip(expected) {
+        assert_eq!(expand_message_xmd(msg, dst, 0x20).unwrap(), hex_bytes(uniform_bytes));
+    }
+    let long = "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced";
+    assert_eq!(expand_message_xmd(b"", dst, 0x80).unwrap(), hex_bytes(long));
+}
+
+#[test]
+fn expand_message_xmd_rejects_bad_parameters() {
+    assert!(expand_message_xmd(b"abc", b"", 32).is_err());
+    assert!(expand_message_xmd(b"abc", b"dst", 255 * 32 + 1).is_err());
+    assert_eq!(expand_message_xmd(b"abc", b"dst", 255 * 32).unwrap().len(), 255 * 32);
+}
+
+#[test]
+fn secp256k1_xmd_sha256_sswu_ro_vectors() {
+    let params = CurveParams::secp256k1();
+    assert_eq!(Suite::for_curve(&params.curve), Some(Suite::Secp256k1));
+    let dst = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+    let u = hash_to_field(b"", dst, 2, params.curve.p()).unwrap();
+    assert_eq!(u[0], hex("6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3"));
+    assert_eq!(u[1], hex("1ae6c212e08fe1a5937f6202f929a2cc8ef4ee5b9782db68b0d5799fd8f09e16"));
+    let expected = [
+        ("c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346", "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067"),
+        ("3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b", "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6"),
+        ("bac54083f293f1fe08e4a70137260aa90783a5cb84d3f35848b324d0674b0e3a", "4436476085d4c3c4508b60fcf4389c40176adce756b398bdee27bca19758d828"),
+        ("e2167bc785333a37aa562f021f1e881defb853839babf52a7f72b102e41890e9", "f2401dd95cc35867ffed4f367cd564763719fbc6a53e969fb8496a1e6685d873"),
+        ("e3c8d35aaaf0b9b647e88a0a0a7ee5d5bed5ad38238152e4e6fd8c1f8cb7c998", "8446eeb6181bf12f56a9d24e262221cc2f0c4725c7e3803024b5888ee5823aa6"),
+    ];
+    for (msg, (x, y)) in messages().iter().zip(expected) {
+        assert_eq!(params.hash_to_curve(msg, dst).unwrap(), Point::new(hex(x), hex(y)));
+    }
+}
+
+#[test]
+fn p256_xmd_sha256_sswu_ro_vectors() {
+    let params = CurveParams::secp256r1();
+    assert_eq!(Suite::for_curve(&params.curve), Some(Suite::P256));
+    let dst = b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_";
+    let expected = [
+        ("2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4", "8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415"),
+        ("0bb8b87485551aa43ed54f009230450b492fead5f1cc91658775dac4a3388a0f", "5c41b3d0731a27a7b14bc0bf0ccded2d8751f83493404c84a88e71ffd424212e"),
+        ("65038ac8f2b1def042a5df0b33b1f4eca6bff7cb0f9c6c1526811864e544ed80", "cad44d40a656e7aff4002a8de287abc8ae0482b5ae825822bb870d6df9b56ca3"),
+    ];
+    for (msg, (x, y)) in messages().iter().zip(expected) {
+        assert_eq!(params.hash_to_curve(msg, dst).unwrap(), Point::new(hex(x), hex(y)));
+    }
+}
+
+#[test]
+fn try_and_increment_lands_in_the_prime_order_subgroup() {
+    for params in [CurveParams::e17(), CurveParams::e17_order_15()] {
+        assert_eq!(Suite::for_curve(&params.curve), None);
+        let dst = b"mpc-zk-paillier-test";
+        for msg in messages() {
+            let point = params.hash_to_curve(&msg, dst).unwrap();
+            assert!(params.curve.is_point(&point));
+            assert!(!point.is_infinity());
+            assert!(params.curve.scalar_mul(&point, &params.n).is_infinity());
+            assert_eq!(hash_to_curve(&params.curve, &params.h, &msg, dst).unwrap(), point);
+        }
+    }
+}
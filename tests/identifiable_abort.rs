@@ -0,0 +1,185 @@
+use mpc_zk_paillier::arithmetic::basic_op;
+use mpc_zk_paillier::curve::ecc::{CurveParams, Point};
+use mpc_zk_paillier::mpc_wallet::{
+    ecdsa_mpc, paillier_primes, proof_transcript, verify_key_share, verify_nonce, verify_paillier_key,
+    verify_partial_signature, Abort, Check, Evidence, KeyShareMsg, NonceMsg, PaillierKeyMsg, PartialSignatureMsg,
+    PartialSignatureOpening, Round,
+};
+use mpc_zk_paillier::paillier::{cipher_paillier_with_r, gen_key_paillier, PaillierKey};
+use mpc_zk_paillier::zk::paillier_dlog::{self, PaillierDlogStatement};
+use mpc_zk_paillier::zk::schnorr;
+use num_bigint::BigInt;
+
+fn point(x: i64, y: i64) -> Point {
+    Point::new(BigInt::from(x), BigInt::from(y))
+}
+
+fn assert_blames(abort: Abort, params: &CurveParams, culprit: usize, round: Round, check: Check) {
+    assert_eq!(abort.culprit, culprit);
+    assert_eq!(abort.round, round);
+    assert_eq!(abort.check, check);
+    assert!(abort.verify(params), "{}", abort);
+}
+
+// X = x G with a proof made for the given round and sender
+fn proven(params: &CurveParams, round: Round, party: usize) -> (Point, schnorr::SchnorrProof) {
+    let keys = params.gen_key_pair();
+    let proof = schnorr::prove(&params.curve, &params.point_g, &params.n, &keys.sk, &mut proof_transcript(params, round, party));
+    (keys.pk, proof)
+}
+
+#[test]
+fn malformed_key_shares_are_blamed_on_their_sender() {
+    let params = CurveParams::e17();
+    let (point_q, proof) = proven(&params, Round::KeyShare, 2);
+    assert_eq!(verify_key_share(&params, &KeyShareMsg { party: 2, point_q: point_q.clone(), proof: proof.clone() }), Ok(()));
+
+    let off_curve = KeyShareMsg { party: 2, point_q: point(1, 1), proof: proof.clone() };
+    assert_blames(verify_key_share(&params, &off_curve).unwrap_err(), &params, 2, Round::KeyShare, Check::PointOnCurve);
+    let infinity = KeyShareMsg { party: 2, point_q: Point::Infinity, proof: proof.clone() };
+    assert_blames(verify_key_share(&params, &infinity).unwrap_err(), &params, 2, Round::KeyShare, Check::PointNotInfinity);
+    // the proof of Part 2 replayed by Part 1, on a curve where it cannot pass by chance
+    let params = CurveParams::secp256k1();
+    let (point_q, proof) = proven(&params, Round::KeyShare, 2);
+    let replayed = KeyShareMsg { party: 1, point_q, proof };
+    assert_blames(verify_key_share(&params, &replayed).unwrap_err(), &params, 1, Round::KeyShare, Check::ProofOfKnowledge);
+}
+
+#[test]
+fn key_shares_outside_the_subgroup_are_blamed_on_their_sender() {
+    // 15 points and n = 5: any point of order 3 or 15 is on the curve but outside <G>
+    let params = CurveParams::e17_order_15();
+    let (_, proof) = proven(&params, Round::KeyShare, 2);
+    let outside = (0..17)
+        .flat_map(|x| (0..17).map(move |y| point(x, y)))
+        .find(|p| params.curve.is_point(p) && !params.curve.scalar_mul(p, &params.n).is_infinity())
+        .unwrap();
+    let msg = KeyShareMsg { party: 2, point_q: outside, proof };
+    assert_blames(verify_key_share(&params, &msg).unwrap_err(), &params, 2, Round::KeyShare, Check::PointInSubgroup);
+    // a point of <G> is not evidence against its sender
+    let abort = Abort {
+        culprit: 2,
+        round: Round::KeyShare,
+        check: Check::PointInSubgroup,
+        evidence: Box::new(Evidence::Point(params.point_g.clone())),
+    };
+    assert!(!abort.verify(&params));
+}
+
+#[test]
+fn malformed_nonces_are_blamed_on_their_sender() {
+    let params = CurveParams::e17();
+    let (point_r, proof) = proven(&params, Round::Nonce, 1);
+    assert_eq!(verify_nonce(&params, &NonceMsg { party: 1, point_r: point_r.clone(), proof: proof.clone() }), Ok(()));
+
+    let off_curve = NonceMsg { party: 1, point_r: point(1, 1), proof: proof.clone() };
+    assert_blames(verify_nonce(&params, &off_curve).unwrap_err(), &params, 1, Round::Nonce, Check::PointOnCurve);
+    // a key share proof does not prove a nonce
+    let params = CurveParams::secp256k1();
+    let (point_r, proof) = proven(&params, Round::KeyShare, 1);
+    let wrong_round = NonceMsg { party: 1, point_r, proof };
+    assert_blames(verify_nonce(&params, &wrong_round).unwrap_err(), &params, 1, Round::Nonce, Check::ProofOfKnowledge);
+}
+
+// Party 1's Paillier message for c_key = Enc(plaintext; 7), proven against Q1 = d1 G
+fn paillier_key_msg(params: &CurveParams, key: &PaillierKey, d1: &BigInt, plaintext: &BigInt) -> (PaillierKeyMsg, Point) {
+    let point_q = params.mul_g(d1);
+    let randomness = BigInt::from(7);
+    let statement = PaillierDlogStatement {
+        public_key: key.public_key.clone(),
+        c_key: cipher_paillier_with_r(&key.public_key, plaintext, &randomness),
+        point_x: point_q.clone(),
+    };
+    let mut transcript = proof_transcript(params, Round::PaillierKey, 1);
+    let proof = paillier_dlog::prove(&params.curve, &params.point_g, &params.n, &statement, plaintext, &randomness, &mut transcript);
+    let msg = PaillierKeyMsg { party: 1, public_key: statement.public_key, c_key: statement.c_key, proof };
+    (msg, point_q)
+}
+
+#[test]
+fn malformed_ciphertexts_are_blamed_on_their_sender() {
+    let params = CurveParams::e17();
+    let (p, q) = paillier_primes(&params.n);
+    let key = gen_key_paillier(&p, &q);
+    let n = key.public_key.1.clone();
+    for c3 in [BigInt::from(0), n.clone(), &n * &n, BigInt::from(3)] {
+        let msg = PartialSignatureMsg { party: 2, c3 };
+        let abort = verify_partial_signature(&msg, &key.public_key).unwrap_err();
+        assert_blames(abort, &params, 2, Round::PartialSignature, Check::CiphertextInRange);
+    }
+    let d1 = BigInt::from(4);
+    let (mut msg, point_q) = paillier_key_msg(&params, &key, &d1, &d1);
+    msg.c_key = BigInt::from(11);
+    assert_blames(verify_paillier_key(&params, &msg, &point_q).unwrap_err(), &params, 1, Round::PaillierKey, Check::CiphertextInRange);
+}
+
+#[test]
+fn a_c_key_that_does_not_encrypt_d1_is_blamed_on_part_1() {
+    let params = CurveParams::secp256k1();
+    let (p, q) = paillier_primes(&params.n);
+    let key = gen_key_paillier(&p, &q);
+    let d1 = basic_op::random_scalar(&params.n);
+    let (msg, point_q) = paillier_key_msg(&params, &key, &d1, &d1);
+    assert_eq!(verify_paillier_key(&params, &msg, &point_q), Ok(()));
+    // the proof is bound to Q1: another share does not match it
+    let other = params.gen_key_pair().pk;
+    assert_blames(verify_paillier_key(&params, &msg, &other).unwrap_err(), &params, 1, Round::PaillierKey, Check::EncryptedShare);
+    // an encryption of d1 + 1, proven as if it were d1
+    let (wrong, point_q) = paillier_key_msg(&params, &key, &d1, &(&d1 + 1));
+    assert_blames(verify_paillier_key(&params, &wrong, &point_q).unwrap_err(), &params, 1, Round::PaillierKey, Check::EncryptedShare);
+    // d1 + n 2^100 is d1 modulo n, but c_key^v would wrap modulo N: the proof bounds the plaintext
+    let huge = &d1 + (&params.n << 100);
+    let (wrapped, point_q) = paillier_key_msg(&params, &key, &d1, &huge);
+    assert_blames(verify_paillier_key(&params, &wrapped, &point_q).unwrap_err(), &params, 1, Round::PaillierKey, Check::EncryptedShare);
+}
+
+#[test]
+fn a_failed_partial_signature_is_blamed_on_part_2() {
+    // c_key is proven to encrypt d1, and Part 1 opens a c3 whose signature does not verify
+    let params = CurveParams::secp256k1();
+    let (p, q) = paillier_primes(&params.n);
+    let key = gen_key_paillier(&p, &q);
+    let d1 = basic_op::random_scalar(&params.n);
+    let (msg, point_q1) = paillier_key_msg(&params, &key, &d1, &d1);
+    let (s_prime, randomness, k1) = (basic_op::random_scalar(&params.n), BigInt::from(7), basic_op::random_scalar(&params.n));
+    let point_r2 = params.gen_key_pair().pk;
+    let opening = PartialSignatureOpening {
+        c3: cipher_paillier_with_r(&key.public_key, &s_prime, &randomness),
+        key_share: PaillierDlogStatement { public_key: key.public_key.clone(), c_key: msg.c_key.clone(), point_x: point_q1 },
+        key_share_proof: msg.proof.clone(),
+        s_prime,
+        randomness,
+        point_r1: params.mul_g(&k1),
+        k1,
+        point_q: params.curve.scalar_mul(&point_r2, &d1),
+        point_r2,
+        hash: basic_op::random_scalar(&params.n),
+    };
+    let abort = Abort {
+        culprit: 2,
+        round: Round::PartialSignature,
+        check: Check::PartialSignatureOpening,
+        evidence: Box::new(Evidence::PartialSignature(Box::new(opening.clone()))),
+    };
+    assert!(abort.verify(&params));
+    assert!(abort.to_string().starts_with("Part 2 misbehaved"));
+    // only Part 2 sends c3
+    assert!(!Abort { culprit: 1, ..abort.clone() }.verify(&params));
+    // without a valid proof on c_key the failure could be Part 1's
+    let mut unproven = opening.clone();
+    unproven.key_share_proof.response += 1;
+    let evidence = Box::new(Evidence::PartialSignature(Box::new(unproven)));
+    assert!(!Abort { evidence, ..abort.clone() }.verify(&params));
+    // an opening that does not match c3
+    let mut misopened = opening;
+    misopened.s_prime += 1;
+    let evidence = Box::new(Evidence::PartialSignature(Box::new(misopened)));
+    assert!(!Abort { evidence, ..abort }.verify(&params));
+}
+
+#[test]
+fn an_honest_run_signs() {
+    let params = CurveParams::e17();
+    let (r, s) = ecdsa_mpc(&params).unwrap();
+    assert!(r > BigInt::from(0) && s > BigInt::from(0));
+}
@@ -0,0 +1,68 @@
+// the point at infinity is the identity of every operation, and (0, 0) is an ordinary point
+use mpc_zk_paillier::curve::ecc::{CurveParams, EcWei, Point};
+use num_bigint::BigInt;
+
+fn point(x: i64, y: i64) -> Point {
+    Point::new(BigInt::from(x), BigInt::from(y))
+}
+
+#[test]
+fn infinity_is_the_identity_of_addition() {
+    for params in [CurveParams::e17(), CurveParams::secp256k1()] {
+        let new_ec = &params.curve;
+        let point_g = &params.point_g;
+        assert!(new_ec.is_point(&Point::Infinity));
+        assert_eq!(new_ec.point_add(&Point::Infinity, &Point::Infinity), Point::Infinity);
+        assert_eq!(new_ec.point_add(point_g, &Point::Infinity), *point_g);
+        assert_eq!(new_ec.point_add(&Point::Infinity, point_g), *point_g);
+        assert_eq!(new_ec.negate(&Point::Infinity), Point::Infinity);
+        assert_eq!(new_ec.point_add(point_g, &new_ec.negate(point_g)), Point::Infinity);
+        assert_eq!(Point::Infinity.order(new_ec), BigInt::from(1));
+    }
+}
+
+#[test]
+fn multiplications_reach_infinity_at_the_order() {
+    for params in [CurveParams::e17(), CurveParams::secp256k1()] {
+        let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+        let muls: [fn(&EcWei, &Point, &BigInt) -> Point; 3] = [EcWei::scalar_mul, EcWei::scalar_mul_ct, EcWei::scalar_mul_wnaf];
+        for mul in muls {
+            assert_eq!(mul(new_ec, point_g, &BigInt::from(0)), Point::Infinity);
+            assert_eq!(mul(new_ec, point_g, n), Point::Infinity);
+            assert_eq!(mul(new_ec, point_g, &(n * 3)), Point::Infinity);
+            assert_eq!(mul(new_ec, &Point::Infinity, &BigInt::from(5)), Point::Infinity);
+            assert_eq!(mul(new_ec, point_g, &(n + 1)), *point_g);
+        }
+        assert_eq!(params.mul_g(&BigInt::from(0)), Point::Infinity);
+        assert_eq!(params.mul_g(n), Point::Infinity);
+        assert_eq!(new_ec.multi_scalar_mul(&[]), Point::Infinity);
+        assert_eq!(new_ec.multi_scalar_mul(&[(Point::Infinity, BigInt::from(3))]), Point::Infinity);
+        assert_eq!(new_ec.multi_scalar_mul(&[(point_g.clone(), BigInt::from(2)), (point_g.clone(), BigInt::from(-2))]), Point::Infinity);
+    }
+}
+
+#[test]
+fn points_of_order_two_double_to_infinity() {
+    // y^2 = x^3 + x over F_17: (0, 0) is an affine point of order 2, not the identity
+    let new_ec = EcWei::new(BigInt::from(1), BigInt::from(0), BigInt::from(17)).unwrap();
+    let origin = point(0, 0);
+    assert!(new_ec.is_point(&origin));
+    assert!(!origin.is_infinity());
+    assert_eq!(origin.order(&new_ec), BigInt::from(2));
+    assert_eq!(new_ec.negate(&origin), origin);
+    assert_eq!(new_ec.point_add(&origin, &origin), Point::Infinity);
+    assert_eq!(new_ec.scalar_mul(&origin, &BigInt::from(2)), Point::Infinity);
+    assert_eq!(new_ec.scalar_mul_ct(&origin, &BigInt::from(2)), Point::Infinity);
+    assert_eq!(new_ec.scalar_mul_wnaf(&origin, &BigInt::from(3)), origin);
+    assert_eq!(new_ec.point_add(&origin, &Point::Infinity), origin);
+}
+
+#[test]
+fn infinity_has_no_coordinates() {
+    assert_eq!(Point::Infinity.x(), None);
+    assert_eq!(Point::Infinity.y(), None);
+    assert_eq!(Point::Infinity.to_string(), "∞");
+    assert_eq!(point(0, 0).x(), Some(&BigInt::from(0)));
+    assert_eq!(point(0, 0).to_string(), "(0, 0)");
+    assert_ne!(point(0, 0), Point::Infinity);
+}
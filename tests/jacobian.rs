@@ -0,0 +1,74 @@
+// scalar_mul works in Jacobian coordinates; it must agree with a plain double-and-add on
+// the affine point_add for every point and scalar, including ∞, P + P and P + (-P)
+use mpc_zk_paillier::arithmetic::basic_op;
+use mpc_zk_paillier::curve::ecc::{CurveModel, CurveParams, EcWei, Point};
+use num_bigint::BigInt;
+
+// the reference: left-to-right double-and-add with affine additions only
+fn affine_mul(new_ec: &EcWei, point: &Point, k: &BigInt) -> Point {
+    let mut acc = Point::Infinity;
+    for i in (0..k.bits()).rev() {
+        acc = new_ec.point_add(&acc, &acc);
+        if k.bit(i) {
+            acc = new_ec.point_add(&acc, point);
+        }
+    }
+    acc
+}
+
+#[test]
+fn every_multiple_matches_on_small_curves() {
+    // e17 (prime order 11), e17-15 (cyclic of order 15) and y^2 = x^3 + x, whose points
+    // of order 2 have y = 0 and double to ∞
+    let curves = [
+        CurveParams::e17().curve,
+        CurveParams::e17_order_15().curve,
+        EcWei::new(BigInt::from(1), BigInt::from(0), BigInt::from(17)).unwrap(),
+    ];
+    for new_ec in curves {
+        let mut points = new_ec.group_points();
+        points.push(Point::Infinity);
+        let order = u64::try_from(new_ec.order()).unwrap();
+        for point in &points {
+            let mut repeated = Point::Infinity;
+            for k in 0..=2 * order + 1 {
+                let k = BigInt::from(k);
+                assert_eq!(new_ec.scalar_mul(point, &k), repeated, "{} * {}", k, point);
+                assert_eq!(affine_mul(&new_ec, point, &k), repeated, "{} * {}", k, point);
+                repeated = new_ec.point_add(&repeated, point);
+            }
+        }
+    }
+}
+
+#[test]
+fn random_multiples_match_on_secp256k1() {
+    let params = CurveParams::secp256k1();
+    let (new_ec, n) = (&params.curve, &params.n);
+    let mut points = vec![params.point_g.clone(), new_ec.negate(&params.point_g), Point::Infinity];
+    points.extend((0..2).map(|_| params.gen_key_pair().pk));
+    let mut scalars: Vec<BigInt> = [0, 1, 2, 3].into_iter().map(BigInt::from).collect();
+    scalars.extend([n - 1, n.clone(), n + 1, 2 * n - 1]);
+    scalars.extend((0..2).map(|_| basic_op::random_scalar(n)));
+    for point in &points {
+        for k in &scalars {
+            assert_eq!(new_ec.scalar_mul(point, k), affine_mul(new_ec, point, k), "{} * {}", k, point);
+        }
+    }
+}
+
+#[test]
+fn doubling_matches_addition_to_itself() {
+    let params = CurveParams::secp256k1();
+    let new_ec = &params.curve;
+    let two = BigInt::from(2);
+    for _ in 0..8 {
+        let point = params.gen_key_pair().pk;
+        let doubled = new_ec.point_add(&point, &point);
+        assert_eq!(new_ec.scalar_mul(&point, &two), doubled);
+        assert_eq!(new_ec.scalar_mul(&point, &BigInt::from(4)), new_ec.point_add(&doubled, &doubled));
+        // 2P - P through the Jacobian chain of 3P = 2P + P, minus 2P
+        let tripled = new_ec.scalar_mul(&point, &BigInt::from(3));
+        assert_eq!(new_ec.point_add(&tripled, &new_ec.negate(&doubled)), point);
+    }
+}
@@ -0,0 +1,124 @@
+// multi_scalar_mul against a sum of single multiplications, on both of its paths (Straus
+// below 80 terms, Pippenger from 80 on), and the batch verifiers built on it
+use mpc_zk_paillier::arithmetic::basic_op;
+use mpc_zk_paillier::curve::ecc::{CurveParams, EcWei, Point};
+use mpc_zk_paillier::zk::dleq;
+use mpc_zk_paillier::zk::schnorr::{self, SchnorrProof};
+use mpc_zk_paillier::zk::transcript::Transcript;
+use num_bigint::BigInt;
+
+// sum(k_i * P_i) one term at a time, with -k * P = k * (-P)
+fn naive(new_ec: &EcWei, terms: &[(Point, BigInt)]) -> Point {
+    terms.iter().fold(Point::Infinity, |acc, (point, k)| {
+        let term = if k < &BigInt::from(0) {
+            new_ec.negate(&new_ec.scalar_mul(point, &-k))
+        } else {
+            new_ec.scalar_mul(point, k)
+        };
+        new_ec.point_add(&acc, &term)
+    })
+}
+
+// terms with zero, negative and large scalars and with ∞ among the points
+fn terms(params: &CurveParams, len: usize) -> Vec<(Point, BigInt)> {
+    let n = &params.n;
+    (0..len)
+        .map(|i| {
+            let point = if i % 7 == 3 { Point::Infinity } else { params.gen_key_pair().pk };
+            let k = match i % 5 {
+                0 => BigInt::from(0),
+                1 => -basic_op::random_scalar(n),
+                2 => basic_op::random_scalar(n) + n,
+                _ => basic_op::random_scalar(n),
+            };
+            (point, k)
+        })
+        .collect()
+}
+
+#[test]
+fn straus_and_pippenger_match_the_naive_sum() {
+    for params in [CurveParams::e17(), CurveParams::secp256k1()] {
+        let new_ec = &params.curve;
+        for len in [1, 2, 5, 79, 80, 130] {
+            let terms = terms(&params, len);
+            assert_eq!(new_ec.multi_scalar_mul(&terms), naive(new_ec, &terms), "{} terms on {}", len, params.name);
+        }
+    }
+}
+
+#[test]
+fn degenerate_sums_give_infinity() {
+    let params = CurveParams::secp256k1();
+    let (new_ec, point_g) = (&params.curve, &params.point_g);
+    assert_eq!(new_ec.multi_scalar_mul(&[]), Point::Infinity);
+    assert_eq!(new_ec.multi_scalar_mul(&[(point_g.clone(), BigInt::from(0))]), Point::Infinity);
+    assert_eq!(new_ec.multi_scalar_mul(&[(point_g.clone(), params.n.clone())]), Point::Infinity);
+    // k P + (-k) P on both paths
+    for len in [10, 100] {
+        let half: Vec<(Point, BigInt)> = terms(&params, len / 2);
+        let mut cancelling = half.clone();
+        cancelling.extend(half.into_iter().map(|(point, k)| (point, -k)));
+        assert_eq!(new_ec.multi_scalar_mul(&cancelling), Point::Infinity, "{} terms", len);
+    }
+    let infinities = vec![(Point::Infinity, BigInt::from(5)); 90];
+    assert_eq!(new_ec.multi_scalar_mul(&infinities), Point::Infinity);
+}
+
+#[test]
+fn schnorr_batches_with_one_bad_proof_are_rejected() {
+    let params = CurveParams::secp256k1();
+    let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+    let transcript = Transcript::new(b"batch-test");
+    let statements: Vec<(Point, SchnorrProof)> = (0..6)
+        .map(|_| {
+            let keys = params.gen_key_pair();
+            (keys.pk, schnorr::prove(new_ec, point_g, n, &keys.sk, &mut transcript.clone()))
+        })
+        .collect();
+    assert!(schnorr::batch_verify(new_ec, point_g, n, &statements, &transcript));
+    for i in [0, 5] {
+        let mut bad = statements.clone();
+        bad[i].1.response = (&bad[i].1.response + 1) % n;
+        assert!(!schnorr::batch_verify(new_ec, point_g, n, &bad, &transcript));
+        let mut swapped = statements.clone();
+        swapped[i].0 = statements[(i + 1) % 6].0.clone();
+        assert!(!schnorr::batch_verify(new_ec, point_g, n, &swapped, &transcript));
+    }
+    // two errors that would cancel in an unweighted sum
+    let mut cancelling = statements.clone();
+    cancelling[1].1.response = (&cancelling[1].1.response + 1) % n;
+    cancelling[2].1.response = (&cancelling[2].1.response - 1 + n) % n;
+    assert!(!schnorr::batch_verify(new_ec, point_g, n, &cancelling, &transcript));
+    assert!(!schnorr::batch_verify(new_ec, point_g, n, &statements, &Transcript::new(b"other")));
+}
+
+#[test]
+fn dleq_batches_with_one_bad_pair_are_rejected() {
+    let params = CurveParams::secp256k1();
+    let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+    let secret = basic_op::random_scalar(n);
+    let point_x = params.mul_g(&secret);
+    let pairs: Vec<(Point, Point)> = (0..5)
+        .map(|_| {
+            let point_h = params.gen_key_pair().pk;
+            let point_y = new_ec.scalar_mul(&point_h, &secret);
+            (point_h, point_y)
+        })
+        .collect();
+    let transcript = Transcript::new(b"batch-test");
+    let proof = dleq::prove_batch(new_ec, n, point_g, &pairs, &secret, &mut transcript.clone());
+    assert!(dleq::verify_batch(new_ec, n, point_g, &point_x, &pairs, &proof, &mut transcript.clone()));
+    for i in [0, 4] {
+        let mut bad = pairs.clone();
+        bad[i].1 = new_ec.point_add(&bad[i].1, point_g);
+        assert!(!dleq::verify_batch(new_ec, n, point_g, &point_x, &bad, &proof, &mut transcript.clone()));
+    }
+    // a pair with another secret, proven honestly for the others
+    let mut mixed = pairs.clone();
+    mixed[2].1 = new_ec.scalar_mul(&mixed[2].0, &(&secret + 1));
+    let forged = dleq::prove_batch(new_ec, n, point_g, &mixed, &secret, &mut transcript.clone());
+    assert!(!dleq::verify_batch(new_ec, n, point_g, &point_x, &mixed, &forged, &mut transcript.clone()));
+    assert!(!dleq::verify_batch(new_ec, n, point_g, &point_x, &pairs[..4], &proof, &mut transcript.clone()));
+    assert!(!dleq::verify_batch(new_ec, n, point_g, &point_x, &[], &proof, &mut transcript.clone()));
+}
@@ -0,0 +1,83 @@
+// group orders and point orders on the registry curves and on curves counted from scratch
+use mpc_zk_paillier::arithmetic::basic_op;
+use mpc_zk_paillier::curve::ecc::{CurveModel, CurveParams, EcWei, Point};
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+#[test]
+fn registry_generators_have_the_recorded_order() {
+    let registry = [
+        CurveParams::e17(),
+        CurveParams::e17_order_15(),
+        CurveParams::secp256k1(),
+        CurveParams::secp256r1(),
+        CurveParams::secp384r1(),
+    ];
+    for params in registry {
+        let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+        assert_eq!(new_ec.order(), n * &params.h, "{}", params.name);
+        assert_eq!(point_g.order(new_ec), *n, "{}", params.name);
+        assert_eq!(new_ec.scalar_mul(point_g, n), Point::Infinity);
+        assert_eq!(new_ec.scalar_mul(point_g, &(n - 1)), new_ec.negate(point_g));
+    }
+}
+
+#[test]
+fn toy_curves_have_the_expected_point_orders() {
+    // e17 has prime order 11: every affine point generates the group
+    let params = CurveParams::e17();
+    let points = params.curve.group_points();
+    assert_eq!(points.len(), 10);
+    assert!(points.iter().all(|point| point.order(&params.curve) == BigInt::from(11)));
+
+    // Z_15 has phi(d) elements of order d for each d | 15
+    let params = CurveParams::e17_order_15();
+    let mut counts = [0usize; 16];
+    for point in params.curve.group_points() {
+        counts[usize::try_from(point.order(&params.curve)).unwrap()] += 1;
+    }
+    assert_eq!((counts[3], counts[5], counts[15]), (2, 4, 8));
+    assert_eq!(counts.iter().sum::<usize>(), 14);
+}
+
+#[test]
+fn counted_orders_annihilate_random_points() {
+    // no recorded order: #E comes from the point counting, and every point order divides it
+    let mut p = BigInt::from(1) << 40;
+    while !basic_op::is_probable_prime(&p) {
+        p += 1;
+    }
+    for (a, b) in [(2, 3), (-3, 5), (0, 7)] {
+        let reduce = |x: i64| ((BigInt::from(x) % &p) + &p) % &p;
+        let new_ec = EcWei::new(reduce(a), reduce(b), p.clone()).unwrap();
+        let order = new_ec.order();
+        assert!(new_ec.point_count().satisfies_hasse(&p));
+        for _ in 0..4 {
+            let point = random_point(&new_ec, &p);
+            assert_eq!(new_ec.scalar_mul(&point, &order), Point::Infinity);
+            let point_order = point.order(&new_ec);
+            assert!((&order % &point_order).is_zero());
+            assert_eq!(new_ec.scalar_mul(&point, &point_order), Point::Infinity);
+        }
+    }
+}
+
+fn random_point(new_ec: &EcWei, p: &BigInt) -> Point {
+    loop {
+        let x = basic_op::random_scalar(p);
+        let rhs = (&x * &x * &x + new_ec.a() * &x + new_ec.b()) % p;
+        if let Some(y) = basic_op::sqrt_mod(&((rhs + p) % p), p) {
+            return Point::new(x, y);
+        }
+    }
+}
+
+#[test]
+fn the_twelve_base_pseudoprime_is_composite() {
+    // psi_12 passes Miller-Rabin for every prime base up to 37
+    let psi_12: BigInt = "318665857834031151167461".parse().unwrap();
+    assert!(!basic_op::is_probable_prime(&psi_12));
+    let (p, q) = (BigInt::from(399165290221u64), BigInt::from(798330580441u64));
+    assert_eq!(&p * &q, psi_12);
+    assert_eq!(basic_op::factorize(&psi_12), vec![(p, 1), (q, 1)]);
+}
@@ -0,0 +1,45 @@
+// the proof that a Paillier ciphertext encrypts the discrete logarithm of a point
+use mpc_zk_paillier::arithmetic::basic_op;
+use mpc_zk_paillier::curve::ecc::CurveParams;
+use mpc_zk_paillier::mpc_wallet::paillier_primes;
+use mpc_zk_paillier::paillier::{cipher_paillier_with_r, gen_key_paillier};
+use mpc_zk_paillier::zk::paillier_dlog::{self, PaillierDlogStatement};
+use mpc_zk_paillier::zk::transcript::Transcript;
+use num_bigint::BigInt;
+
+#[test]
+fn proofs_verify_and_reject_tampering() {
+    for params in [CurveParams::e17(), CurveParams::secp256k1()] {
+        let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+        let (p, q) = paillier_primes(n);
+        let key = gen_key_paillier(&p, &q);
+        let x = basic_op::random_scalar(n);
+        let randomness = BigInt::from(7);
+        let statement = PaillierDlogStatement {
+            public_key: key.public_key.clone(),
+            c_key: cipher_paillier_with_r(&key.public_key, &x, &randomness),
+            point_x: params.mul_g(&x),
+        };
+        let transcript = Transcript::new(b"paillier-dlog-test");
+        let proof = paillier_dlog::prove(new_ec, point_g, n, &statement, &x, &randomness, &mut transcript.clone());
+        assert!(paillier_dlog::verify(new_ec, point_g, n, &statement, &proof, &mut transcript.clone()), "{}", params.name);
+
+        let mut bad = proof.clone();
+        bad.randomness_response = (&bad.randomness_response + 1) % &key.public_key.1;
+        assert!(!paillier_dlog::verify(new_ec, point_g, n, &statement, &bad, &mut transcript.clone()));
+        let mut bad = proof.clone();
+        bad.response = -bad.response;
+        assert!(!paillier_dlog::verify(new_ec, point_g, n, &statement, &bad, &mut transcript.clone()));
+        let mut bad = proof.clone();
+        bad.response = paillier_dlog::response_bound(n);
+        assert!(!paillier_dlog::verify(new_ec, point_g, n, &statement, &bad, &mut transcript.clone()));
+        // a challenge from another transcript or statement matches with probability 1 / n,
+        // so these only fail reliably on a large curve
+        if params.name == "secp256k1" {
+            assert!(!paillier_dlog::verify(new_ec, point_g, n, &statement, &proof, &mut Transcript::new(b"other")));
+            let mut other = statement.clone();
+            other.point_x = new_ec.point_add(&other.point_x, point_g);
+            assert!(!paillier_dlog::verify(new_ec, point_g, n, &other, &proof, &mut transcript.clone()));
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use mpc_zk_paillier::curve::ecc::{CurveParams, EcWei, Point};
+use mpc_zk_paillier::plot::{plot_addition, plot_curve, plot_orbit, wrapped_line};
+use num_bigint::BigInt;
+
+fn point(x: i64, y: i64) -> Point {
+    Point::new(BigInt::from(x), BigInt::from(y))
+}
+
+fn well_formed(svg: &str) {
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.ends_with("</svg>\n"));
+    assert!(!svg.contains("href"));
+}
+
+#[test]
+fn wrapped_lines_pass_through_the_points_of_the_line() {
+    let p = BigInt::from(17);
+    for slope in 0..17 {
+        let through = (BigInt::from(7), BigInt::from(9));
+        let segments = wrapped_line(&p, &BigInt::from(slope), (&through.0, &through.1));
+        // inside the square, and few pieces thanks to the slope in (-p/2, p/2]
+        assert!(segments.len() <= 10, "slope {}", slope);
+        for ((x1, y1), (x2, y2)) in &segments {
+            for v in [x1, y1, x2, y2] {
+                assert!((-1e-9..=17.0 + 1e-9).contains(v));
+            }
+        }
+        for x in 0..17 {
+            let y = (((slope * (x - 7) + 9) % 17) + 17) % 17;
+            let (x, y) = (x as f64, y as f64);
+            // a point may sit on the top edge, where y = p stands for y = 0
+            let on_a_segment = segments.iter().any(|((x1, y1), (x2, y2))| {
+                let drawn = y1 + (y2 - y1) * (x - x1) / (x2 - x1);
+                *x1 - 1e-9 <= x && x <= *x2 + 1e-9 && ((drawn - y).abs() < 1e-6 || (drawn - y - 17.0).abs() < 1e-6)
+            });
+            assert!(on_a_segment, "slope {} misses ({}, {})", slope, x, y);
+        }
+    }
+}
+
+#[test]
+fn e17_curve_plot() {
+    let svg = plot_curve(&CurveParams::e17().curve).unwrap();
+    well_formed(&svg);
+    assert_eq!(svg.matches("<circle class=\"point\"").count(), 10);
+    assert!(svg.contains("11 points (∞ not shown)"));
+    assert!(svg.contains("y² = x³ - 2x + 7 mod 17"));
+}
+
+#[test]
+fn addition_plots() {
+    let new_ec = CurveParams::e17().curve;
+    let chord = plot_addition(&new_ec, &point(7, 9), &point(10, 16)).unwrap();
+    well_formed(&chord);
+    assert!(chord.contains("(7, 9) + (10, 16) = (13, 11)"));
+    for label in [">A<", ">B<", ">-(A + B)<", ">A + B<"] {
+        assert!(chord.contains(label), "{}", label);
+    }
+    assert_eq!(chord.matches("class=\"reflection\"").count(), 1);
+
+    let tangent = plot_addition(&new_ec, &point(7, 9), &point(7, 9)).unwrap();
+    assert!(tangent.contains(">A = B<"));
+
+    // P + (-P): a single vertical line and no third point
+    let opposite = plot_addition(&new_ec, &point(7, 9), &point(7, 8)).unwrap();
+    assert!(opposite.contains("= ∞"));
+    assert_eq!(opposite.matches("<line class=\"line\"").count(), 1);
+    assert!(!opposite.contains("reflection\""));
+
+    assert!(plot_addition(&new_ec, &Point::Infinity, &point(7, 9)).is_err());
+    assert!(plot_addition(&new_ec, &point(1, 1), &point(7, 9)).is_err());
+}
+
+#[test]
+fn orbit_plot() {
+    let new_ec = CurveParams::e17().curve;
+    let svg = plot_orbit(&new_ec, &point(7, 9)).unwrap();
+    well_formed(&svg);
+    // the group has prime order 11: ten multiples joined by nine arrows
+    assert!(svg.contains("11P = ∞"));
+    assert_eq!(svg.matches("<line class=\"step\"").count(), 9);
+    assert_eq!(svg.matches("<circle class=\"marked\"").count(), 10);
+}
+
+#[test]
+fn large_fields_are_refused() {
+    let new_ec = EcWei::new(BigInt::from(2), BigInt::from(3), BigInt::from(1031)).unwrap();
+    assert!(plot_curve(&new_ec).is_err());
+    assert!(plot_curve(&CurveParams::secp256k1().curve).is_err());
+}
@@ -0,0 +1,113 @@
+use mpc_zk_paillier::arithmetic::basic_op;
+use mpc_zk_paillier::curve::ecc::{CountingMethod, CurveModel, CurveParams, EcWei};
+use num_bigint::BigInt;
+
+fn curve(a: i64, b: i64, p: &BigInt) -> EcWei {
+    let reduce = |x: i64| ((BigInt::from(x) % p) + p) % p;
+    EcWei::new(reduce(a), reduce(b), p.clone()).unwrap()
+}
+
+fn next_prime(mut n: BigInt) -> BigInt {
+    while !basic_op::is_probable_prime(&n) {
+        n += 1;
+    }
+    n
+}
+
+#[test]
+fn legendre_and_schoof_match_the_listed_group() {
+    for params in [CurveParams::e17(), CurveParams::e17_order_15()] {
+        let listed = BigInt::from(params.curve.group_points().len() + 1);
+        let fresh = EcWei::new(params.curve.a().clone(), params.curve.b().clone(), params.curve.p().clone()).unwrap();
+        let legendre = fresh.count_points_with(CountingMethod::Legendre).unwrap();
+        let schoof = fresh.count_points_with(CountingMethod::Schoof).unwrap();
+        assert_eq!(legendre.order, listed);
+        assert_eq!(schoof.order, listed);
+        assert_eq!(legendre.trace, params.curve.p() + 1 - &listed);
+    }
+}
+
+#[test]
+fn all_methods_agree() {
+    for p in [1009u32, 10007] {
+        let p = BigInt::from(p);
+        for (a, b) in [(0, 5), (3, 0), (2, 7), (-3, 4)] {
+            let new_ec = curve(a, b, &p);
+            let expected = new_ec.count_points_with(CountingMethod::Legendre).unwrap();
+            let mut methods = vec![CountingMethod::Mestre, CountingMethod::Schoof, CountingMethod::Sea];
+            if a == 0 || b == 0 {
+                methods.push(CountingMethod::ComplexMultiplication);
+            }
+            for method in methods {
+                let count = curve(a, b, &p).count_points_with(method).unwrap();
+                assert_eq!(count.order, expected.order, "{:?} on y^2 = x^3 + {}x + {} mod {}", method, a, b, p);
+                assert_eq!(count.method, method);
+            }
+        }
+    }
+}
+
+#[test]
+fn counts_satisfy_hasse_and_give_the_trace() {
+    let params = CurveParams::e17();
+    let count = params.curve.point_count();
+    assert_eq!(count.method, CountingMethod::Legendre);
+    assert_eq!(count.order, BigInt::from(11));
+    assert_eq!(count.trace, BigInt::from(7));
+    assert!(count.satisfies_hasse(params.curve.p()));
+    assert_eq!(params.curve.trace(), BigInt::from(7));
+}
+
+#[test]
+fn twist_order_is_p_plus_one_plus_t() {
+    let p = BigInt::from(1009);
+    let count = curve(2, 7, &p).point_count();
+    // d = 11 is a non-residue mod 1009: y^2 = x^3 + 2 d^2 x + 7 d^3
+    assert_eq!(basic_op::legendre(&BigInt::from(11), &p), -1);
+    let twist = curve(2 * 121, 7 * 1331, &p);
+    assert_eq!(twist.order(), count.twist_order());
+}
+
+#[test]
+fn sea_counts_a_72_bit_curve() {
+    let p = next_prime(BigInt::from(1) << 72);
+    let new_ec = EcWei::new(BigInt::from(2), BigInt::from(3), p.clone()).unwrap();
+    assert_eq!(CountingMethod::for_curve(&new_ec), CountingMethod::Sea);
+    let count = new_ec.count_points_with(CountingMethod::Sea).unwrap();
+    // cross-checked with baby-step giant-step
+    assert_eq!(count.order, BigInt::parse_bytes(b"4722366482893174952700", 10).unwrap());
+    assert!(count.satisfies_hasse(&p));
+    // the count is cached for order()
+    assert_eq!(new_ec.order(), count.order);
+}
+
+#[test]
+fn complex_multiplication_counts_secp256k1() {
+    let params = CurveParams::secp256k1();
+    let fresh = EcWei::new(params.curve.a().clone(), params.curve.b().clone(), params.curve.p().clone()).unwrap();
+    assert_eq!(CountingMethod::for_curve(&fresh), CountingMethod::ComplexMultiplication);
+    let count = fresh.point_count();
+    assert_eq!(count.order, &params.n * &params.h);
+    assert!(count.satisfies_hasse(params.curve.p()));
+}
+
+#[test]
+fn complex_multiplication_matches_mestre() {
+    let p = next_prime(BigInt::from(1) << 40);
+    for (a, b) in [(0, 7), (5, 0), (0, -2), (-1, 0)] {
+        let cm = curve(a, b, &p).count_points_with(CountingMethod::ComplexMultiplication).unwrap();
+        let mestre = curve(a, b, &p).count_points_with(CountingMethod::Mestre).unwrap();
+        assert_eq!(cm.order, mestre.order, "y^2 = x^3 + {}x + {}", a, b);
+    }
+}
+
+#[test]
+fn methods_refuse_what_they_cannot_count() {
+    let large = next_prime(BigInt::from(1) << 40);
+    assert!(curve(2, 3, &large).count_points_with(CountingMethod::Legendre).is_err());
+    assert!(curve(2, 3, &large).count_points_with(CountingMethod::ComplexMultiplication).is_err());
+    let small = BigInt::from(17);
+    assert!(curve(-2, 7, &small).count_points_with(CountingMethod::Mestre).is_err());
+    assert!(curve(-2, 7, &small).count_points_with(CountingMethod::Sea).is_err());
+    assert!(curve(1, 1, &BigInt::from(21)).count_points_with(CountingMethod::Schoof).is_err());
+}
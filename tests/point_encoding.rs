@@ -0,0 +1,86 @@
+// SEC1 point encoding: round trips on a large and a toy curve, and every malformed input
+use mpc_zk_paillier::curve::ecc::{CurveParams, EcWei, Point};
+use num_bigint::BigInt;
+
+fn point(x: i64, y: i64) -> Point {
+    Point::new(BigInt::from(x), BigInt::from(y))
+}
+
+#[test]
+fn points_round_trip_compressed_and_uncompressed() {
+    for params in [CurveParams::secp256k1(), CurveParams::e17()] {
+        let new_ec = &params.curve;
+        let field_len = new_ec.field_len();
+        let mut points = vec![Point::Infinity, params.point_g.clone(), new_ec.negate(&params.point_g)];
+        points.extend((0..8).map(|_| params.gen_key_pair().pk));
+        for point in points {
+            let compressed = new_ec.encode_point(&point, true).unwrap();
+            let uncompressed = new_ec.encode_point(&point, false).unwrap();
+            if point.is_infinity() {
+                assert_eq!((compressed.clone(), uncompressed.clone()), (vec![0x00], vec![0x00]));
+            } else {
+                assert_eq!(compressed.len(), 1 + field_len);
+                assert_eq!(uncompressed.len(), 1 + 2 * field_len);
+                assert_eq!(uncompressed[0], 0x04);
+                assert_eq!(compressed[0], if point.y().unwrap().bit(0) { 0x03 } else { 0x02 });
+            }
+            assert_eq!(new_ec.decode_point(&compressed), Ok(point.clone()));
+            assert_eq!(new_ec.decode_point(&uncompressed), Ok(point));
+        }
+    }
+    // the generator of secp256k1 as in SEC2
+    let params = CurveParams::secp256k1();
+    let bytes = params.curve.encode_point(&params.point_g, true).unwrap();
+    assert_eq!(bytes[..4], [0x02, 0x79, 0xbe, 0x66]);
+    assert_eq!(CurveParams::e17().curve.encode_point(&point(7, 8), false), Ok(vec![0x04, 7, 8]));
+}
+
+#[test]
+fn encode_rejects_points_without_an_encoding() {
+    let new_ec = CurveParams::e17().curve;
+    for compressed in [true, false] {
+        assert!(new_ec.encode_point(&point(-1, 500), compressed).is_err());
+        assert!(new_ec.encode_point(&point(1, 1), compressed).is_err());
+        // (7, 8) with coordinates shifted by p, and with a negative representative of y
+        assert!(new_ec.encode_point(&point(24, 8), compressed).is_err());
+        assert!(new_ec.encode_point(&point(7, -9), compressed).is_err());
+    }
+}
+
+#[test]
+fn decode_rejects_malformed_inputs() {
+    let params = CurveParams::secp256k1();
+    let new_ec = &params.curve;
+    let compressed = new_ec.encode_point(&params.point_g, true).unwrap();
+    let uncompressed = new_ec.encode_point(&params.point_g, false).unwrap();
+    assert!(new_ec.decode_point(&[]).is_err());
+    assert!(new_ec.decode_point(&compressed[..32]).is_err());
+    assert!(new_ec.decode_point(&uncompressed[..64]).is_err());
+    assert!(new_ec.decode_point(&[0x00, 0x00]).is_err());
+    let mut wrong_tag = compressed.clone();
+    for tag in [0x01, 0x05, 0x06, 0x07, 0xff] {
+        wrong_tag[0] = tag;
+        assert!(new_ec.decode_point(&wrong_tag).is_err());
+    }
+    // an uncompressed tag on a compressed body, and the reverse
+    let mut swapped = compressed.clone();
+    swapped[0] = 0x04;
+    assert!(new_ec.decode_point(&swapped).is_err());
+    let mut swapped = uncompressed.clone();
+    swapped[0] = 0x02;
+    assert!(new_ec.decode_point(&swapped).is_err());
+
+    let new_ec = CurveParams::e17().curve;
+    // x >= p, in both forms
+    assert!(new_ec.decode_point(&[0x02, 17]).is_err());
+    assert!(new_ec.decode_point(&[0x04, 24, 8]).is_err());
+    assert!(new_ec.decode_point(&[0x04, 7, 25]).is_err());
+    // (1, 1) is not on the curve, and x = 1 is no abscissa: 1 - 2 + 7 = 6 is not a square mod 17
+    assert!(new_ec.decode_point(&[0x04, 1, 1]).is_err());
+    assert!(new_ec.decode_point(&[0x02, 1]).is_err());
+
+    // y^2 = x^3 + x has the point (0, 0) of order 2, whose y is even
+    let new_ec = EcWei::new(BigInt::from(1), BigInt::from(0), BigInt::from(17)).unwrap();
+    assert_eq!(new_ec.decode_point(&[0x02, 0]), Ok(point(0, 0)));
+    assert!(new_ec.decode_point(&[0x03, 0]).is_err());
+}
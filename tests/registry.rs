@@ -0,0 +1,61 @@
+// the CurveParams registry: lookups by name and OID, and the domain parameters it returns
+use mpc_zk_paillier::curve::ecc::{CurveParams, EcWei, Point};
+use num_bigint::BigInt;
+use std::collections::HashSet;
+
+#[test]
+fn every_registered_curve_is_found_by_name() {
+    let all = CurveParams::all();
+    let names: HashSet<&str> = all.iter().map(|params| params.name).collect();
+    assert_eq!(names.len(), all.len());
+    for expected in ["secp256k1", "secp256r1", "secp384r1", "e17", "e17-15"] {
+        let params = CurveParams::from_name(expected).unwrap();
+        assert_eq!(params.name, expected);
+    }
+    for unknown in ["", "secp521r1", "P-256", "e17 "] {
+        assert!(CurveParams::from_name(unknown).is_none(), "{}", unknown);
+    }
+}
+
+#[test]
+fn sec2_curves_are_found_by_oid() {
+    for (oid, name) in [("1.3.132.0.10", "secp256k1"), ("1.2.840.10045.3.1.7", "secp256r1"), ("1.3.132.0.34", "secp384r1")] {
+        let params = CurveParams::from_oid(oid).unwrap();
+        assert_eq!(params.name, name);
+        assert_eq!(CurveParams::from_name(name).unwrap().oid, Some(oid));
+    }
+    // the teaching curves have no OID
+    assert_eq!(CurveParams::e17().oid, None);
+    assert_eq!(CurveParams::e17_order_15().oid, None);
+    assert!(CurveParams::from_oid("1.3.132.0.35").is_none());
+    assert!(CurveParams::from_oid("").is_none());
+}
+
+#[test]
+fn looked_up_parameters_match_the_constructors() {
+    for params in CurveParams::all() {
+        let found = CurveParams::from_name(params.name).unwrap();
+        assert_eq!(found.point_g, params.point_g);
+        assert_eq!((&found.n, &found.h), (&params.n, &params.h));
+        assert_eq!(found.curve.p(), params.curve.p());
+        assert!(params.curve.is_point(&params.point_g));
+        assert!(params.curve.scalar_mul(&params.point_g, &params.n).is_infinity());
+    }
+    let secp256k1 = CurveParams::from_name("secp256k1").unwrap();
+    assert_eq!(secp256k1.curve.b(), &BigInt::from(7));
+    assert_eq!(secp256k1.n.bits(), 256);
+    assert_eq!(CurveParams::from_name("secp384r1").unwrap().n.bits(), 384);
+}
+
+#[test]
+fn curves_outside_the_registry_stay_outside() {
+    // y^2 = x^3 + x over F_17 has 16 points; (1, 6) has order 4 and is not registered
+    let new_ec = EcWei::new(BigInt::from(1), BigInt::from(0), BigInt::from(17)).unwrap();
+    let point_g = Point::new(BigInt::from(1), BigInt::from(6));
+    assert!(new_ec.is_point(&point_g));
+    assert_eq!(point_g.order(&new_ec), BigInt::from(4));
+    let params = CurveParams::new("custom", new_ec, point_g, BigInt::from(4), BigInt::from(4));
+    assert_eq!(params.name, "custom");
+    assert_eq!(params.oid, None);
+    assert!(CurveParams::from_name("custom").is_none());
+}
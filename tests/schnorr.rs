@@ -0,0 +1,67 @@
+use mpc_zk_paillier::curve::ecc::{CurveParams, Point};
+use mpc_zk_paillier::zk::schnorr::{self, SchnorrProof};
+use mpc_zk_paillier::zk::transcript::Transcript;
+use num_bigint::BigInt;
+
+fn transcript() -> Transcript {
+    Transcript::new(b"schnorr-test")
+}
+
+#[test]
+fn proofs_verify_and_round_trip_through_bytes() {
+    for params in [CurveParams::e17(), CurveParams::secp256k1()] {
+        let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+        let keys = params.gen_key_pair();
+        let proof = schnorr::prove(new_ec, point_g, n, &keys.sk, &mut transcript());
+        assert!(schnorr::verify(new_ec, point_g, n, &keys.pk, &proof, &mut transcript()));
+
+        let bytes = proof.to_bytes(new_ec, n);
+        assert_eq!(bytes.len(), 1 + 2 * new_ec.p().bits().div_ceil(8) as usize + n.bits().div_ceil(8) as usize);
+        assert_eq!(SchnorrProof::from_bytes(new_ec, n, &bytes), Some(proof.clone()));
+        assert_eq!(SchnorrProof::from_bytes(new_ec, n, &bytes[1..]), None);
+        let mut extended = bytes.clone();
+        extended.push(0);
+        assert_eq!(SchnorrProof::from_bytes(new_ec, n, &extended), None);
+    }
+}
+
+#[test]
+fn from_bytes_rejects_points_off_the_curve_and_large_responses() {
+    let params = CurveParams::e17();
+    let (new_ec, n) = (&params.curve, &params.n);
+    let proof = SchnorrProof { commitment: Point::new(BigInt::from(1), BigInt::from(1)), response: BigInt::from(3) };
+    assert_eq!(SchnorrProof::from_bytes(new_ec, n, &proof.to_bytes(new_ec, n)), None);
+    let proof = SchnorrProof { commitment: params.point_g.clone(), response: n.clone() };
+    assert_eq!(SchnorrProof::from_bytes(new_ec, n, &proof.to_bytes(new_ec, n)), None);
+}
+
+#[test]
+fn verify_rejects_wrong_statements_proofs_and_transcripts() {
+    let params = CurveParams::secp256k1();
+    let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+    let keys = params.gen_key_pair();
+    let proof = schnorr::prove(new_ec, point_g, n, &keys.sk, &mut transcript());
+
+    assert!(!schnorr::verify(new_ec, point_g, n, &params.gen_key_pair().pk, &proof, &mut transcript()));
+    assert!(!schnorr::verify(new_ec, point_g, n, &keys.pk, &proof, &mut Transcript::new(b"other")));
+    let mut forged = proof.clone();
+    forged.response = (&forged.response + 1) % n;
+    assert!(!schnorr::verify(new_ec, point_g, n, &keys.pk, &forged, &mut transcript()));
+    let mut forged = proof.clone();
+    forged.commitment = Point::new(BigInt::from(1), BigInt::from(1));
+    assert!(!schnorr::verify(new_ec, point_g, n, &keys.pk, &forged, &mut transcript()));
+    assert!(!schnorr::verify(new_ec, point_g, n, &Point::new(BigInt::from(1), BigInt::from(1)), &proof, &mut transcript()));
+}
+
+#[test]
+fn the_interactive_protocol_accepts_honest_provers_only() {
+    let params = CurveParams::secp256k1();
+    let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+    let keys = params.gen_key_pair();
+    let (nonce, commitment) = schnorr::commit(new_ec, point_g, n);
+    let challenge = schnorr::random_challenge(n);
+    let response = schnorr::respond(n, &keys.sk, &nonce, &challenge);
+    assert!(schnorr::verify_transcript(new_ec, point_g, &keys.pk, &commitment, &challenge, &response));
+    let wrong = schnorr::respond(n, &(&keys.sk + 1), &nonce, &challenge);
+    assert!(!schnorr::verify_transcript(new_ec, point_g, &keys.pk, &commitment, &challenge, &wrong));
+}
@@ -0,0 +1,64 @@
+use mpc_zk_paillier::curve::ecc::{CurveParams, Point};
+use mpc_zk_paillier::zk::dleq::{DleqSigma, DleqStatement};
+use mpc_zk_paillier::zk::schnorr::SchnorrSigma;
+use mpc_zk_paillier::zk::sigma::{self, Or, OrWitness, SigmaProtocol};
+use mpc_zk_paillier::zk::transcript::Transcript;
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+#[test]
+fn simulated_or_branches_take_zero_like_real_ones() {
+    // on e17 (n = 11) a real challenge or response is 0 about once in 11 proofs
+    let params = CurveParams::e17();
+    let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+    let or = Or::new(SchnorrSigma::new(new_ec, point_g, n), SchnorrSigma::new(new_ec, point_g, n), n);
+    let keys = params.gen_key_pair();
+    let statement = (params.gen_key_pair().pk, keys.pk.clone());
+    let (mut zero_challenges, mut zero_responses) = (0, 0);
+    for _ in 0..400 {
+        let proof = sigma::prove(&or, &statement, &OrWitness::Right(keys.sk.clone()), &mut Transcript::new(b"or"));
+        assert!(sigma::verify(&or, &statement, &proof, &mut Transcript::new(b"or")));
+        zero_challenges += proof.response.challenge_left.is_zero() as usize;
+        zero_responses += proof.response.response_left.is_zero() as usize;
+    }
+    assert!(zero_challenges > 0 && zero_responses > 0);
+}
+
+#[test]
+fn simulated_transcripts_verify_for_every_challenge() {
+    let params = CurveParams::e17();
+    let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+    let schnorr = SchnorrSigma::new(new_ec, point_g, n);
+    let dleq = DleqSigma::new(new_ec, n);
+    let point_x = params.gen_key_pair().pk;
+    let statement = DleqStatement::new(new_ec, point_g, &params.mul_g(&BigInt::from(3)), &BigInt::from(5));
+    let mut challenges = Vec::new();
+    for _ in 0..400 {
+        challenges.push(schnorr.random_challenge());
+        challenges.push(dleq.random_challenge());
+    }
+    assert!(challenges.iter().any(|c| c.is_zero()));
+    assert!(challenges.iter().all(|c| c < n));
+    for challenge in &challenges {
+        let (commitment, response) = schnorr.simulate(&point_x, challenge);
+        assert!(schnorr.verify(&point_x, &commitment, challenge, &response));
+        let (commitment, response) = dleq.simulate(&statement, challenge);
+        assert!(dleq.verify(&statement, &commitment, challenge, &response));
+    }
+}
+
+#[test]
+fn schnorr_sigma_rejects_commitments_off_the_curve() {
+    let params = CurveParams::e17();
+    let (new_ec, point_g, n) = (&params.curve, &params.point_g, &params.n);
+    let schnorr = SchnorrSigma::new(new_ec, point_g, n);
+    let keys = params.gen_key_pair();
+    let (state, commitment) = schnorr.commit(&keys.pk, &keys.sk);
+    let challenge = schnorr.random_challenge();
+    let response = schnorr.respond(&keys.pk, &keys.sk, state, &challenge);
+    assert!(schnorr.verify(&keys.pk, &commitment, &challenge, &response));
+    let off_curve = Point::new(BigInt::from(1), BigInt::from(1));
+    for z in 0..11 {
+        assert!(!schnorr.verify(&keys.pk, &off_curve, &challenge, &BigInt::from(z)));
+    }
+}
@@ -0,0 +1,86 @@
+// statistical timing test in the style of dudect: one scalar multiplication is timed on two
+// classes of secrets, a fixed low-weight scalar and fresh random scalars, interleaved in
+// random order so that noise from the machine hits both classes alike. Welch's t-test then
+// compares the classes on the mean (first order) and on the squared deviation from the mean
+// (second order, i.e. the variance). |t| above 10 is a leak by any reasonable standard.
+// Timings of a debug build on a shared machine are too noisy to gate every `cargo test`, so
+// the tests are ignored by default: cargo test --release --test timing -- --ignored --nocapture
+use mpc_zk_paillier::arithmetic::basic_op::random_scalar;
+use mpc_zk_paillier::curve::ecc::{CurveParams, EcWei, Point};
+use num_bigint::BigInt;
+use rand::Rng;
+use std::time::Instant;
+
+const SAMPLES: usize = 300;
+const THRESHOLD: f64 = 10.0;
+
+fn measure(params: &CurveParams, mul: fn(&EcWei, &Point, &BigInt) -> Point) -> (Vec<f64>, Vec<f64>) {
+    let mut rng = rand::thread_rng();
+    let fixed = BigInt::from(3);
+    let (mut class_fixed, mut class_random) = (Vec::new(), Vec::new());
+    for _ in 0..2 * SAMPLES {
+        let is_fixed = rng.gen_bool(0.5);
+        let secret = if is_fixed { fixed.clone() } else { random_scalar(&params.n) };
+        let start = Instant::now();
+        std::hint::black_box(mul(&params.curve, &params.point_g, std::hint::black_box(&secret)));
+        let elapsed = start.elapsed().as_nanos() as f64;
+        if is_fixed {
+            class_fixed.push(elapsed);
+        } else {
+            class_random.push(elapsed);
+        }
+    }
+    (crop(class_fixed), crop(class_random))
+}
+
+// drops the slowest 10% of a class, which are dominated by interrupts and scheduling
+fn crop(mut samples: Vec<f64>) -> Vec<f64> {
+    samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    samples.truncate(samples.len() * 9 / 10);
+    samples
+}
+
+fn mean_var(samples: &[f64]) -> (f64, f64) {
+    let len = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / len;
+    let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (len - 1.0);
+    (mean, var)
+}
+
+fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+    let (mean_a, var_a) = mean_var(a);
+    let (mean_b, var_b) = mean_var(b);
+    (mean_a - mean_b) / (var_a / a.len() as f64 + var_b / b.len() as f64).sqrt()
+}
+
+// the second order test: Welch's t on (x - mean)^2 compares the variances of the classes
+fn welch_t_second_order(a: &[f64], b: &[f64]) -> f64 {
+    let centered = |samples: &[f64]| {
+        let (mean, _) = mean_var(samples);
+        samples.iter().map(|x| (x - mean).powi(2)).collect::<Vec<f64>>()
+    };
+    welch_t(&centered(a), &centered(b))
+}
+
+#[test]
+#[ignore = "timing measurement, run in release with --ignored"]
+fn montgomery_ladder_timing_does_not_depend_on_the_secret() {
+    let params = CurveParams::secp256k1();
+    let (class_fixed, class_random) = measure(&params, EcWei::scalar_mul_ct);
+    let t1 = welch_t(&class_fixed, &class_random);
+    let t2 = welch_t_second_order(&class_fixed, &class_random);
+    println!("scalar_mul_ct: t (mean) = {:.2}, t (variance) = {:.2}", t1, t2);
+    assert!(t1.abs() < THRESHOLD, "mean timing depends on the secret: t = {:.2}", t1);
+    assert!(t2.abs() < THRESHOLD, "timing variance depends on the secret: t = {:.2}", t2);
+}
+
+// the same harness must catch the leak of double-and-add, otherwise the test above proves nothing
+#[test]
+#[ignore = "timing measurement, run in release with --ignored"]
+fn double_and_add_timing_leaks_the_secret() {
+    let params = CurveParams::secp256k1();
+    let (class_fixed, class_random) = measure(&params, EcWei::scalar_mul);
+    let t1 = welch_t(&class_fixed, &class_random);
+    println!("scalar_mul: t (mean) = {:.2}", t1);
+    assert!(t1.abs() > THRESHOLD, "the harness did not detect the leak: t = {:.2}", t1);
+}
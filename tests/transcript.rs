@@ -0,0 +1,108 @@
+// the Fiat-Shamir transcript: challenges are deterministic, and change with every label,
+// message and the order in which they were appended
+use mpc_zk_paillier::curve::ecc::{CurveParams, Point};
+use mpc_zk_paillier::zk::transcript::Transcript;
+use num_bigint::BigInt;
+
+fn challenge(transcript: &mut Transcript) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    transcript.challenge_bytes(b"c", &mut bytes);
+    bytes
+}
+
+fn with_messages(messages: &[(&[u8], &[u8])]) -> [u8; 32] {
+    let mut transcript = Transcript::new(b"test");
+    for (label, message) in messages {
+        transcript.append_message(label, message);
+    }
+    challenge(&mut transcript)
+}
+
+#[test]
+fn the_same_messages_give_the_same_challenge() {
+    let reference = with_messages(&[(b"a", b"1"), (b"b", b"2")]);
+    assert_eq!(with_messages(&[(b"a", b"1"), (b"b", b"2")]), reference);
+    assert_ne!(with_messages(&[(b"a", b"1"), (b"b", b"3")]), reference);
+    assert_ne!(with_messages(&[(b"a", b"1"), (b"c", b"2")]), reference);
+    assert_ne!(with_messages(&[(b"b", b"2"), (b"a", b"1")]), reference);
+    assert_ne!(with_messages(&[(b"a", b"1")]), reference);
+    // lengths are absorbed, so moving bytes between label and message changes the challenge
+    assert_ne!(with_messages(&[(b"a1", b""), (b"b", b"2")]), reference);
+    assert_ne!(with_messages(&[(b"a", b"1b"), (b"", b"2")]), reference);
+
+    let mut other = Transcript::new(b"other");
+    other.append_message(b"a", b"1");
+    other.append_message(b"b", b"2");
+    assert_ne!(challenge(&mut other), reference);
+}
+
+#[test]
+fn challenges_ratchet_the_state() {
+    let mut transcript = Transcript::new(b"test");
+    let first = challenge(&mut transcript);
+    let second = challenge(&mut transcript);
+    assert_ne!(first, second);
+    // the label of a challenge is bound as well
+    let mut relabeled = Transcript::new(b"test");
+    let mut bytes = [0u8; 32];
+    relabeled.challenge_bytes(b"d", &mut bytes);
+    assert_ne!(bytes, first);
+}
+
+#[test]
+fn scalars_and_points_are_encoded_without_collisions() {
+    let scalar = |value: i64| {
+        let mut transcript = Transcript::new(b"test");
+        transcript.append_scalar(b"x", &BigInt::from(value));
+        challenge(&mut transcript)
+    };
+    assert_ne!(scalar(5), scalar(-5));
+    assert_eq!(scalar(5), scalar(5));
+
+    let point = |point: &Point| {
+        let mut transcript = Transcript::new(b"test");
+        transcript.append_point(b"P", point);
+        challenge(&mut transcript)
+    };
+    // both concatenate to 01 02 03: only the length prefixes of the coordinates tell them apart
+    let a = Point::new(BigInt::from(1), BigInt::from(0x0203));
+    let b = Point::new(BigInt::from(0x0102), BigInt::from(3));
+    assert_ne!(point(&a), point(&b));
+    assert_ne!(point(&Point::Infinity), point(&Point::new(BigInt::from(0), BigInt::from(0))));
+
+    let params = CurveParams::e17();
+    let points = |points: &[&Point]| {
+        let mut transcript = Transcript::new(b"test");
+        transcript.append_points(b"Ps", points);
+        challenge(&mut transcript)
+    };
+    let (g, h) = (params.point_g.clone(), params.mul_g(&BigInt::from(2)));
+    assert_ne!(points(&[&g, &h]), points(&[&h, &g]));
+    assert_ne!(points(&[&g]), points(&[&g, &g]));
+}
+
+#[test]
+fn challenge_scalars_are_below_the_modulus() {
+    let n = BigInt::from(11);
+    let mut transcript = Transcript::new(b"test");
+    let mut seen = [false; 11];
+    for _ in 0..500 {
+        let c = transcript.challenge_scalar(b"c", &n);
+        assert!(c >= BigInt::from(0) && c < n);
+        seen[usize::try_from(c).unwrap()] = true;
+    }
+    assert!(seen.iter().all(|&hit| hit));
+}
+
+#[test]
+fn forks_inherit_the_state_but_diverge_by_label() {
+    let mut transcript = Transcript::new(b"test");
+    transcript.append_message(b"a", b"1");
+    let mut left = transcript.fork(b"left");
+    let mut again = transcript.fork(b"left");
+    let mut right = transcript.fork(b"right");
+    let left_challenge = challenge(&mut left);
+    assert_eq!(challenge(&mut again), left_challenge);
+    assert_ne!(challenge(&mut right), left_challenge);
+    assert_ne!(challenge(&mut transcript), left_challenge);
+}
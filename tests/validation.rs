@@ -0,0 +1,70 @@
+// curve validation: singular curves are refused, standard curves pass, and each weakness of
+// a small curve shows up as its own finding
+use mpc_zk_paillier::curve::ecc::{Check, CurveParams, EcEdwards, EcMont, EcWei, Point, Severity, ValidationReport};
+use num_bigint::BigInt;
+
+fn curve(a: i64, b: i64, p: i64) -> EcWei {
+    EcWei::new(BigInt::from(a), BigInt::from(b), BigInt::from(p)).unwrap()
+}
+
+fn severity(report: &ValidationReport, check: Check) -> Severity {
+    report.finding(check).unwrap().severity
+}
+
+#[test]
+fn singular_curves_are_refused() {
+    // 4 (-3)^3 + 27 2^2 = 0: y^2 = (x - 1)^2 (x + 2) has a node
+    assert!(EcWei::new(BigInt::from(-3), BigInt::from(2), BigInt::from(17)).is_err());
+    assert!(EcWei::new(BigInt::from(0), BigInt::from(0), BigInt::from(17)).is_err());
+    assert!(EcMont::new(BigInt::from(2), BigInt::from(1), BigInt::from(17)).is_err());
+    assert!(EcEdwards::new(BigInt::from(3), BigInt::from(3), BigInt::from(17)).is_err());
+}
+
+#[test]
+fn standard_curves_are_secure() {
+    for params in [CurveParams::secp256k1(), CurveParams::secp256r1(), CurveParams::secp384r1()] {
+        let report = params.validate();
+        assert!(report.is_secure(), "{}:\n{}", params.name, report);
+    }
+    let report = CurveParams::secp256k1().validate();
+    assert!(report.finding(Check::JInvariant).unwrap().message.starts_with("j = 0"));
+}
+
+#[test]
+fn educational_curves_are_flagged() {
+    let report = CurveParams::e17().validate();
+    assert_eq!(report.worst(), Severity::Warning);
+    assert_eq!(severity(&report, Check::SubgroupOrder), Severity::Warning);
+    assert_eq!(severity(&report, Check::Generator), Severity::Info);
+    assert_eq!(severity(&CurveParams::e17_order_15().validate(), Check::Cofactor), Severity::Info);
+}
+
+#[test]
+fn weak_curves_are_reported() {
+    // #E = p = 23
+    let anomalous = curve(5, 3, 23).validate();
+    assert_eq!(severity(&anomalous, Check::Anomalous), Severity::Error);
+
+    // y^2 = x^3 + x is supersingular for p = 3 mod 4: #E = p + 1 and the embedding degree is 2
+    let supersingular = curve(1, 0, 1019).validate();
+    let finding = supersingular.finding(Check::EmbeddingDegree).unwrap();
+    assert_eq!(finding.severity, Severity::Warning);
+    assert!(finding.message.starts_with("embedding degree 2"));
+
+    let composite = curve(1, 1, 21).validate();
+    assert_eq!(severity(&composite, Check::FieldPrime), Severity::Error);
+    assert!(composite.finding(Check::SubgroupOrder).is_none());
+}
+
+#[test]
+fn wrong_domain_parameters_are_errors() {
+    let e17 = CurveParams::e17();
+    let wrong_h = CurveParams::new("e17-h2", e17.curve.clone(), e17.point_g.clone(), e17.n.clone(), BigInt::from(2));
+    assert_eq!(severity(&wrong_h.validate(), Check::Cofactor), Severity::Error);
+
+    let wrong_g = CurveParams::new("e17-g", e17.curve.clone(), Point::new(BigInt::from(1), BigInt::from(1)), e17.n.clone(), BigInt::from(1));
+    assert_eq!(severity(&wrong_g.validate(), Check::Generator), Severity::Error);
+
+    let wrong_n = CurveParams::new("e17-n", e17.curve.clone(), e17.point_g.clone(), BigInt::from(7), BigInt::from(1));
+    assert_eq!(severity(&wrong_n.validate(), Check::SubgroupOrder), Severity::Error);
+}